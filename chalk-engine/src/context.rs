@@ -189,6 +189,23 @@ pub trait ContextOps<C: Context>: Sized + Clone + Debug + AggregateOps<C> {
         infer: &mut C::InferenceTable,
     ) -> Result<Vec<C::ProgramClause>, Floundered>;
 
+    /// First relate the parameters according to `variance` (which may
+    /// require knowledge of the program, e.g. the variance of a struct's
+    /// parameters), then add the residual subgoals as new subgoals of the
+    /// ex-clause. Also add region constraints.
+    ///
+    /// If the parameters fail to unify, then `Error` is returned
+    // Used by: simplify
+    fn unify_parameters_into_ex_clause(
+        &self,
+        infer: &mut C::InferenceTable,
+        environment: &C::Environment,
+        variance: C::Variance,
+        a: &C::Parameter,
+        b: &C::Parameter,
+        ex_clause: &mut ExClause<C>,
+    ) -> Fallible<()>;
+
     /// Create an inference table for processing a new goal and instantiate that goal
     /// in that context, returning "all the pieces".
     ///
@@ -285,21 +302,6 @@ pub trait UnificationOps<C: Context> {
 
     // Used by: logic
     fn invert_goal(&mut self, value: &C::GoalInEnvironment) -> Option<C::GoalInEnvironment>;
-
-    /// First unify the parameters, then add the residual subgoals
-    /// as new subgoals of the ex-clause.
-    /// Also add region constraints.
-    ///
-    /// If the parameters fail to unify, then `Error` is returned
-    // Used by: simplify
-    fn unify_parameters_into_ex_clause(
-        &mut self,
-        environment: &C::Environment,
-        variance: C::Variance,
-        a: &C::Parameter,
-        b: &C::Parameter,
-        ex_clause: &mut ExClause<C>,
-    ) -> Fallible<()>;
 }
 
 /// "Truncation" (called "abstraction" in the papers referenced below)
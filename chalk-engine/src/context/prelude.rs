@@ -6,3 +6,4 @@ pub(crate) use super::ContextOps;
 pub(crate) use super::InferenceTable;
 pub(crate) use super::ResolventOps;
 pub(crate) use super::TruncateOps;
+pub(crate) use super::UnificationOps;
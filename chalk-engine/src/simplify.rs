@@ -9,7 +9,8 @@ impl<C: Context> Forest<C> {
     /// and negative HH goals. This operation may fail if the HH goal
     /// includes unifications that cannot be completed.
     pub(super) fn simplify_hh_goal(
-        infer: &mut dyn InferenceTable<C>,
+        context: &impl ContextOps<C>,
+        infer: &mut C::InferenceTable,
         subst: C::Substitution,
         initial_environment: C::Environment,
         initial_hh_goal: HhGoal<C>,
@@ -52,7 +53,8 @@ impl<C: Context> Forest<C> {
                             subgoal,
                         )));
                 }
-                HhGoal::Unify(variance, a, b) => infer.unify_parameters_into_ex_clause(
+                HhGoal::Unify(variance, a, b) => context.unify_parameters_into_ex_clause(
+                    infer,
                     &environment,
                     variance,
                     &a,
@@ -1,38 +1,55 @@
 use crate::error::ChalkError;
+use crate::include;
 use crate::lowering::LowerGoal;
 use crate::program::Program;
 use crate::query::{Lowering, LoweringDatabase};
 use chalk_ir::family::ChalkIr;
 use chalk_ir::tls;
 use chalk_ir::Canonical;
+use chalk_ir::ClosureId;
 use chalk_ir::ConstrainedSubst;
+use chalk_ir::EnumId;
 use chalk_ir::Goal;
 use chalk_ir::Identifier;
 use chalk_ir::ImplId;
 use chalk_ir::InEnvironment;
+use chalk_ir::OpaqueTyId;
 use chalk_ir::Parameter;
 use chalk_ir::ProgramClause;
 use chalk_ir::StructId;
 use chalk_ir::TraitId;
 use chalk_ir::TypeId;
 use chalk_ir::TypeKindId;
+use chalk_ir::TypeName;
 use chalk_ir::UCanonical;
+use chalk_ir::Variance;
 use chalk_rust_ir::AssociatedTyDatum;
 use chalk_rust_ir::AssociatedTyValue;
 use chalk_rust_ir::AssociatedTyValueId;
+use chalk_rust_ir::ClosureDatum;
+use chalk_rust_ir::CrateDatum;
+use chalk_rust_ir::EnumDatum;
 use chalk_rust_ir::ImplDatum;
+use chalk_rust_ir::LangItem;
+use chalk_rust_ir::OpaqueTyDatum;
 use chalk_rust_ir::StructDatum;
 use chalk_rust_ir::TraitDatum;
+use chalk_solve::coherence::SpecializationPriorities;
 use chalk_solve::RustIrDatabase;
 use chalk_solve::Solution;
 use chalk_solve::SolverChoice;
 use salsa::Database;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 #[salsa::database(Lowering)]
 #[derive(Debug, Default)]
 pub struct ChalkDatabase {
     runtime: salsa::Runtime<ChalkDatabase>,
+    /// The included files whose text we have set.
+    loaded_files: BTreeSet<PathBuf>,
 }
 
 impl Database for ChalkDatabase {
@@ -42,13 +59,63 @@ impl Database for ChalkDatabase {
 }
 
 impl ChalkDatabase {
+    /// Creates a database for the program `program_text`, whose includes
+    /// are resolved relative to the current directory.
     pub fn with(program_text: &str, solver_choice: SolverChoice) -> Self {
+        Self::with_dir(program_text, Path::new(""), solver_choice)
+    }
+
+    /// Creates a database for the program `program_text`, whose includes
+    /// are resolved relative to `program_dir`. The included files are
+    /// read from disk.
+    pub fn with_dir(program_text: &str, program_dir: &Path, solver_choice: SolverChoice) -> Self {
         let mut db = ChalkDatabase::default();
-        db.set_program_text(Arc::new(program_text.to_string()));
+        db.set_program_dir(program_dir.to_owned());
         db.set_solver_choice(solver_choice);
+        db.edit_program(program_text);
         db
     }
 
+    /// Replaces the program text, reading any files that it newly
+    /// includes. Use this rather than setting `program_text` directly,
+    /// which leaves those files unread.
+    pub fn edit_program(&mut self, program_text: &str) {
+        self.set_program_text(Arc::new(program_text.to_string()));
+        self.load_included_files();
+    }
+
+    /// Replaces the text of the included file at `path` (as resolved by
+    /// `include::resolve`). Only that file is parsed again; the program
+    /// is lowered again (as a whole) only if the items of the file
+    /// changed.
+    pub fn edit_file(&mut self, path: &Path, text: &str) {
+        self.set_file_text(path.to_owned(), Some(Arc::new(text.to_string())));
+        self.loaded_files.insert(path.to_owned());
+        self.load_included_files();
+    }
+
+    /// Reads the files that the program includes, directly or
+    /// indirectly, that we have not read yet.
+    fn load_included_files(&mut self) {
+        let mut pending = match self.parsed_program() {
+            Ok(program) => include::included_files(&program.items, &self.program_dir()),
+            Err(_) => return,
+        };
+        let mut visited = BTreeSet::new();
+        while let Some(path) = pending.pop() {
+            if !visited.insert(path.clone()) {
+                continue;
+            }
+            if self.loaded_files.insert(path.clone()) {
+                let text = fs::read_to_string(&path).ok().map(Arc::new);
+                self.set_file_text(path.clone(), text);
+            }
+            if let Ok(file) = self.parsed_file(path.clone()) {
+                pending.extend(include::included_files(&file.items, include::dir_of(&path)));
+            }
+        }
+    }
+
     pub fn with_program<R>(&self, op: impl FnOnce(&Program) -> R) -> R {
         let program = &self.checked_program().unwrap();
         tls::set_current_program(&program, || op(&program))
@@ -101,6 +168,22 @@ impl RustIrDatabase for ChalkDatabase {
         self.program_ir().unwrap().struct_datum(id)
     }
 
+    fn enum_datum(&self, id: EnumId) -> Arc<EnumDatum> {
+        self.program_ir().unwrap().enum_datum(id)
+    }
+
+    fn closure_datum(&self, id: ClosureId) -> Arc<ClosureDatum> {
+        self.program_ir().unwrap().closure_datum(id)
+    }
+
+    fn opaque_ty_data(&self, id: OpaqueTyId) -> Arc<OpaqueTyDatum> {
+        self.program_ir().unwrap().opaque_ty_data(id)
+    }
+
+    fn crate_datum(&self, krate: Identifier) -> Arc<CrateDatum> {
+        self.program_ir().unwrap().crate_datum(krate)
+    }
+
     fn impls_for_trait(&self, trait_id: TraitId, parameters: &[Parameter<ChalkIr>]) -> Vec<ImplId> {
         self.program_ir()
             .unwrap()
@@ -113,13 +196,28 @@ impl RustIrDatabase for ChalkDatabase {
             .local_impls_to_coherence_check(trait_id)
     }
 
-    fn impl_provided_for(&self, auto_trait_id: TraitId, struct_id: StructId) -> bool {
+    fn impl_provided_for(&self, auto_trait_id: TraitId, type_name: TypeName) -> bool {
         self.program_ir()
             .unwrap()
-            .impl_provided_for(auto_trait_id, struct_id)
+            .impl_provided_for(auto_trait_id, type_name)
+    }
+
+    fn well_known_trait_id(&self, lang_item: LangItem) -> Option<TraitId> {
+        self.program_ir().unwrap().well_known_trait_id(lang_item)
+    }
+
+    fn adt_variances(&self, adt_id: TypeKindId) -> Arc<Vec<Variance>> {
+        self.program_ir().unwrap().adt_variances(adt_id)
     }
 
     fn type_name(&self, id: TypeKindId) -> Identifier {
         self.program_ir().unwrap().type_name(id)
     }
+
+    fn specialization_priorities(
+        &self,
+        trait_id: TraitId,
+    ) -> Option<Arc<SpecializationPriorities>> {
+        self.coherence().ok()?.get(&trait_id).cloned()
+    }
 }
@@ -2,6 +2,7 @@ use chalk_parse::ast::{Identifier, Kind};
 use chalk_rust_ir::LangItem;
 use chalk_solve::coherence::CoherenceError;
 use chalk_solve::wf::WfError;
+use std::path::PathBuf;
 
 /// Wrapper type for the various errors that can occur during chalk
 /// processing.
@@ -36,6 +37,14 @@ impl From<CoherenceError> for ChalkError {
     }
 }
 
+impl From<IncludeError> for ChalkError {
+    fn from(value: IncludeError) -> Self {
+        ChalkError {
+            error_text: value.to_string(),
+        }
+    }
+}
+
 impl From<RustIrError> for ChalkError {
     fn from(value: RustIrError) -> Self {
         ChalkError {
@@ -56,7 +65,10 @@ impl std::error::Error for ChalkError {}
 pub enum RustIrError {
     InvalidTypeName(Identifier),
     InvalidLifetimeName(Identifier),
+    InvalidConstName(Identifier),
     DuplicateLangItem(LangItem),
+    InvalidRelaxedBound(Identifier),
+    MisplacedRelaxedBound(Identifier),
     NotTrait(Identifier),
     DuplicateOrShadowedParameters,
     AutoTraitAssociatedTypes(Identifier),
@@ -91,6 +103,11 @@ pub enum RustIrError {
         actual: Kind,
     },
     CannotApplyTypeParameter(Identifier),
+    TraitAliasImpl(Identifier),
+    CyclicTraitAlias(Identifier),
+    InvalidCrateName(Identifier),
+    DuplicateCrate(Identifier),
+    CyclicCrateDependency(Identifier),
 }
 
 impl std::fmt::Display for RustIrError {
@@ -98,7 +115,18 @@ impl std::fmt::Display for RustIrError {
         match self {
             RustIrError::InvalidTypeName(name) => write!(f, "invalid type name `{}`", name),
             RustIrError::InvalidLifetimeName(name) => write!(f, "invalid lifetime name `{}`", name),
+            RustIrError::InvalidConstName(name) => write!(f, "invalid const name `{}`", name),
             RustIrError::DuplicateLangItem(item) => write!(f, "duplicate lang item `{:?}`", item),
+            RustIrError::InvalidRelaxedBound(name) => write!(
+                f,
+                "relaxed bound `?{}` does not name the `Sized` lang item",
+                name
+            ),
+            RustIrError::MisplacedRelaxedBound(name) => write!(
+                f,
+                "`?{}` bounds are only allowed on the type parameters of an item",
+                name
+            ),
             RustIrError::NotTrait(name) => write!(
                 f,
                 "expected a trait, found `{}`, which is not a trait",
@@ -177,8 +205,42 @@ impl std::fmt::Display for RustIrError {
             RustIrError::CannotApplyTypeParameter(name) => {
                 write!(f, "cannot apply type parameter `{}`", name)
             }
+            RustIrError::TraitAliasImpl(name) => write!(
+                f,
+                "cannot implement trait alias `{}`; implement the traits it stands for instead",
+                name
+            ),
+            RustIrError::CyclicTraitAlias(name) => {
+                write!(f, "trait alias `{}` is defined in terms of itself", name)
+            }
+            RustIrError::InvalidCrateName(name) => write!(f, "invalid crate name `{}`", name),
+            RustIrError::DuplicateCrate(name) => write!(f, "duplicate crate `{}`", name),
+            RustIrError::CyclicCrateDependency(name) => {
+                write!(f, "crate `{}` depends on itself", name)
+            }
         }
     }
 }
 
 impl std::error::Error for RustIrError {}
+
+#[derive(Debug)]
+pub enum IncludeError {
+    UnreadableFile(PathBuf),
+    Cycle(PathBuf),
+}
+
+impl std::fmt::Display for IncludeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            IncludeError::UnreadableFile(path) => {
+                write!(f, "cannot read included file `{}`", path.display())
+            }
+            IncludeError::Cycle(path) => {
+                write!(f, "file `{}` includes itself", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for IncludeError {}
@@ -0,0 +1,101 @@
+//! Expansion of `include "file.chalk";` directives, which splice the
+//! items of another file into a program.
+
+use crate::error::{ChalkError, IncludeError};
+use crate::query::LoweringDatabase;
+use chalk_parse::ast::{CrateDefn, Item, ModuleDefn};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+/// The path of the file that `include "path";` refers to, when it
+/// appears in a file in the directory `dir`. The result is normalized,
+/// so that every path to the same file (not counting symbolic links) is
+/// the same.
+pub fn resolve(dir: &Path, path: &str) -> PathBuf {
+    let mut resolved = PathBuf::new();
+    for component in dir.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if resolved.file_name().is_some() => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
+        }
+    }
+    resolved
+}
+
+/// The directory that the includes of the file at `path` are resolved
+/// relative to.
+pub fn dir_of(path: &Path) -> &Path {
+    path.parent().unwrap_or_else(|| Path::new(""))
+}
+
+/// The files that `items`, found in a file in the directory `dir`,
+/// include directly.
+pub fn included_files(items: &[Item], dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    for item in items {
+        match item {
+            Item::Include(include) => files.push(resolve(dir, &include.path)),
+            Item::Module(d) => files.extend(included_files(&d.items, dir)),
+            Item::Crate(d) => files.extend(included_files(&d.items, dir)),
+            _ => {}
+        }
+    }
+    files
+}
+
+/// Replaces the includes in a program with the items of the files they
+/// include. Each file is included at most once, so that two files can
+/// both include a common prelude; a file that (indirectly) includes
+/// itself is an error.
+pub struct IncludeExpander<'db, DB> {
+    db: &'db DB,
+    /// The files whose includes we are expanding, outermost first.
+    stack: Vec<PathBuf>,
+    included: BTreeSet<PathBuf>,
+}
+
+impl<'db, DB: LoweringDatabase> IncludeExpander<'db, DB> {
+    pub fn new(db: &'db DB) -> Self {
+        IncludeExpander {
+            db,
+            stack: vec![],
+            included: BTreeSet::new(),
+        }
+    }
+
+    pub fn expand(&mut self, items: &[Item], dir: &Path) -> Result<Vec<Item>, ChalkError> {
+        let mut expanded = vec![];
+        for item in items {
+            match item {
+                Item::Include(include) => {
+                    let path = resolve(dir, &include.path);
+                    if self.stack.contains(&path) {
+                        return Err(IncludeError::Cycle(path).into());
+                    }
+                    if !self.included.insert(path.clone()) {
+                        continue;
+                    }
+
+                    let file = self.db.parsed_file(path.clone())?;
+                    self.stack.push(path.clone());
+                    expanded.extend(self.expand(&file.items, dir_of(&path))?);
+                    self.stack.pop();
+                }
+                Item::Module(d) => expanded.push(Item::Module(ModuleDefn {
+                    name: d.name,
+                    items: self.expand(&d.items, dir)?,
+                })),
+                Item::Crate(d) => expanded.push(Item::Crate(CrateDefn {
+                    name: d.name,
+                    dependencies: d.dependencies.clone(),
+                    items: self.expand(&d.items, dir)?,
+                })),
+                item => expanded.push(item.clone()),
+            }
+        }
+        Ok(expanded)
+    }
+}
@@ -6,6 +6,7 @@ extern crate chalk_macros;
 
 pub mod db;
 pub mod error;
+pub mod include;
 pub mod lowering;
 pub mod program;
 pub mod program_environment;
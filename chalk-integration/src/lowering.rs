@@ -1,9 +1,12 @@
 use chalk_ir::cast::{Cast, Caster};
 use chalk_ir::family::ChalkIr;
-use chalk_ir::{self, ImplId, StructId, TraitId, TypeId, TypeKindId};
+use chalk_ir::{
+    self, ClosureId, EnumId, ImplId, OpaqueTyId, StructId, TraitId, TypeId, TypeKindId,
+};
 use chalk_parse::ast::*;
 use chalk_rust_ir as rust_ir;
 use chalk_rust_ir::{Anonymize, AssociatedTyValueId, IntoWhereClauses, ToParameter};
+use chalk_solve::variance;
 use itertools::Itertools;
 use lalrpop_intern::intern;
 use std::collections::BTreeMap;
@@ -17,6 +20,10 @@ type TypeKinds = BTreeMap<chalk_ir::TypeKindId, rust_ir::TypeKind>;
 type AssociatedTyLookups = BTreeMap<(chalk_ir::TraitId, chalk_ir::Identifier), AssociatedTyLookup>;
 type AssociatedTyValueIds = BTreeMap<(chalk_ir::ImplId, chalk_ir::Identifier), AssociatedTyValueId>;
 type ParameterMap = BTreeMap<chalk_ir::ParameterKind<chalk_ir::Identifier>, usize>;
+type LangItems = BTreeMap<rust_ir::LangItem, chalk_ir::TraitId>;
+type TraitAliases =
+    BTreeMap<chalk_ir::TraitId, chalk_ir::Binders<Vec<chalk_ir::QuantifiedWhereClause<ChalkIr>>>>;
+type Crates = BTreeMap<chalk_ir::Identifier, Arc<rust_ir::CrateDatum>>;
 
 pub type LowerResult<T> = Result<T, RustIrError>;
 
@@ -25,6 +32,16 @@ struct Env<'k> {
     type_ids: &'k TypeIds,
     type_kinds: &'k TypeKinds,
     associated_ty_lookups: &'k AssociatedTyLookups,
+    lang_items: &'k LangItems,
+    /// The bounds that each trait alias stands for, with binders for
+    /// the alias's `Self` and parameters.
+    trait_aliases: &'k TraitAliases,
+    /// The path of the module whose items we are lowering, from the
+    /// outermost module inwards; empty at the top level.
+    module: &'k [chalk_ir::Identifier],
+    /// The crate whose items we are lowering, if any.
+    krate: Option<chalk_ir::Identifier>,
+    crates: &'k Crates,
     /// Parameter identifiers are used as keys, therefore
     /// all identifiers in an environment must be unique (no shadowing).
     parameter_map: ParameterMap,
@@ -58,6 +75,10 @@ enum LifetimeLookup {
     Parameter(usize),
 }
 
+enum ConstLookup {
+    Parameter(usize),
+}
+
 const SELF: &str = "Self";
 const FIXME_SELF: &str = "__FIXME_SELF__";
 
@@ -70,8 +91,8 @@ impl<'k> Env<'k> {
             return Ok(NameLookup::Parameter(*k));
         }
 
-        if let Some(id) = self.type_ids.get(&name.str) {
-            return Ok(NameLookup::Type(*id));
+        if let Some(id) = resolve_name(self.type_ids, self.module, name) {
+            return Ok(NameLookup::Type(id));
         }
 
         Err(RustIrError::InvalidTypeName(name))?
@@ -88,6 +109,23 @@ impl<'k> Env<'k> {
         Err(RustIrError::InvalidLifetimeName(name))?
     }
 
+    fn lookup_const(&self, name: Identifier) -> LowerResult<ConstLookup> {
+        if let Some(k) = self
+            .parameter_map
+            .get(&chalk_ir::ParameterKind::Const(name.str))
+        {
+            return Ok(ConstLookup::Parameter(*k));
+        }
+
+        Err(RustIrError::InvalidConstName(name))?
+    }
+
+    /// True if `name` refers to a const parameter that is in scope.
+    fn is_const_parameter(&self, name: Identifier) -> bool {
+        self.parameter_map
+            .contains_key(&chalk_ir::ParameterKind::Const(name.str))
+    }
+
     fn type_kind(&self, id: chalk_ir::TypeKindId) -> &rust_ir::TypeKind {
         &self.type_kinds[&id]
     }
@@ -117,6 +155,48 @@ impl<'k> Env<'k> {
         })
     }
 
+    /// Replaces each bound on a trait alias in `where_clauses` (the
+    /// bounds of a `dyn` or `impl` type) with the bounds the alias
+    /// stands for, so that e.g. `dyn Alias` has the same bounds as
+    /// `dyn Foo + Bar`.
+    fn expand_trait_aliases(
+        &self,
+        where_clauses: Vec<chalk_ir::QuantifiedWhereClause<ChalkIr>>,
+    ) -> Vec<chalk_ir::QuantifiedWhereClause<ChalkIr>> {
+        let mut expanded = vec![];
+        for qwc in where_clauses {
+            let components = match &qwc.value {
+                chalk_ir::WhereClause::Implemented(trait_ref) => self
+                    .trait_aliases
+                    .get(&trait_ref.trait_id)
+                    .map(|components| components.substitute(&trait_ref.parameters)),
+                _ => None,
+            };
+
+            match components {
+                // The components are quantified over their own binders
+                // as well as those of the bound on the alias.
+                Some(components) => expanded.extend(
+                    self.expand_trait_aliases(
+                        components
+                            .into_iter()
+                            .map(|component| chalk_ir::Binders {
+                                binders: component
+                                    .binders
+                                    .into_iter()
+                                    .chain(qwc.binders.iter().cloned())
+                                    .collect(),
+                                value: component.value,
+                            })
+                            .collect(),
+                    ),
+                ),
+                None => expanded.push(qwc),
+            }
+        }
+        expanded
+    }
+
     fn in_binders<I, T, OP>(&self, binders: I, op: OP) -> LowerResult<chalk_ir::Binders<T>>
     where
         I: IntoIterator<Item = chalk_ir::ParameterKind<chalk_ir::Identifier>>,
@@ -132,6 +212,108 @@ impl<'k> Env<'k> {
     }
 }
 
+/// Resolves the name of a type or trait used in the module `module`.
+/// Names (and paths like `foo::Bar`) are relative to the module they
+/// are used in; if no item by that name is found there, we look in each
+/// enclosing module in turn, up to the top level.
+fn resolve_name(
+    type_ids: &TypeIds,
+    module: &[chalk_ir::Identifier],
+    name: Identifier,
+) -> Option<chalk_ir::TypeKindId> {
+    (0..=module.len())
+        .rev()
+        .find_map(|depth| type_ids.get(&qualify(&module[..depth], name.str)))
+        .cloned()
+}
+
+/// The fully qualified name of the item `name` in the module `module`,
+/// e.g. `foo::bar::Baz`.
+fn qualify(module: &[chalk_ir::Identifier], name: chalk_ir::Identifier) -> chalk_ir::Identifier {
+    if module.is_empty() {
+        return name;
+    }
+    intern(
+        &module
+            .iter()
+            .chain(Some(&name))
+            .map(|segment| segment.to_string())
+            .join("::"),
+    )
+}
+
+/// An item of the program, along with the path of the module it is
+/// defined in and the crate it belongs to.
+struct ScopedItem<'a> {
+    module: Vec<chalk_ir::Identifier>,
+    krate: Option<chalk_ir::Identifier>,
+    item: &'a Item,
+}
+
+/// Collects the items in `items` and, recursively, in the modules and
+/// crates among them. The modules and crates themselves are not
+/// included; a crate `a` is a module named `a` for name resolution.
+fn flatten_items<'a>(
+    items: &'a [Item],
+    module: &[chalk_ir::Identifier],
+    krate: Option<chalk_ir::Identifier>,
+    flattened: &mut Vec<ScopedItem<'a>>,
+) {
+    for item in items {
+        match item {
+            Item::Module(d) => {
+                let mut inner = module.to_vec();
+                inner.push(d.name.str);
+                flatten_items(&d.items, &inner, krate, flattened);
+            }
+            Item::Crate(d) => {
+                let mut inner = module.to_vec();
+                inner.push(d.name.str);
+                flatten_items(&d.items, &inner, Some(d.name.str), flattened);
+            }
+            _ => flattened.push(ScopedItem {
+                module: module.to_vec(),
+                krate,
+                item,
+            }),
+        }
+    }
+}
+
+/// Lowers the `crate` blocks of the program, finding all the crates
+/// that each crate depends on, directly or indirectly.
+fn lower_crates(items: &[Item]) -> LowerResult<Crates> {
+    let mut defns = BTreeMap::new();
+    for item in items {
+        if let Item::Crate(d) = item {
+            if defns.insert(d.name.str, d).is_some() {
+                Err(RustIrError::DuplicateCrate(d.name))?;
+            }
+        }
+    }
+
+    let mut crates = BTreeMap::new();
+    for (&name, defn) in &defns {
+        let mut dependencies = vec![];
+        let mut stack = defn.dependencies.clone();
+        while let Some(dependency) = stack.pop() {
+            if dependency.str == name {
+                Err(RustIrError::CyclicCrateDependency(defn.name))?;
+            }
+            if dependencies.contains(&dependency.str) {
+                continue;
+            }
+            match defns.get(&dependency.str) {
+                Some(d) => stack.extend(d.dependencies.iter().cloned()),
+                None => Err(RustIrError::InvalidCrateName(dependency))?,
+            }
+            dependencies.push(dependency.str);
+        }
+        crates.insert(name, Arc::new(rust_ir::CrateDatum { name, dependencies }));
+    }
+    Ok(crates)
+}
+
 pub(crate) trait LowerProgram {
     /// Lowers from a Program AST to the internal IR for a program.
     fn lower(&self) -> LowerResult<LoweredProgram>;
@@ -146,15 +328,19 @@ impl LowerProgram for Program {
             chalk_ir::RawId { index: i }
         };
 
+        let mut items = vec![];
+        flatten_items(&self.items, &[], None, &mut items);
+        let crate_data = lower_crates(&self.items)?;
+
         // Make a vector mapping each thing in `items` to an id,
         // based just on its position:
-        let raw_ids: Vec<_> = self.items.iter().map(|_| next_item_id()).collect();
+        let raw_ids: Vec<_> = items.iter().map(|_| next_item_id()).collect();
 
         // Create ids for associated type declarations and values
         let mut associated_ty_lookups = BTreeMap::new();
         let mut associated_ty_value_ids = BTreeMap::new();
-        for (item, &raw_id) in self.items.iter().zip(&raw_ids) {
-            match item {
+        for (scoped, &raw_id) in items.iter().zip(&raw_ids) {
+            match scoped.item {
                 Item::TraitDefn(d) => {
                     if d.flags.auto && !d.assoc_ty_defns.is_empty() {
                         Err(RustIrError::AutoTraitAssociatedTypes(d.name))?;
@@ -182,36 +368,109 @@ impl LowerProgram for Program {
 
         let mut type_ids = BTreeMap::new();
         let mut type_kinds = BTreeMap::new();
-        for (item, &raw_id) in self.items.iter().zip(&raw_ids) {
-            let (k, id) = match *item {
+        let mut lang_items = BTreeMap::new();
+        for (scoped, &raw_id) in items.iter().zip(&raw_ids) {
+            if let Item::TraitDefn(TraitDefn {
+                lang_item: Some(lang_item),
+                ..
+            }) = scoped.item
+            {
+                let lang_item = lang_item.lower();
+                if lang_items.insert(lang_item, TraitId(raw_id)).is_some() {
+                    Err(RustIrError::DuplicateLangItem(lang_item))?;
+                }
+            }
+
+            let (mut k, id) = match *scoped.item {
                 Item::StructDefn(ref d) => (d.lower_type_kind()?, StructId(raw_id).into()),
+                Item::EnumDefn(ref d) => (d.lower_type_kind()?, EnumId(raw_id).into()),
+                Item::ClosureDefn(ref d) => (d.lower_type_kind()?, ClosureId(raw_id).into()),
+                Item::OpaqueTyDefn(ref d) => (d.lower_type_kind()?, OpaqueTyId(raw_id).into()),
                 Item::TraitDefn(ref d) => (d.lower_type_kind()?, TraitId(raw_id).into()),
+                Item::TraitAlias(ref d) => (d.lower_type_kind()?, TraitId(raw_id).into()),
                 Item::Impl(_) => continue,
                 Item::Clause(_) => continue,
+                Item::Module(_) | Item::Crate(_) | Item::Include(_) => unreachable!(),
             };
+            k.name = qualify(&scoped.module, k.name);
             type_ids.insert(k.name, id);
             type_kinds.insert(id, k);
         }
 
+        // Trait aliases are lowered first, since `dyn` and `impl` types
+        // anywhere in the program expand them. A `dyn` type in the bounds
+        // of an alias can therefore only expand aliases defined before it.
+        check_trait_alias_cycles(&items, &raw_ids, &type_ids)?;
+        let mut trait_alias_data = BTreeMap::new();
+        let mut trait_aliases = BTreeMap::new();
+        for (scoped, &raw_id) in items.iter().zip(&raw_ids) {
+            if let Item::TraitAlias(ref d) = *scoped.item {
+                let empty_env = Env {
+                    type_ids: &type_ids,
+                    type_kinds: &type_kinds,
+                    associated_ty_lookups: &associated_ty_lookups,
+                    lang_items: &lang_items,
+                    trait_aliases: &trait_aliases,
+                    module: &scoped.module,
+                    krate: scoped.krate,
+                    crates: &crate_data,
+                    parameter_map: BTreeMap::new(),
+                };
+                let trait_id = TraitId(raw_id);
+                let trait_datum = d.lower_trait(trait_id, &empty_env)?;
+                let components = trait_datum
+                    .binders
+                    .map_ref(|bound| bound.where_clauses.clone());
+                trait_aliases.insert(trait_id, components);
+                trait_alias_data.insert(trait_id, Arc::new(trait_datum));
+            }
+        }
+
         let mut struct_data = BTreeMap::new();
+        let mut enum_data = BTreeMap::new();
+        let mut closure_data = BTreeMap::new();
+        let mut opaque_ty_data = BTreeMap::new();
         let mut trait_data = BTreeMap::new();
         let mut impl_data = BTreeMap::new();
         let mut associated_ty_data = BTreeMap::new();
         let mut associated_ty_values = BTreeMap::new();
         let mut custom_clauses = Vec::new();
-        for (item, &raw_id) in self.items.iter().zip(&raw_ids) {
+        for (scoped, &raw_id) in items.iter().zip(&raw_ids) {
             let empty_env = Env {
                 type_ids: &type_ids,
                 type_kinds: &type_kinds,
                 associated_ty_lookups: &associated_ty_lookups,
+                lang_items: &lang_items,
+                trait_aliases: &trait_aliases,
+                module: &scoped.module,
+                krate: scoped.krate,
+                crates: &crate_data,
                 parameter_map: BTreeMap::new(),
             };
 
-            match *item {
+            match *scoped.item {
                 Item::StructDefn(ref d) => {
                     let struct_id = StructId(raw_id);
                     struct_data.insert(struct_id, Arc::new(d.lower_struct(struct_id, &empty_env)?));
                 }
+                Item::EnumDefn(ref d) => {
+                    let enum_id = EnumId(raw_id);
+                    enum_data.insert(enum_id, Arc::new(d.lower_enum(enum_id, &empty_env)?));
+                }
+                Item::ClosureDefn(ref d) => {
+                    let closure_id = ClosureId(raw_id);
+                    closure_data.insert(
+                        closure_id,
+                        Arc::new(d.lower_closure(closure_id, &empty_env)?),
+                    );
+                }
+                Item::OpaqueTyDefn(ref d) => {
+                    let opaque_ty_id = OpaqueTyId(raw_id);
+                    opaque_ty_data.insert(
+                        opaque_ty_id,
+                        Arc::new(d.lower_opaque_ty(opaque_ty_id, &empty_env)?),
+                    );
+                }
                 Item::TraitDefn(ref trait_defn) => {
                     let trait_id = TraitId(raw_id);
                     trait_data.insert(
@@ -249,6 +508,11 @@ impl LowerProgram for Program {
                             Ok(rust_ir::AssociatedTyDatumBound {
                                 bounds: assoc_ty_defn.bounds.lower(&env)?,
                                 where_clauses: assoc_ty_defn.where_clauses.lower(&env)?,
+                                default: assoc_ty_defn
+                                    .default
+                                    .as_ref()
+                                    .map(|ty| ty.lower(env))
+                                    .transpose()?,
                             })
                         })?;
 
@@ -263,6 +527,10 @@ impl LowerProgram for Program {
                         );
                     }
                 }
+                Item::TraitAlias(_) => {
+                    let trait_id = TraitId(raw_id);
+                    trait_data.insert(trait_id, trait_alias_data[&trait_id].clone());
+                }
                 Item::Impl(ref impl_defn) => {
                     let impl_id = ImplId(raw_id);
                     let impl_datum = Arc::new(impl_defn.lower_impl(
@@ -296,6 +564,11 @@ impl LowerProgram for Program {
                             Arc::new(rust_ir::AssociatedTyValue {
                                 impl_id,
                                 associated_ty_id: lookup.id,
+                                defaultness: if atv.default {
+                                    rust_ir::Defaultness::Default
+                                } else {
+                                    rust_ir::Defaultness::Final
+                                },
                                 value,
                             }),
                         );
@@ -304,20 +577,34 @@ impl LowerProgram for Program {
                 Item::Clause(ref clause) => {
                     custom_clauses.extend(clause.lower_clause(&empty_env)?);
                 }
+                Item::Module(_) | Item::Crate(_) | Item::Include(_) => unreachable!(),
             }
         }
 
-        let program = LoweredProgram {
+        let mut program = LoweredProgram {
             type_ids,
             type_kinds,
             struct_data,
+            enum_data,
+            closure_data,
+            opaque_ty_data,
+            crate_data,
             trait_data,
+            lang_items,
             impl_data,
             associated_ty_values,
             associated_ty_data,
+            adt_variances: BTreeMap::new(),
             custom_clauses,
         };
 
+        let adt_ids = program
+            .struct_data
+            .keys()
+            .map(|&id| TypeKindId::from(id))
+            .chain(program.enum_data.keys().map(|&id| id.into()));
+        program.adt_variances = variance::compute_adt_variances(&program, adt_ids);
+
         Ok(program)
     }
 }
@@ -378,6 +665,36 @@ impl LowerParameterMap for StructDefn {
     }
 }
 
+impl LowerParameterMap for EnumDefn {
+    fn synthetic_parameters(&self) -> Option<chalk_ir::ParameterKind<chalk_ir::Identifier>> {
+        None
+    }
+
+    fn declared_parameters(&self) -> &[ParameterKind] {
+        &self.parameter_kinds
+    }
+}
+
+impl LowerParameterMap for ClosureDefn {
+    fn synthetic_parameters(&self) -> Option<chalk_ir::ParameterKind<chalk_ir::Identifier>> {
+        None
+    }
+
+    fn declared_parameters(&self) -> &[ParameterKind] {
+        &self.parameter_kinds
+    }
+}
+
+impl LowerParameterMap for OpaqueTyDefn {
+    fn synthetic_parameters(&self) -> Option<chalk_ir::ParameterKind<chalk_ir::Identifier>> {
+        None
+    }
+
+    fn declared_parameters(&self) -> &[ParameterKind] {
+        &self.parameter_kinds
+    }
+}
+
 impl LowerParameterMap for Impl {
     fn synthetic_parameters(&self) -> Option<chalk_ir::ParameterKind<chalk_ir::Identifier>> {
         None
@@ -418,6 +735,16 @@ impl LowerParameterMap for TraitDefn {
     }
 }
 
+impl LowerParameterMap for TraitAliasDefn {
+    fn synthetic_parameters(&self) -> Option<chalk_ir::ParameterKind<chalk_ir::Identifier>> {
+        Some(chalk_ir::ParameterKind::Ty(intern(SELF)))
+    }
+
+    fn declared_parameters(&self) -> &[ParameterKind] {
+        &self.parameter_kinds
+    }
+}
+
 impl LowerParameterMap for Clause {
     fn synthetic_parameters(&self) -> Option<chalk_ir::ParameterKind<chalk_ir::Identifier>> {
         None
@@ -437,18 +764,82 @@ impl LowerParameterKind for ParameterKind {
         match *self {
             ParameterKind::Ty(ref n) => chalk_ir::ParameterKind::Ty(n.str),
             ParameterKind::Lifetime(ref n) => chalk_ir::ParameterKind::Lifetime(n.str),
+            ParameterKind::Const(ref n) => chalk_ir::ParameterKind::Const(n.str),
         }
     }
 }
 
-trait LowerWhereClauses {
+trait LowerWhereClauses: LowerParameterMap {
     fn where_clauses(&self) -> &[QuantifiedWhereClause];
+    fn parameter_bounds(&self) -> &[ParameterBounds];
 
+    /// The where clauses that the bounds written inline on the item's
+    /// parameters, like the `T: Clone` in `impl<T: Clone>`, are sugar for.
+    fn inline_where_clauses(&self) -> Vec<QuantifiedWhereClause> {
+        self.parameter_bounds()
+            .iter()
+            .flat_map(|b| desugar_bounds(Ty::Id { name: b.name }, &b.bounds))
+            .collect()
+    }
+
+    /// Lowers the where clauses of the item, adding an implicit `T: Sized`
+    /// bound for each of its type parameters `T`, unless the program has
+    /// no `Sized` lang item or the bound is relaxed with `T: ?Sized`.
     fn lower_where_clauses(
         &self,
         env: &Env,
     ) -> LowerResult<Vec<chalk_ir::QuantifiedWhereClause<ChalkIr>>> {
-        self.where_clauses().lower(env)
+        let mut where_clauses = vec![];
+        let mut relaxed = vec![];
+        let inline_where_clauses = self.inline_where_clauses();
+        for qwc in inline_where_clauses.iter().chain(self.where_clauses()) {
+            match &qwc.where_clause {
+                WhereClause::Relaxed { ty, trait_name } => {
+                    let is_type_parameter = |name: &Identifier| {
+                        self.declared_parameters().iter().any(|param| match param {
+                            ParameterKind::Ty(param) => param.str == name.str,
+                            _ => false,
+                        })
+                    };
+                    let param = match ty {
+                        Ty::Id { name }
+                            if qwc.parameter_kinds.is_empty() && is_type_parameter(name) =>
+                        {
+                            name.str
+                        }
+                        _ => Err(RustIrError::MisplacedRelaxedBound(*trait_name))?,
+                    };
+                    match env.lookup(*trait_name)? {
+                        NameLookup::Type(TypeKindId::TraitId(trait_id))
+                            if env.lang_items.get(&rust_ir::LangItem::Sized) == Some(&trait_id) => {
+                        }
+                        _ => Err(RustIrError::InvalidRelaxedBound(*trait_name))?,
+                    }
+                    relaxed.push(param);
+                }
+                _ => where_clauses.extend(qwc.lower(env)?),
+            }
+        }
+
+        if let Some(&sized_trait_id) = env.lang_items.get(&rust_ir::LangItem::Sized) {
+            for param in self.declared_parameters() {
+                match param {
+                    ParameterKind::Ty(name) if !relaxed.contains(&name.str) => {
+                        let ty = Ty::Id { name: *name }.lower(env)?;
+                        where_clauses.push(chalk_ir::Binders {
+                            binders: vec![],
+                            value: chalk_ir::WhereClause::Implemented(chalk_ir::TraitRef {
+                                trait_id: sized_trait_id,
+                                parameters: vec![ty.cast()],
+                            }),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(where_clauses)
     }
 }
 
@@ -469,6 +860,59 @@ impl LowerWhereClauses for StructDefn {
     fn where_clauses(&self) -> &[QuantifiedWhereClause] {
         &self.where_clauses
     }
+
+    fn parameter_bounds(&self) -> &[ParameterBounds] {
+        &self.parameter_bounds
+    }
+}
+
+impl LowerTypeKind for EnumDefn {
+    fn lower_type_kind(&self) -> LowerResult<rust_ir::TypeKind> {
+        Ok(rust_ir::TypeKind {
+            sort: rust_ir::TypeSort::Enum,
+            name: self.name.str,
+            binders: chalk_ir::Binders {
+                binders: self.all_parameters().anonymize(),
+                value: (),
+            },
+        })
+    }
+}
+
+impl LowerWhereClauses for EnumDefn {
+    fn where_clauses(&self) -> &[QuantifiedWhereClause] {
+        &self.where_clauses
+    }
+
+    fn parameter_bounds(&self) -> &[ParameterBounds] {
+        &self.parameter_bounds
+    }
+}
+
+impl LowerTypeKind for ClosureDefn {
+    fn lower_type_kind(&self) -> LowerResult<rust_ir::TypeKind> {
+        Ok(rust_ir::TypeKind {
+            sort: rust_ir::TypeSort::Closure,
+            name: self.name.str,
+            binders: chalk_ir::Binders {
+                binders: self.all_parameters().anonymize(),
+                value: (),
+            },
+        })
+    }
+}
+
+impl LowerTypeKind for OpaqueTyDefn {
+    fn lower_type_kind(&self) -> LowerResult<rust_ir::TypeKind> {
+        Ok(rust_ir::TypeKind {
+            sort: rust_ir::TypeSort::Opaque,
+            name: self.name.str,
+            binders: chalk_ir::Binders {
+                binders: self.all_parameters().anonymize(),
+                value: (),
+            },
+        })
+    }
 }
 
 impl LowerTypeKind for TraitDefn {
@@ -490,12 +934,112 @@ impl LowerWhereClauses for TraitDefn {
     fn where_clauses(&self) -> &[QuantifiedWhereClause] {
         &self.where_clauses
     }
+
+    fn parameter_bounds(&self) -> &[ParameterBounds] {
+        &self.parameter_bounds
+    }
+
+    /// Supertraits are bounds on `Self`: `trait Copy: Clone { }` is
+    /// `trait Copy where Self: Clone { }`.
+    fn inline_where_clauses(&self) -> Vec<QuantifiedWhereClause> {
+        let self_ty = Ty::Id {
+            name: Identifier {
+                str: intern(SELF),
+                span: self.name.span,
+            },
+        };
+        let supertraits = self
+            .supertraits
+            .iter()
+            .cloned()
+            .map(ParameterBound::Bound)
+            .collect::<Vec<_>>();
+        desugar_bounds(self_ty, &supertraits)
+            .into_iter()
+            .chain(
+                self.parameter_bounds
+                    .iter()
+                    .flat_map(|b| desugar_bounds(Ty::Id { name: b.name }, &b.bounds)),
+            )
+            .collect()
+    }
+}
+
+/// Desugars the bounds `bounds` on `ty` into where clauses, e.g.
+/// `T: Foo + ?Sized` into `T: Foo, T: ?Sized`.
+fn desugar_bounds(ty: Ty, bounds: &[ParameterBound]) -> Vec<QuantifiedWhereClause> {
+    bounds
+        .iter()
+        .map(|bound| match bound {
+            ParameterBound::Bound(qil) => {
+                let trait_ref = |trait_bound: &TraitBound| TraitRef {
+                    trait_name: trait_bound.trait_name,
+                    args: Some(Parameter::Ty(ty.clone()))
+                        .into_iter()
+                        .chain(trait_bound.args_no_self.iter().cloned())
+                        .collect(),
+                };
+                let where_clause = match &qil.bound {
+                    InlineBound::TraitBound(b) => WhereClause::Implemented {
+                        trait_ref: trait_ref(b),
+                    },
+                    InlineBound::ProjectionEqBound(b) => WhereClause::ProjectionEq {
+                        projection: ProjectionTy {
+                            trait_ref: trait_ref(&b.trait_bound),
+                            name: b.name,
+                            args: b.args.clone(),
+                        },
+                        ty: b.value.clone(),
+                    },
+                };
+                QuantifiedWhereClause {
+                    parameter_kinds: qil.parameter_kinds.clone(),
+                    where_clause,
+                }
+            }
+            ParameterBound::Relaxed(trait_name) => QuantifiedWhereClause {
+                parameter_kinds: vec![],
+                where_clause: WhereClause::Relaxed {
+                    ty: ty.clone(),
+                    trait_name: *trait_name,
+                },
+            },
+        })
+        .collect()
+}
+
+impl LowerTypeKind for TraitAliasDefn {
+    fn lower_type_kind(&self) -> LowerResult<rust_ir::TypeKind> {
+        let binders: Vec<_> = self.parameter_kinds.iter().map(|p| p.lower()).collect();
+        Ok(rust_ir::TypeKind {
+            sort: rust_ir::TypeSort::Trait,
+            name: self.name.str,
+            binders: chalk_ir::Binders {
+                binders: binders.anonymize(),
+                value: (),
+            },
+        })
+    }
+}
+
+impl LowerWhereClauses for TraitAliasDefn {
+    fn where_clauses(&self) -> &[QuantifiedWhereClause] {
+        &self.where_clauses
+    }
+
+    fn parameter_bounds(&self) -> &[ParameterBounds] {
+        &self.parameter_bounds
+    }
 }
 
 impl LowerWhereClauses for Impl {
     fn where_clauses(&self) -> &[QuantifiedWhereClause] {
         &self.where_clauses
     }
+
+    fn parameter_bounds(&self) -> &[ParameterBounds] {
+        &self.parameter_bounds
+    }
 }
 
 trait LowerWhereClauseVec {
@@ -534,6 +1078,27 @@ impl LowerWhereClause<chalk_ir::WhereClause<ChalkIr>> for WhereClause {
                 }),
                 chalk_ir::WhereClause::Implemented(projection.trait_ref.lower(env)?),
             ],
+            WhereClause::LifetimeOutlives { a, b } => {
+                vec![chalk_ir::WhereClause::LifetimeOutlives(
+                    chalk_ir::LifetimeOutlives {
+                        a: a.lower(env)?,
+                        b: b.lower(env)?,
+                    },
+                )]
+            }
+            WhereClause::TypeOutlives { ty, lifetime } => {
+                vec![chalk_ir::WhereClause::TypeOutlives(
+                    chalk_ir::TypeOutlives {
+                        ty: ty.lower(env)?,
+                        lifetime: lifetime.lower(env)?,
+                    },
+                )]
+            }
+            // Only the where clauses of an item may relax its implicit
+            // bounds; see `LowerWhereClauses`.
+            WhereClause::Relaxed { trait_name, .. } => {
+                Err(RustIrError::MisplacedRelaxedBound(*trait_name))?
+            }
         };
         Ok(where_clauses)
     }
@@ -585,9 +1150,16 @@ impl LowerDomainGoal for DomainGoal {
                 )]
             }
             DomainGoal::Compatible => vec![chalk_ir::DomainGoal::Compatible(())],
+            DomainGoal::Reveal => vec![chalk_ir::DomainGoal::Reveal(())],
             DomainGoal::DownstreamType { ty } => {
                 vec![chalk_ir::DomainGoal::DownstreamType(ty.lower(env)?)]
             }
+            DomainGoal::ObjectSafe { id } => match env.lookup(*id)? {
+                NameLookup::Type(TypeKindId::TraitId(trait_id)) => {
+                    vec![chalk_ir::DomainGoal::ObjectSafe(trait_id)]
+                }
+                NameLookup::Type(_) | NameLookup::Parameter(_) => Err(RustIrError::NotTrait(*id))?,
+            },
         };
         Ok(goals)
     }
@@ -615,6 +1187,11 @@ impl LowerLeafGoal for LeafGoal {
                 b: b.lower(env)?.cast(),
             }
             .cast()],
+            LeafGoal::Subtype { a, b } => vec![chalk_ir::SubtypeGoal {
+                a: a.lower(env)?,
+                b: b.lower(env)?,
+            }
+            .cast()],
         };
         Ok(goals)
     }
@@ -651,6 +1228,7 @@ impl LowerStructDefn for StructDefn {
         let flags = rust_ir::StructFlags {
             upstream: self.flags.upstream,
             fundamental: self.flags.fundamental,
+            krate: env.krate,
         };
 
         Ok(rust_ir::StructDatum {
@@ -661,6 +1239,114 @@ impl LowerStructDefn for StructDefn {
     }
 }
 
+trait LowerEnumDefn {
+    fn lower_enum(&self, enum_id: chalk_ir::EnumId, env: &Env) -> LowerResult<rust_ir::EnumDatum>;
+}
+
+impl LowerEnumDefn for EnumDefn {
+    fn lower_enum(&self, enum_id: chalk_ir::EnumId, env: &Env) -> LowerResult<rust_ir::EnumDatum> {
+        let binders = env.in_binders(self.all_parameters(), |env| {
+            let variants = self
+                .variants
+                .iter()
+                .map(|v| {
+                    let fields: LowerResult<_> = v.fields.iter().map(|f| f.ty.lower(env)).collect();
+                    Ok(rust_ir::EnumVariant {
+                        name: v.name.str,
+                        fields: fields?,
+                    })
+                })
+                .collect::<LowerResult<_>>()?;
+            let where_clauses = self.lower_where_clauses(env)?;
+
+            Ok(rust_ir::EnumDatumBound {
+                variants,
+                where_clauses,
+            })
+        })?;
+
+        let flags = rust_ir::EnumFlags {
+            upstream: self.flags.upstream,
+            krate: env.krate,
+        };
+
+        Ok(rust_ir::EnumDatum {
+            id: enum_id,
+            binders,
+            flags,
+        })
+    }
+}
+
+trait LowerClosureDefn {
+    fn lower_closure(
+        &self,
+        closure_id: chalk_ir::ClosureId,
+        env: &Env,
+    ) -> LowerResult<rust_ir::ClosureDatum>;
+}
+
+impl LowerClosureDefn for ClosureDefn {
+    fn lower_closure(
+        &self,
+        closure_id: chalk_ir::ClosureId,
+        env: &Env,
+    ) -> LowerResult<rust_ir::ClosureDatum> {
+        let binders = env.in_binders(self.all_parameters(), |env| {
+            let inputs: LowerResult<_> = self.argument_types.iter().map(|t| t.lower(env)).collect();
+            let upvars: LowerResult<_> = self.upvars.iter().map(|t| t.lower(env)).collect();
+
+            Ok(rust_ir::ClosureDatumBound {
+                inputs: inputs?,
+                output: self.return_type.lower(env)?,
+                upvars: upvars?,
+            })
+        })?;
+
+        let kind = match self.kind {
+            ClosureKind::Fn => rust_ir::ClosureKind::Fn,
+            ClosureKind::FnMut => rust_ir::ClosureKind::FnMut,
+            ClosureKind::FnOnce => rust_ir::ClosureKind::FnOnce,
+        };
+
+        Ok(rust_ir::ClosureDatum {
+            id: closure_id,
+            binders,
+            kind,
+            krate: env.krate,
+        })
+    }
+}
+
+trait LowerOpaqueTyDefn {
+    fn lower_opaque_ty(
+        &self,
+        opaque_ty_id: chalk_ir::OpaqueTyId,
+        env: &Env,
+    ) -> LowerResult<rust_ir::OpaqueTyDatum>;
+}
+
+impl LowerOpaqueTyDefn for OpaqueTyDefn {
+    fn lower_opaque_ty(
+        &self,
+        opaque_ty_id: chalk_ir::OpaqueTyId,
+        env: &Env,
+    ) -> LowerResult<rust_ir::OpaqueTyDatum> {
+        let binders = env.in_binders(self.all_parameters(), |env| {
+            Ok(rust_ir::OpaqueTyDatumBound {
+                bounds: self.bounds.lower(env)?,
+                hidden_ty: self.ty.lower(env)?,
+            })
+        })?;
+
+        Ok(rust_ir::OpaqueTyDatum {
+            id: opaque_ty_id,
+            binders,
+            krate: env.krate,
+        })
+    }
+}
+
 trait LowerTraitRef {
     fn lower(&self, env: &Env) -> LowerResult<chalk_ir::TraitRef<ChalkIr>>;
 }
@@ -839,17 +1525,39 @@ impl LowerImplType for ImplType {
 }
 
 trait LowerTraitFlags {
-    fn lower(&self) -> rust_ir::TraitFlags;
+    fn lower(&self, env: &Env) -> rust_ir::TraitFlags;
 }
 
 impl LowerTraitFlags for TraitFlags {
-    fn lower(&self) -> rust_ir::TraitFlags {
+    fn lower(&self, env: &Env) -> rust_ir::TraitFlags {
         rust_ir::TraitFlags {
             auto: self.auto,
             marker: self.marker,
             upstream: self.upstream,
             fundamental: self.fundamental,
             non_enumerable: self.non_enumerable,
+            krate: env.krate,
+            alias: false,
+        }
+    }
+}
+
+trait LowerLangItem {
+    fn lower(&self) -> rust_ir::LangItem;
+}
+
+impl LowerLangItem for LangItem {
+    fn lower(&self) -> rust_ir::LangItem {
+        match self {
+            LangItem::Sized => rust_ir::LangItem::Sized,
+            LangItem::Copy => rust_ir::LangItem::Copy,
+            LangItem::Clone => rust_ir::LangItem::Clone,
+            LangItem::Drop => rust_ir::LangItem::Drop,
+            LangItem::FnOnce => rust_ir::LangItem::FnOnce,
+            LangItem::FnMut => rust_ir::LangItem::FnMut,
+            LangItem::Fn => rust_ir::LangItem::Fn,
+            LangItem::Send => rust_ir::LangItem::Send,
+            LangItem::Sync => rust_ir::LangItem::Sync,
         }
     }
 }
@@ -935,11 +1643,13 @@ impl LowerTy for Ty {
                 // FIXME: Figure out a proper name for this type parameter
                 Some(chalk_ir::ParameterKind::Ty(intern(FIXME_SELF))),
                 |env| {
-                    Ok(bounds
-                        .lower(env)?
-                        .iter()
-                        .flat_map(|qil| qil.into_where_clauses(chalk_ir::Ty::BoundVar(0)))
-                        .collect())
+                    Ok(env.expand_trait_aliases(
+                        bounds
+                            .lower(env)?
+                            .iter()
+                            .flat_map(|qil| qil.into_where_clauses(chalk_ir::Ty::BoundVar(0)))
+                            .collect(),
+                    ))
                 },
             )?)),
 
@@ -947,11 +1657,13 @@ impl LowerTy for Ty {
                 // FIXME: Figure out a proper name for this type parameter
                 Some(chalk_ir::ParameterKind::Ty(intern(FIXME_SELF))),
                 |env| {
-                    Ok(bounds
-                        .lower(env)?
-                        .iter()
-                        .flat_map(|qil| qil.into_where_clauses(chalk_ir::Ty::BoundVar(0)))
-                        .collect())
+                    Ok(env.expand_trait_aliases(
+                        bounds
+                            .lower(env)?
+                            .iter()
+                            .flat_map(|qil| qil.into_where_clauses(chalk_ir::Ty::BoundVar(0)))
+                            .collect(),
+                    ))
                 },
             )?)),
 
@@ -975,7 +1687,7 @@ impl LowerTy for Ty {
                     .map(|t| Ok(t.lower(env)?))
                     .collect::<LowerResult<Vec<_>>>()?;
 
-                for (param, arg) in k.binders.binders.iter().zip(args.iter()) {
+                for (param, arg) in k.binders.binders.iter().zip(parameters.iter()) {
                     if param.kind() != arg.kind() {
                         Err(RustIrError::IncorrectParameterKind {
                             identifier: name,
@@ -1010,6 +1722,108 @@ impl LowerTy for Ty {
                 };
                 Ok(chalk_ir::Ty::ForAll(Box::new(quantified_ty)))
             }
+
+            Ty::Ref {
+                lifetime,
+                mutability,
+                ref ty,
+            } => Ok(chalk_ir::Ty::Apply(chalk_ir::ApplicationTy {
+                name: chalk_ir::TypeName::Ref(mutability.lower()),
+                parameters: vec![lifetime.lower(env)?.cast(), ty.lower(env)?.cast()],
+            })),
+
+            Ty::Raw { mutability, ref ty } => Ok(chalk_ir::Ty::Apply(chalk_ir::ApplicationTy {
+                name: chalk_ir::TypeName::Raw(mutability.lower()),
+                parameters: vec![ty.lower(env)?.cast()],
+            })),
+
+            Ty::Tuple { ref types } => Ok(chalk_ir::Ty::Apply(chalk_ir::ApplicationTy {
+                name: chalk_ir::TypeName::Tuple(types.len()),
+                parameters: types
+                    .iter()
+                    .map(|t| Ok(t.lower(env)?.cast()))
+                    .collect::<LowerResult<Vec<_>>>()?,
+            })),
+
+            Ty::Fn { ref args, ref ret } => Ok(chalk_ir::Ty::Apply(chalk_ir::ApplicationTy {
+                name: chalk_ir::TypeName::FnPtr(args.len()),
+                parameters: args
+                    .iter()
+                    .chain(Some(&**ret))
+                    .map(|t| Ok(t.lower(env)?.cast()))
+                    .collect::<LowerResult<Vec<_>>>()?,
+            })),
+
+            Ty::Slice { ref ty } => Ok(chalk_ir::Ty::Apply(chalk_ir::ApplicationTy {
+                name: chalk_ir::TypeName::Slice,
+                parameters: vec![ty.lower(env)?.cast()],
+            })),
+
+            Ty::Array { ref ty, ref len } => Ok(chalk_ir::Ty::Apply(chalk_ir::ApplicationTy {
+                name: chalk_ir::TypeName::Array,
+                parameters: vec![ty.lower(env)?.cast(), len.lower(env)?.cast()],
+            })),
+
+            Ty::Str => Ok(chalk_ir::Ty::Apply(chalk_ir::ApplicationTy {
+                name: chalk_ir::TypeName::Str,
+                parameters: vec![],
+            })),
+
+            Ty::Scalar { ty } => Ok(chalk_ir::Ty::Apply(chalk_ir::ApplicationTy {
+                name: chalk_ir::TypeName::Scalar(ty.lower()),
+                parameters: vec![],
+            })),
+
+            Ty::Never => Ok(chalk_ir::Ty::Apply(chalk_ir::ApplicationTy {
+                name: chalk_ir::TypeName::Never,
+                parameters: vec![],
+            })),
+        }
+    }
+}
+
+trait LowerMutability {
+    fn lower(&self) -> chalk_ir::Mutability;
+}
+
+impl LowerMutability for Mutability {
+    fn lower(&self) -> chalk_ir::Mutability {
+        match self {
+            Mutability::Mut => chalk_ir::Mutability::Mut,
+            Mutability::Not => chalk_ir::Mutability::Not,
+        }
+    }
+}
+
+trait LowerScalar {
+    fn lower(&self) -> chalk_ir::Scalar;
+}
+
+impl LowerScalar for ScalarType {
+    fn lower(&self) -> chalk_ir::Scalar {
+        match *self {
+            ScalarType::Bool => chalk_ir::Scalar::Bool,
+            ScalarType::Char => chalk_ir::Scalar::Char,
+            ScalarType::Int(int) => chalk_ir::Scalar::Int(match int {
+                IntTy::Isize => chalk_ir::IntTy::Isize,
+                IntTy::I8 => chalk_ir::IntTy::I8,
+                IntTy::I16 => chalk_ir::IntTy::I16,
+                IntTy::I32 => chalk_ir::IntTy::I32,
+                IntTy::I64 => chalk_ir::IntTy::I64,
+                IntTy::I128 => chalk_ir::IntTy::I128,
+            }),
+            ScalarType::Uint(uint) => chalk_ir::Scalar::Uint(match uint {
+                UintTy::Usize => chalk_ir::UintTy::Usize,
+                UintTy::U8 => chalk_ir::UintTy::U8,
+                UintTy::U16 => chalk_ir::UintTy::U16,
+                UintTy::U32 => chalk_ir::UintTy::U32,
+                UintTy::U64 => chalk_ir::UintTy::U64,
+                UintTy::U128 => chalk_ir::UintTy::U128,
+            }),
+            ScalarType::Float(float) => chalk_ir::Scalar::Float(match float {
+                FloatTy::F32 => chalk_ir::FloatTy::F32,
+                FloatTy::F64 => chalk_ir::FloatTy::F64,
+            }),
         }
     }
 }
@@ -1021,8 +1835,29 @@ trait LowerParameter {
 impl LowerParameter for Parameter {
     fn lower(&self, env: &Env) -> LowerResult<chalk_ir::Parameter<ChalkIr>> {
         match *self {
+            // A bare name like the `N` in `Foo<N>` parses as a type, but
+            // may refer to a const parameter.
+            Parameter::Ty(Ty::Id { name }) if env.is_const_parameter(name) => {
+                Ok(Const::Id { name }.lower(env)?.cast())
+            }
             Parameter::Ty(ref t) => Ok(t.lower(env)?.cast()),
             Parameter::Lifetime(ref l) => Ok(l.lower(env)?.cast()),
+            Parameter::Const(ref c) => Ok(c.lower(env)?.cast()),
+        }
+    }
+}
+
+trait LowerConst {
+    fn lower(&self, env: &Env) -> LowerResult<chalk_ir::Const<ChalkIr>>;
+}
+
+impl LowerConst for Const {
+    fn lower(&self, env: &Env) -> LowerResult<chalk_ir::Const<ChalkIr>> {
+        match *self {
+            Const::Id { name } => match env.lookup_const(name)? {
+                ConstLookup::Parameter(d) => Ok(chalk_ir::Const::BoundVar(d)),
+            },
+            Const::Value(value) => Ok(chalk_ir::Const::Value(value)),
         }
     }
 }
@@ -1064,6 +1899,10 @@ impl LowerImpl for Impl {
             let trait_ref = self.trait_ref.lower(env)?;
             debug!("trait_ref = {:?}", trait_ref);
 
+            if env.trait_aliases.contains_key(&trait_ref.trait_id) {
+                Err(RustIrError::TraitAliasImpl(self.trait_ref.trait_name))?;
+            }
+
             if !polarity.is_positive() && !self.assoc_ty_values.is_empty() {
                 Err(RustIrError::NegativeImplAssociatedValues(
                     self.trait_ref.trait_name,
@@ -1093,6 +1932,7 @@ impl LowerImpl for Impl {
             polarity,
             binders: binders,
             impl_type: self.impl_type.lower(),
+            krate: empty_env.krate,
             associated_ty_value_ids,
         })
     }
@@ -1123,6 +1963,7 @@ impl LowerClause for Clause {
                 .map(|consequence| chalk_ir::ProgramClauseImplication {
                     consequence,
                     conditions: conditions.clone(),
+                    constraints: vec![],
                 })
                 .collect::<Vec<_>>();
             Ok(implications)
@@ -1165,7 +2006,7 @@ impl LowerTrait for TraitDefn {
                 if all_parameters_len > 1 {
                     Err(RustIrError::AutoTraitParameters(self.name))?;
                 }
-                if !self.where_clauses.is_empty() {
+                if !self.where_clauses.is_empty() || !self.supertraits.is_empty() {
                     Err(RustIrError::AutoTraitWhereClauses(self.name))?;
                 }
             }
@@ -1184,12 +2025,100 @@ impl LowerTrait for TraitDefn {
         Ok(rust_ir::TraitDatum {
             id: trait_id,
             binders: binders,
-            flags: self.flags.lower(),
+            flags: self.flags.lower(env),
             associated_ty_ids,
         })
     }
 }
 
+impl LowerTrait for TraitAliasDefn {
+    fn lower_trait(
+        &self,
+        trait_id: chalk_ir::TraitId,
+        env: &Env,
+    ) -> LowerResult<rust_ir::TraitDatum> {
+        // An alias stands for its bounds, applied to `Self`, together
+        // with its where clauses.
+        let binders = env.in_binders(self.all_parameters(), |env| {
+            let mut where_clauses: Vec<_> = self
+                .bounds
+                .lower(env)?
+                .iter()
+                .flat_map(|qil| qil.into_where_clauses(chalk_ir::Ty::BoundVar(0)))
+                .collect();
+            where_clauses.extend(self.lower_where_clauses(env)?);
+            Ok(rust_ir::TraitDatumBound { where_clauses })
+        })?;
+
+        Ok(rust_ir::TraitDatum {
+            id: trait_id,
+            binders,
+            flags: rust_ir::TraitFlags {
+                auto: false,
+                marker: false,
+                upstream: false,
+                fundamental: false,
+                non_enumerable: false,
+                krate: env.krate,
+                alias: true,
+            },
+            associated_ty_ids: vec![],
+        })
+    }
+}
+
+/// Reports an error if a trait alias is defined in terms of itself,
+/// directly or through other aliases, as its expansion would never end.
+fn check_trait_alias_cycles(
+    items: &[ScopedItem<'_>],
+    raw_ids: &[chalk_ir::RawId],
+    type_ids: &TypeIds,
+) -> LowerResult<()> {
+    let aliases: BTreeMap<_, _> = items
+        .iter()
+        .zip(raw_ids)
+        .filter_map(|(scoped, &raw_id)| match scoped.item {
+            Item::TraitAlias(d) => Some((TypeKindId::from(TraitId(raw_id)), (scoped, d))),
+            _ => None,
+        })
+        .collect();
+
+    fn visit<'a>(
+        id: chalk_ir::TypeKindId,
+        aliases: &BTreeMap<chalk_ir::TypeKindId, (&'a ScopedItem<'a>, &'a TraitAliasDefn)>,
+        type_ids: &TypeIds,
+        stack: &mut Vec<chalk_ir::TypeKindId>,
+        done: &mut Vec<chalk_ir::TypeKindId>,
+    ) -> LowerResult<()> {
+        let (scoped, alias) = aliases[&id];
+        if done.contains(&id) {
+            return Ok(());
+        }
+        if stack.contains(&id) {
+            Err(RustIrError::CyclicTraitAlias(alias.name))?;
+        }
+
+        stack.push(id);
+        for trait_name in alias.trait_names() {
+            match resolve_name(type_ids, &scoped.module, trait_name) {
+                Some(component) if aliases.contains_key(&component) => {
+                    visit(component, aliases, type_ids, stack, done)?
+                }
+                _ => {}
+            }
+        }
+        stack.pop();
+        done.push(id);
+        Ok(())
+    }
+
+    let mut done = vec![];
+    for &id in aliases.keys() {
+        visit(id, &aliases, type_ids, &mut vec![], &mut done)?;
+    }
+    Ok(())
+}
+
 pub trait LowerGoal<A> {
     fn lower(&self, arg: &A) -> LowerResult<Box<chalk_ir::Goal<ChalkIr>>>;
 }
@@ -1212,10 +2141,25 @@ impl LowerGoal<LoweredProgram> for Goal {
             })
             .collect();
 
+        let trait_aliases: TraitAliases = program
+            .trait_data
+            .iter()
+            .filter(|(_, datum)| datum.is_alias())
+            .map(|(&trait_id, datum)| {
+                let components = datum.binders.map_ref(|bound| bound.where_clauses.clone());
+                (trait_id, components)
+            })
+            .collect();
+
         let env = Env {
             type_ids: &program.type_ids,
             type_kinds: &program.type_kinds,
             associated_ty_lookups: &associated_ty_lookups,
+            lang_items: &program.lang_items,
+            trait_aliases: &trait_aliases,
+            module: &[],
+            krate: None,
+            crates: &program.crate_data,
             parameter_map: BTreeMap::new(),
         };
 
@@ -1249,6 +2193,11 @@ impl<'k> LowerGoal<Env<'k>> for Goal {
             ))),
             Goal::Not(g) => Ok(Box::new(chalk_ir::Goal::Not(g.lower(env)?))),
             Goal::Compatible(g) => Ok(Box::new(g.lower(env)?.compatible())),
+            Goal::Reveal(g) => Ok(Box::new(g.lower(env)?.reveal())),
+            Goal::InCrate(krate, g) => match env.crates.get(&krate.str) {
+                Some(crate_datum) => Ok(Box::new(crate_datum.perspective(*g.lower(env)?))),
+                None => Err(RustIrError::InvalidCrateName(*krate))?,
+            },
             Goal::Leaf(leaf) => {
                 // A where clause can lower to multiple leaf goals; wrap these in Goal::And.
                 let leaves = leaf.lower(env)?.into_iter().map(chalk_ir::Goal::Leaf);
@@ -1315,6 +2264,7 @@ impl Kinded for ParameterKind {
         match *self {
             ParameterKind::Ty(_) => Kind::Ty,
             ParameterKind::Lifetime(_) => Kind::Lifetime,
+            ParameterKind::Const(_) => Kind::Const,
         }
     }
 }
@@ -1324,15 +2274,17 @@ impl Kinded for Parameter {
         match *self {
             Parameter::Ty(_) => Kind::Ty,
             Parameter::Lifetime(_) => Kind::Lifetime,
+            Parameter::Const(_) => Kind::Const,
         }
     }
 }
 
-impl<T, L> Kinded for chalk_ir::ParameterKind<T, L> {
+impl<T, L, C> Kinded for chalk_ir::ParameterKind<T, L, C> {
     fn kind(&self) -> Kind {
         match *self {
             chalk_ir::ParameterKind::Ty(_) => Kind::Ty,
             chalk_ir::ParameterKind::Lifetime(_) => Kind::Lifetime,
+            chalk_ir::ParameterKind::Const(_) => Kind::Const,
         }
     }
 }
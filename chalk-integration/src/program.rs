@@ -3,13 +3,14 @@ use chalk_ir::debug::Angle;
 use chalk_ir::family::ChalkIr;
 use chalk_ir::tls;
 use chalk_ir::{
-    Identifier, ImplId, Parameter, ProgramClause, ProjectionTy, StructId, TraitId, Ty, TypeId,
-    TypeKindId, TypeName,
+    ClosureId, EnumId, Identifier, ImplId, OpaqueTyId, Parameter, ProgramClause, ProjectionTy,
+    StructId, TraitId, Ty, TypeId, TypeKindId, TypeName, Variance,
 };
 use chalk_rust_ir::{
-    AssociatedTyDatum, AssociatedTyValue, AssociatedTyValueId, ImplDatum, ImplType, StructDatum,
-    TraitDatum, TypeKind,
+    AssociatedTyDatum, AssociatedTyValue, AssociatedTyValueId, ClosureDatum, CrateDatum, EnumDatum,
+    ImplDatum, ImplType, LangItem, OpaqueTyDatum, StructDatum, TraitDatum, TypeKind,
 };
+use chalk_solve::coherence::SpecializationPriorities;
 use chalk_solve::split::Split;
 use chalk_solve::RustIrDatabase;
 use std::collections::BTreeMap;
@@ -21,12 +22,24 @@ pub struct Program {
     /// From type-name to item-id. Used during lowering only.
     pub type_ids: BTreeMap<Identifier, TypeKindId>,
 
-    /// For each struct/trait:
+    /// For each struct/enum/closure/trait:
     pub type_kinds: BTreeMap<TypeKindId, TypeKind>,
 
     /// For each struct:
     pub struct_data: BTreeMap<StructId, Arc<StructDatum>>,
 
+    /// For each enum:
+    pub enum_data: BTreeMap<EnumId, Arc<EnumDatum>>,
+
+    /// For each closure type:
+    pub closure_data: BTreeMap<ClosureId, Arc<ClosureDatum>>,
+
+    /// For each named opaque type:
+    pub opaque_ty_data: BTreeMap<OpaqueTyId, Arc<OpaqueTyDatum>>,
+
+    /// For each crate, in programs made up of several crates:
+    pub crate_data: BTreeMap<Identifier, Arc<CrateDatum>>,
+
     /// For each impl:
     pub impl_data: BTreeMap<ImplId, Arc<ImplDatum>>,
 
@@ -36,9 +49,15 @@ pub struct Program {
     /// For each trait:
     pub trait_data: BTreeMap<TraitId, Arc<TraitDatum>>,
 
+    /// For each trait marked with `#[lang(..)]`:
+    pub lang_items: BTreeMap<LangItem, TraitId>,
+
     /// For each associated ty declaration `type Foo` found in a trait:
     pub associated_ty_data: BTreeMap<TypeId, Arc<AssociatedTyDatum>>,
 
+    /// For each struct and enum, the variances of its parameters:
+    pub adt_variances: BTreeMap<TypeKindId, Arc<Vec<Variance>>>,
+
     /// For each user-specified clause
     pub custom_clauses: Vec<ProgramClause<ChalkIr>>,
 }
@@ -122,6 +141,22 @@ impl RustIrDatabase for Program {
         self.struct_data[&id].clone()
     }
 
+    fn enum_datum(&self, id: EnumId) -> Arc<EnumDatum> {
+        self.enum_data[&id].clone()
+    }
+
+    fn closure_datum(&self, id: ClosureId) -> Arc<ClosureDatum> {
+        self.closure_data[&id].clone()
+    }
+
+    fn opaque_ty_data(&self, id: OpaqueTyId) -> Arc<OpaqueTyDatum> {
+        self.opaque_ty_data[&id].clone()
+    }
+
+    fn crate_datum(&self, krate: Identifier) -> Arc<CrateDatum> {
+        self.crate_data[&krate].clone()
+    }
+
     fn impls_for_trait(&self, trait_id: TraitId, parameters: &[Parameter<ChalkIr>]) -> Vec<ImplId> {
         self.impl_data
             .iter()
@@ -146,28 +181,41 @@ impl RustIrDatabase for Program {
             .collect()
     }
 
-    fn impl_provided_for(&self, auto_trait_id: TraitId, struct_id: StructId) -> bool {
+    fn impl_provided_for(&self, auto_trait_id: TraitId, type_name: TypeName) -> bool {
         // Look for an impl like `impl Send for Foo` where `Foo` is
-        // the struct.  See `push_auto_trait_impls` for more.
-        let type_kind_id = TypeKindId::StructId(struct_id);
+        // the struct, enum or built-in type. See `push_auto_trait_impls`
+        // for more.
         self.impl_data.values().any(|impl_datum| {
             let impl_trait_ref = &impl_datum.binders.value.trait_ref;
             impl_trait_ref.trait_id == auto_trait_id
                 && match impl_trait_ref.parameters[0].assert_ty_ref() {
-                    Ty::Apply(apply) => match apply.name {
-                        TypeName::TypeKindId(id) => id == type_kind_id,
-                        _ => false,
-                    },
-
+                    Ty::Apply(apply) => apply.name == type_name,
                     _ => false,
                 }
         })
     }
 
+    fn well_known_trait_id(&self, lang_item: LangItem) -> Option<TraitId> {
+        self.lang_items.get(&lang_item).cloned()
+    }
+
+    fn adt_variances(&self, adt_id: TypeKindId) -> Arc<Vec<Variance>> {
+        self.adt_variances[&adt_id].clone()
+    }
+
     fn type_name(&self, id: TypeKindId) -> Identifier {
         match self.type_kinds.get(&id) {
             Some(v) => v.name,
             None => panic!("no type with id `{:?}`", id),
         }
     }
+
+    fn specialization_priorities(
+        &self,
+        _trait_id: TraitId,
+    ) -> Option<Arc<SpecializationPriorities>> {
+        // The priorities are computed by coherence checking, which
+        // solves goals against the program itself.
+        None
+    }
 }
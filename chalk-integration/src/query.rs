@@ -1,32 +1,60 @@
 // https://crates.io/crates/salsa
 // hello world https://github.com/salsa-rs/salsa/blob/master/examples/hello_world/main.rs
 
-use crate::error::ChalkError;
+use crate::error::{ChalkError, IncludeError};
+use crate::include::IncludeExpander;
 use crate::lowering::LowerProgram;
 use crate::program::Program;
 use crate::program_environment::ProgramEnvironment;
+use chalk_ir::cast::Cast;
 use chalk_ir::tls;
 use chalk_ir::TraitId;
+use chalk_parse::ast;
 use chalk_solve::clauses::builder::ClauseBuilder;
 use chalk_solve::clauses::program_clauses::ToProgramClauses;
 use chalk_solve::coherence::orphan;
 use chalk_solve::coherence::{CoherenceSolver, SpecializationPriorities};
+use chalk_solve::split::Split;
 use chalk_solve::wf;
 use chalk_solve::RustIrDatabase;
 use chalk_solve::Solver;
 use chalk_solve::SolverChoice;
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 
 #[salsa::query_group(Lowering)]
 pub trait LoweringDatabase: RustIrDatabase {
+    /// The text of the program; set through `ChalkDatabase::edit_program`,
+    /// which also sets the `file_text` of the files it includes.
     #[salsa::input]
     fn program_text(&self) -> Arc<String>;
 
+    /// The directory that the includes of the program text are
+    /// resolved relative to.
+    #[salsa::input]
+    fn program_dir(&self) -> PathBuf;
+
+    /// The text of a file included by the program, or `None` if it
+    /// could not be read. Keyed by the path returned by
+    /// `include::resolve`.
+    #[salsa::input]
+    fn file_text(&self, path: PathBuf) -> Option<Arc<String>>;
+
     #[salsa::input]
     fn solver_choice(&self) -> SolverChoice;
 
+    /// The parsed program text, with its includes not yet expanded.
+    fn parsed_program(&self) -> Result<Arc<ast::Program>, ChalkError>;
+
+    /// A parsed included file, with its includes not yet expanded.
+    fn parsed_file(&self, path: PathBuf) -> Result<Arc<ast::Program>, ChalkError>;
+
+    /// The parsed program, with every include replaced by the items of
+    /// the file it includes.
+    fn expanded_program(&self) -> Result<Arc<ast::Program>, ChalkError>;
+
     fn program_ir(&self) -> Result<Arc<Program>, ChalkError>;
 
     /// Performs coherence check and computes which impls specialize
@@ -51,18 +79,38 @@ pub trait LoweringDatabase: RustIrDatabase {
     fn solver(&self) -> Arc<Mutex<Solver>>;
 }
 
-fn program_ir(db: &impl LoweringDatabase) -> Result<Arc<Program>, ChalkError> {
+fn parsed_program(db: &impl LoweringDatabase) -> Result<Arc<ast::Program>, ChalkError> {
     let text = db.program_text();
-    Ok(Arc::new(chalk_parse::parse_program(&text)?.lower()?))
+    Ok(Arc::new(chalk_parse::parse_program(&text)?))
+}
+
+fn parsed_file(db: &impl LoweringDatabase, path: PathBuf) -> Result<Arc<ast::Program>, ChalkError> {
+    match db.file_text(path.clone()) {
+        Some(text) => Ok(Arc::new(chalk_parse::parse_program(&text)?)),
+        None => Err(IncludeError::UnreadableFile(path))?,
+    }
+}
+
+fn expanded_program(db: &impl LoweringDatabase) -> Result<Arc<ast::Program>, ChalkError> {
+    let program = db.parsed_program()?;
+    let items = IncludeExpander::new(db).expand(&program.items, &db.program_dir())?;
+    Ok(Arc::new(ast::Program { items }))
+}
+
+fn program_ir(db: &impl LoweringDatabase) -> Result<Arc<Program>, ChalkError> {
+    Ok(Arc::new(db.expanded_program()?.lower()?))
 }
 
 fn orphan_check(db: &impl LoweringDatabase) -> Result<(), ChalkError> {
     let program = db.program_ir()?;
 
+    // Like coherence checking, this solves goals against the program
+    // rather than `db`, as the latter consults the results of
+    // coherence checking when generating program clauses.
     tls::set_current_program(&program, || -> Result<(), ChalkError> {
         let local_impls = program.local_impl_ids();
         for impl_id in local_impls {
-            orphan::perform_orphan_check(db, db.solver_choice(), impl_id)?;
+            orphan::perform_orphan_check(&*program, db.solver_choice(), impl_id)?;
         }
         Ok(())
     })
@@ -77,7 +125,10 @@ fn coherence(
         .trait_data
         .keys()
         .map(|&trait_id| {
-            let solver = CoherenceSolver::new(db, db.solver_choice(), trait_id);
+            // Solve against the program rather than `db`: the program
+            // clauses that `db` generates depend on the specialization
+            // priorities that we are computing here.
+            let solver = CoherenceSolver::new(&*program, db.solver_choice(), trait_id);
             let priorities = solver.specialization_priorities()?;
             Ok((trait_id, priorities))
         })
@@ -101,6 +152,18 @@ fn checked_program(db: &impl LoweringDatabase) -> Result<Arc<Program>, ChalkErro
             solver.verify_struct_decl(id)?;
         }
 
+        for &id in program.enum_data.keys() {
+            solver.verify_enum_decl(id)?;
+        }
+
+        for &trait_id in program.trait_data.keys() {
+            solver.verify_trait_decl(trait_id)?;
+        }
+
+        for &opaque_ty_id in program.opaque_ty_data.keys() {
+            solver.verify_opaque_ty_decl(opaque_ty_id)?;
+        }
+
         for &impl_id in program.impl_data.keys() {
             solver.verify_trait_impl(impl_id)?;
         }
@@ -137,25 +200,37 @@ fn environment(db: &impl LoweringDatabase) -> Result<Arc<ProgramEnvironment>, Ch
         .values()
         .for_each(|d| d.to_program_clauses(builder));
 
+    program
+        .enum_data
+        .values()
+        .for_each(|d| d.to_program_clauses(builder));
+
+    program
+        .closure_data
+        .values()
+        .for_each(|d| d.to_program_clauses(builder));
+
     for (&auto_trait_id, _) in program
         .trait_data
         .iter()
         .filter(|(_, auto_trait)| auto_trait.is_auto_trait())
     {
-        for &struct_id in program.struct_data.keys() {
-            chalk_solve::clauses::push_auto_trait_impls(builder, auto_trait_id, struct_id);
+        let struct_ids = program.struct_data.keys().map(|&id| id.cast());
+        let enum_ids = program.enum_data.keys().map(|&id| id.cast());
+        let closure_ids = program.closure_data.keys().map(|&id| id.cast());
+        for type_name in struct_ids.chain(enum_ids).chain(closure_ids) {
+            chalk_solve::clauses::push_auto_trait_impls(builder, auto_trait_id, type_name);
         }
     }
 
-    for datum in program.impl_data.values() {
+    for (&impl_id, datum) in program.impl_data.iter() {
         // If we encounter a negative impl, do not generate any rule. Negative impls
         // are currently just there to deactivate default impls for auto traits.
         if datum.is_positive() {
             datum.to_program_clauses(builder);
-            datum
-                .associated_ty_value_ids
+            program
+                .impl_associated_ty_values(impl_id)
                 .iter()
-                .map(|&atv_id| db.associated_ty_value(atv_id))
                 .for_each(|atv| atv.to_program_clauses(builder));
         }
     }
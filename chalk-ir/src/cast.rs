@@ -91,6 +91,18 @@ impl<TF: TypeFamily> CastTo<WhereClause<TF>> for ProjectionEq<TF> {
     }
 }
 
+impl<TF: TypeFamily> CastTo<WhereClause<TF>> for LifetimeOutlives<TF> {
+    fn cast_to(self) -> WhereClause<TF> {
+        WhereClause::LifetimeOutlives(self)
+    }
+}
+
+impl<TF: TypeFamily> CastTo<WhereClause<TF>> for TypeOutlives<TF> {
+    fn cast_to(self) -> WhereClause<TF> {
+        WhereClause::TypeOutlives(self)
+    }
+}
+
 impl<T, TF> CastTo<DomainGoal<TF>> for T
 where
     T: CastTo<WhereClause<TF>>,
@@ -125,6 +137,12 @@ impl<TF: TypeFamily> CastTo<DomainGoal<TF>> for Normalize<TF> {
     }
 }
 
+impl<TF: TypeFamily> CastTo<DomainGoal<TF>> for OpaqueEq<TF> {
+    fn cast_to(self) -> DomainGoal<TF> {
+        DomainGoal::OpaqueEq(self)
+    }
+}
+
 impl<TF: TypeFamily> CastTo<DomainGoal<TF>> for WellFormed<TF> {
     fn cast_to(self) -> DomainGoal<TF> {
         DomainGoal::WellFormed(self)
@@ -143,6 +161,12 @@ impl<TF: TypeFamily> CastTo<LeafGoal<TF>> for EqGoal<TF> {
     }
 }
 
+impl<TF: TypeFamily> CastTo<LeafGoal<TF>> for SubtypeGoal<TF> {
+    fn cast_to(self) -> LeafGoal<TF> {
+        LeafGoal::SubtypeGoal(self)
+    }
+}
+
 impl<T: CastTo<Goal<TF>>, TF: TypeFamily> CastTo<Goal<TF>> for Binders<T> {
     fn cast_to(self) -> Goal<TF> {
         if self.binders.is_empty() {
@@ -177,6 +201,7 @@ where
         ProgramClause::Implies(ProgramClauseImplication {
             consequence: self.cast(),
             conditions: vec![],
+            constraints: vec![],
         })
     }
 }
@@ -193,6 +218,7 @@ where
             ProgramClause::ForAll(self.map(|bound| ProgramClauseImplication {
                 consequence: bound.cast(),
                 conditions: vec![],
+                constraints: vec![],
             }))
         }
     }
@@ -259,6 +285,24 @@ impl CastTo<TypeKindId> for StructId {
     }
 }
 
+impl CastTo<TypeKindId> for EnumId {
+    fn cast_to(self) -> TypeKindId {
+        TypeKindId::EnumId(self)
+    }
+}
+
+impl CastTo<TypeKindId> for ClosureId {
+    fn cast_to(self) -> TypeKindId {
+        TypeKindId::ClosureId(self)
+    }
+}
+
+impl CastTo<TypeKindId> for OpaqueTyId {
+    fn cast_to(self) -> TypeKindId {
+        TypeKindId::OpaqueTyId(self)
+    }
+}
+
 impl<T> CastTo<TypeName> for T
 where
     T: CastTo<TypeKindId>,
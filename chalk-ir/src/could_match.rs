@@ -44,6 +44,13 @@ where
                 Ok(())
             }
 
+            fn zip_consts(&mut self, a: &Const<TF>, b: &Const<TF>) -> Fallible<()> {
+                match (a, b) {
+                    (Const::Value(a), Const::Value(b)) if a != b => Err(NoSolution),
+                    _ => Ok(()),
+                }
+            }
+
             fn zip_binders<T>(&mut self, a: &Binders<T>, b: &Binders<T>) -> Fallible<()>
             where
                 T: Zip<TF>,
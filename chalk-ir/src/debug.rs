@@ -16,6 +16,9 @@ impl Debug for TypeKindId {
             TypeKindId::TypeId(id) => write!(fmt, "{:?}", id),
             TypeKindId::TraitId(id) => write!(fmt, "{:?}", id),
             TypeKindId::StructId(id) => write!(fmt, "{:?}", id),
+            TypeKindId::EnumId(id) => write!(fmt, "{:?}", id),
+            TypeKindId::ClosureId(id) => write!(fmt, "{:?}", id),
+            TypeKindId::OpaqueTyId(id) => write!(fmt, "{:?}", id),
         }
     }
 }
@@ -47,6 +50,33 @@ impl Debug for StructId {
     }
 }
 
+impl Debug for EnumId {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
+        tls::with_current_program(|p| match p {
+            Some(prog) => prog.debug_type_kind_id(TypeKindId::EnumId(*self), fmt),
+            None => write!(fmt, "EnumId({:?})", self.0.index),
+        })
+    }
+}
+
+impl Debug for ClosureId {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
+        tls::with_current_program(|p| match p {
+            Some(prog) => prog.debug_type_kind_id(TypeKindId::ClosureId(*self), fmt),
+            None => write!(fmt, "ClosureId({:?})", self.0.index),
+        })
+    }
+}
+
+impl Debug for OpaqueTyId {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
+        tls::with_current_program(|p| match p {
+            Some(prog) => prog.debug_type_kind_id(TypeKindId::OpaqueTyId(*self), fmt),
+            None => write!(fmt, "OpaqueTyId({:?})", self.0.index),
+        })
+    }
+}
+
 impl Display for UniverseIndex {
     fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
         write!(fmt, "U{}", self.counter)
@@ -65,6 +95,17 @@ impl Debug for TypeName {
             TypeName::TypeKindId(id) => write!(fmt, "{:?}", id),
             TypeName::Placeholder(index) => write!(fmt, "{:?}", index),
             TypeName::AssociatedType(assoc_ty) => write!(fmt, "{:?}", assoc_ty),
+            TypeName::Ref(Mutability::Not) => write!(fmt, "&"),
+            TypeName::Ref(Mutability::Mut) => write!(fmt, "&mut"),
+            TypeName::Raw(Mutability::Not) => write!(fmt, "*const"),
+            TypeName::Raw(Mutability::Mut) => write!(fmt, "*mut"),
+            TypeName::Tuple(arity) => write!(fmt, "Tuple{}", arity),
+            TypeName::FnPtr(arity) => write!(fmt, "FnPtr{}", arity),
+            TypeName::Slice => write!(fmt, "Slice"),
+            TypeName::Array => write!(fmt, "Array"),
+            TypeName::Str => write!(fmt, "str"),
+            TypeName::Scalar(scalar) => write!(fmt, "{}", scalar.name()),
+            TypeName::Never => write!(fmt, "!"),
             TypeName::Error => write!(fmt, "{{error}}"),
         }
     }
@@ -109,6 +150,18 @@ impl<TF: TypeFamily> Debug for Lifetime<TF> {
     }
 }
 
+impl<TF: TypeFamily> Debug for Const<TF> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
+        match self {
+            Const::BoundVar(depth) => write!(fmt, "^{}", depth),
+            Const::InferenceVar(var) => write!(fmt, "{:?}", var),
+            Const::Placeholder(index) => write!(fmt, "{:?}", index),
+            Const::Value(value) => write!(fmt, "{}", value),
+            Const::Phantom(..) => unreachable!(),
+        }
+    }
+}
+
 impl Debug for PlaceholderIndex {
     fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
         let PlaceholderIndex { ui, idx } = self;
@@ -118,7 +171,50 @@ impl Debug for PlaceholderIndex {
 
 impl<TF: TypeFamily> Debug for ApplicationTy<TF> {
     fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
-        write!(fmt, "{:?}{:?}", self.name, Angle(&self.parameters))
+        match self.name {
+            TypeName::Ref(mutability) => {
+                let prefix = match mutability {
+                    Mutability::Not => "",
+                    Mutability::Mut => "mut ",
+                };
+                write!(
+                    fmt,
+                    "&{:?} {}{:?}",
+                    self.parameters[0], prefix, self.parameters[1]
+                )
+            }
+            TypeName::Raw(mutability) => {
+                let prefix = match mutability {
+                    Mutability::Not => "const",
+                    Mutability::Mut => "mut",
+                };
+                write!(fmt, "*{} {:?}", prefix, self.parameters[0])
+            }
+            TypeName::Tuple(1) => write!(fmt, "({:?},)", self.parameters[0]),
+            TypeName::Tuple(_) => {
+                write!(fmt, "(")?;
+                for (i, parameter) in self.parameters.iter().enumerate() {
+                    if i > 0 {
+                        write!(fmt, ", ")?;
+                    }
+                    write!(fmt, "{:?}", parameter)?;
+                }
+                write!(fmt, ")")
+            }
+            TypeName::FnPtr(arity) => {
+                write!(fmt, "fn(")?;
+                for (i, parameter) in self.parameters[..arity].iter().enumerate() {
+                    if i > 0 {
+                        write!(fmt, ", ")?;
+                    }
+                    write!(fmt, "{:?}", parameter)?;
+                }
+                write!(fmt, ") -> {:?}", self.parameters[arity])
+            }
+            TypeName::Slice => write!(fmt, "[{:?}]", self.parameters[0]),
+            TypeName::Array => write!(fmt, "[{:?}; {:?}]", self.parameters[0], self.parameters[1]),
+            _ => write!(fmt, "{:?}{:?}", self.name, Angle(&self.parameters)),
+        }
     }
 }
 
@@ -195,17 +291,43 @@ impl<TF: TypeFamily> Debug for Normalize<TF> {
     }
 }
 
+impl<TF: TypeFamily> Debug for OpaqueEq<TF> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
+        write!(
+            fmt,
+            "OpaqueEq({:?}{:?} = {:?})",
+            self.opaque_ty_id,
+            Angle(&self.parameters),
+            self.ty
+        )
+    }
+}
+
 impl<TF: TypeFamily> Debug for ProjectionEq<TF> {
     fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
         write!(fmt, "ProjectionEq({:?} = {:?})", self.projection, self.ty)
     }
 }
 
+impl<TF: TypeFamily> Debug for LifetimeOutlives<TF> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
+        write!(fmt, "Outlives({:?}: {:?})", self.a, self.b)
+    }
+}
+
+impl<TF: TypeFamily> Debug for TypeOutlives<TF> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
+        write!(fmt, "Outlives({:?}: {:?})", self.ty, self.lifetime)
+    }
+}
+
 impl<TF: TypeFamily> Debug for WhereClause<TF> {
     fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
         match self {
             WhereClause::Implemented(tr) => write!(fmt, "Implemented({:?})", tr.with_colon()),
             WhereClause::ProjectionEq(p) => write!(fmt, "{:?}", p),
+            WhereClause::LifetimeOutlives(o) => write!(fmt, "{:?}", o),
+            WhereClause::TypeOutlives(o) => write!(fmt, "{:?}", o),
         }
     }
 }
@@ -235,6 +357,7 @@ impl<TF: TypeFamily> Debug for DomainGoal<TF> {
             DomainGoal::WellFormed(n) => write!(fmt, "{:?}", n),
             DomainGoal::FromEnv(n) => write!(fmt, "{:?}", n),
             DomainGoal::Normalize(n) => write!(fmt, "{:?}", n),
+            DomainGoal::OpaqueEq(n) => write!(fmt, "{:?}", n),
             DomainGoal::IsLocal(n) => write!(fmt, "IsLocal({:?})", n),
             DomainGoal::IsUpstream(n) => write!(fmt, "IsUpstream({:?})", n),
             DomainGoal::IsFullyVisible(n) => write!(fmt, "IsFullyVisible({:?})", n),
@@ -246,6 +369,10 @@ impl<TF: TypeFamily> Debug for DomainGoal<TF> {
                 Angle(&tr.parameters[1..])
             ),
             DomainGoal::Compatible(_) => write!(fmt, "Compatible"),
+            DomainGoal::Reveal(_) => write!(fmt, "Reveal"),
+            DomainGoal::InCrate(krate) => write!(fmt, "InCrate({})", krate),
+            DomainGoal::UpstreamCrate(krate) => write!(fmt, "UpstreamCrate({})", krate),
+            DomainGoal::ObjectSafe(trait_id) => write!(fmt, "ObjectSafe({:?})", trait_id),
             DomainGoal::DownstreamType(n) => write!(fmt, "DownstreamType({:?})", n),
         }
     }
@@ -255,6 +382,7 @@ impl<TF: TypeFamily> Debug for LeafGoal<TF> {
     fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
         match *self {
             LeafGoal::EqGoal(ref eq) => write!(fmt, "{:?}", eq),
+            LeafGoal::SubtypeGoal(ref sub) => write!(fmt, "{:?}", sub),
             LeafGoal::DomainGoal(ref dom) => write!(fmt, "{:?}", dom),
         }
     }
@@ -266,6 +394,12 @@ impl<TF: TypeFamily> Debug for EqGoal<TF> {
     }
 }
 
+impl<TF: TypeFamily> Debug for SubtypeGoal<TF> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
+        write!(fmt, "Subtype({:?} <: {:?})", self.a, self.b)
+    }
+}
+
 impl<TF: TypeFamily> Debug for Goal<TF> {
     fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
         match *self {
@@ -278,6 +412,7 @@ impl<TF: TypeFamily> Debug for Goal<TF> {
                     match *binder {
                         ParameterKind::Ty(()) => write!(fmt, "type")?,
                         ParameterKind::Lifetime(()) => write!(fmt, "lifetime")?,
+                        ParameterKind::Const(()) => write!(fmt, "const")?,
                     }
                 }
                 write!(fmt, "> {{ {:?} }}", subgoal.value)
@@ -306,6 +441,7 @@ impl<T: Debug> Debug for Binders<T> {
                 match *binder {
                     ParameterKind::Ty(()) => write!(fmt, "type")?,
                     ParameterKind::Lifetime(()) => write!(fmt, "lifetime")?,
+                    ParameterKind::Const(()) => write!(fmt, "const")?,
                 }
             }
             write!(fmt, "> ")?;
@@ -328,15 +464,26 @@ impl<TF: TypeFamily> Debug for ProgramClauseImplication<TF> {
         write!(fmt, "{:?}", self.consequence)?;
 
         let conds = self.conditions.len();
-        if conds == 0 {
-            return Ok(());
+        if conds != 0 {
+            write!(fmt, " :- ")?;
+            for cond in &self.conditions[..conds - 1] {
+                write!(fmt, "{:?}, ", cond)?;
+            }
+            write!(fmt, "{:?}", self.conditions[conds - 1])?;
         }
 
-        write!(fmt, " :- ")?;
-        for cond in &self.conditions[..conds - 1] {
-            write!(fmt, "{:?}, ", cond)?;
+        // Region constraints are not proven, but carried along, so we
+        // print them apart from the conditions: `C :- G1, G2; 'a: 'b`.
+        let constraints = self.constraints.len();
+        if constraints != 0 {
+            write!(fmt, "; ")?;
+            for constraint in &self.constraints[..constraints - 1] {
+                write!(fmt, "{:?}, ", constraint)?;
+            }
+            write!(fmt, "{:?}", self.constraints[constraints - 1])?;
         }
-        write!(fmt, "{:?}", self.conditions[conds - 1])
+
+        Ok(())
     }
 }
 
@@ -369,11 +516,12 @@ impl<T: Display> Display for Canonical<T> {
     }
 }
 
-impl<T: Debug, L: Debug> Debug for ParameterKind<T, L> {
+impl<T: Debug, L: Debug, C: Debug> Debug for ParameterKind<T, L, C> {
     fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
         match *self {
             ParameterKind::Ty(ref n) => write!(fmt, "Ty({:?})", n),
             ParameterKind::Lifetime(ref n) => write!(fmt, "Lifetime({:?})", n),
+            ParameterKind::Const(ref n) => write!(fmt, "Const({:?})", n),
         }
     }
 }
@@ -383,6 +531,7 @@ impl<TF: TypeFamily> Debug for Parameter<TF> {
         match &self.0 {
             ParameterKind::Ty(n) => write!(fmt, "{:?}", n),
             ParameterKind::Lifetime(n) => write!(fmt, "{:?}", n),
+            ParameterKind::Const(n) => write!(fmt, "{:?}", n),
         }
     }
 }
@@ -391,6 +540,8 @@ impl<TF: TypeFamily> Debug for Constraint<TF> {
     fn fmt(&self, fmt: &mut Formatter) -> Result<(), Error> {
         match self {
             Constraint::LifetimeEq(a, b) => write!(fmt, "{:?} == {:?}", a, b),
+            Constraint::LifetimeOutlives(a, b) => write!(fmt, "{:?}: {:?}", a, b),
+            Constraint::TypeOutlives(ty, lifetime) => write!(fmt, "{:?}: {:?}", ty, lifetime),
         }
     }
 }
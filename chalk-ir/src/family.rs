@@ -3,6 +3,7 @@ use crate::debug::Angle;
 use crate::fold::{Fold, Folder, ReflexiveFold};
 use crate::tls;
 use crate::zip::Zip;
+use crate::Const;
 use crate::Lifetime;
 use crate::Parameter;
 use crate::ParameterKind;
@@ -53,6 +54,18 @@ pub trait TypeFamily: Debug + Copy + Eq + Ord + Hash {
         + Lookup<Lifetime<Self>>
         + CastTo<Parameter<Self>>;
 
+    /// "Interned" representation of const generic arguments. You can
+    /// use the `Lookup` trait to convert this to a `Const<Self>`.
+    type Const: Debug
+        + Clone
+        + Eq
+        + Ord
+        + Hash
+        + ReflexiveFold<Self>
+        + Zip<Self>
+        + Lookup<Const<Self>>
+        + CastTo<Parameter<Self>>;
+
     /// Prints the debug representation of a projection. To get good
     /// results, this requires inspecting TLS, and is difficult to
     /// code without reference to a specific type-family (and hence
@@ -69,6 +82,10 @@ pub trait TypeFamily: Debug + Copy + Eq + Ord + Hash {
     /// Create an "interned" type from `lifetime`. You can also use
     /// the `Lifetime::intern` method, which is preferred.
     fn intern_lifetime(lifetime: Lifetime<Self>) -> Self::Lifetime;
+
+    /// Create an "interned" const from `constant`. You can also use
+    /// the `Const::intern` method, which is preferred.
+    fn intern_const(constant: Const<Self>) -> Self::Const;
 }
 
 /// Implemented by types that have an associated type family (which
@@ -110,6 +127,16 @@ impl Lookup<Lifetime<ChalkIr>> for Lifetime<ChalkIr> {
     }
 }
 
+impl Lookup<Const<ChalkIr>> for Const<ChalkIr> {
+    fn lookup_ref(&self) -> &Const<ChalkIr> {
+        self
+    }
+
+    fn lookup(self) -> Const<ChalkIr> {
+        self
+    }
+}
+
 /// The default "type family" and the only type family used by chalk
 /// itself. In this family, no interning actually occurs.
 #[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
@@ -118,6 +145,7 @@ pub struct ChalkIr {}
 impl TypeFamily for ChalkIr {
     type Type = Ty<ChalkIr>;
     type Lifetime = Lifetime<ChalkIr>;
+    type Const = Const<ChalkIr>;
 
     fn debug_projection(
         projection: &ProjectionTy<ChalkIr>,
@@ -141,6 +169,10 @@ impl TypeFamily for ChalkIr {
     fn intern_lifetime(lifetime: Lifetime<ChalkIr>) -> Lifetime<ChalkIr> {
         lifetime
     }
+
+    fn intern_const(constant: Const<ChalkIr>) -> Const<ChalkIr> {
+        constant
+    }
 }
 
 impl HasTypeFamily for ChalkIr {
@@ -205,3 +237,20 @@ impl CastTo<Parameter<ChalkIr>> for Lifetime<ChalkIr> {
         Parameter(ParameterKind::Lifetime(self))
     }
 }
+
+impl Fold<ChalkIr> for Const<ChalkIr> {
+    type Result = Self;
+    fn fold_with(
+        &self,
+        folder: &mut dyn Folder<ChalkIr>,
+        binders: usize,
+    ) -> Fallible<Self::Result> {
+        folder.fold_const(self, binders)
+    }
+}
+
+impl CastTo<Parameter<ChalkIr>> for Const<ChalkIr> {
+    fn cast_to(self) -> Parameter<ChalkIr> {
+        Parameter(ParameterKind::Const(self))
+    }
+}
@@ -63,6 +63,7 @@ pub trait Folder<TF: TypeFamily>:
 pub trait TypeFolder<TF: TypeFamily> {
     fn fold_ty(&mut self, ty: &TF::Type, binders: usize) -> Fallible<TF::Type>;
     fn fold_lifetime(&mut self, lifetime: &TF::Lifetime, binders: usize) -> Fallible<TF::Lifetime>;
+    fn fold_const(&mut self, constant: &TF::Const, binders: usize) -> Fallible<TF::Const>;
 }
 
 impl<T, TF> Folder<TF> for T
@@ -91,6 +92,10 @@ where
     fn fold_lifetime(&mut self, lifetime: &TF::Lifetime, binders: usize) -> Fallible<TF::Lifetime> {
         super_fold_lifetime(self, lifetime, binders)
     }
+
+    fn fold_const(&mut self, constant: &TF::Const, binders: usize) -> Fallible<TF::Const> {
+        super_fold_const(self, constant, binders)
+    }
 }
 
 /// The methods for folding **free variables**. These are `BoundVar`
@@ -110,6 +115,9 @@ pub trait FreeVarFolder<TF: TypeFamily> {
 
     /// As `fold_free_var_ty`, but for lifetimes.
     fn fold_free_var_lifetime(&mut self, depth: usize, binders: usize) -> Fallible<TF::Lifetime>;
+
+    /// As `fold_free_var_ty`, but for consts.
+    fn fold_free_var_const(&mut self, depth: usize, binders: usize) -> Fallible<TF::Const>;
 }
 
 /// A convenience trait. If you implement this, you get an
@@ -140,6 +148,14 @@ impl<T: DefaultFreeVarFolder, TF: TypeFamily> FreeVarFolder<TF> for T {
             Ok(Lifetime::<TF>::BoundVar(depth + binders).intern())
         }
     }
+
+    fn fold_free_var_const(&mut self, depth: usize, binders: usize) -> Fallible<TF::Const> {
+        if T::forbid() {
+            panic!("unexpected free variable with depth `{:?}`", depth)
+        } else {
+            Ok(Const::<TF>::BoundVar(depth + binders).intern())
+        }
+    }
 }
 
 pub trait PlaceholderFolder<TF: TypeFamily> {
@@ -162,6 +178,13 @@ pub trait PlaceholderFolder<TF: TypeFamily> {
         universe: PlaceholderIndex,
         binders: usize,
     ) -> Fallible<TF::Lifetime>;
+
+    /// As with `fold_free_placeholder_ty`, but for consts.
+    fn fold_free_placeholder_const(
+        &mut self,
+        universe: PlaceholderIndex,
+        binders: usize,
+    ) -> Fallible<TF::Const>;
 }
 
 /// A convenience trait. If you implement this, you get an
@@ -200,6 +223,18 @@ impl<T: DefaultPlaceholderFolder, TF: TypeFamily> PlaceholderFolder<TF> for T {
             Ok(universe.to_lifetime::<TF>())
         }
     }
+
+    fn fold_free_placeholder_const(
+        &mut self,
+        universe: PlaceholderIndex,
+        _binders: usize,
+    ) -> Fallible<TF::Const> {
+        if T::forbid() {
+            panic!("unexpected placeholder const `{:?}`", universe)
+        } else {
+            Ok(universe.to_const::<TF>())
+        }
+    }
 }
 
 pub trait InferenceFolder<TF: TypeFamily> {
@@ -218,6 +253,9 @@ pub trait InferenceFolder<TF: TypeFamily> {
         var: InferenceVar,
         binders: usize,
     ) -> Fallible<TF::Lifetime>;
+
+    /// As with `fold_free_inference_ty`, but for consts.
+    fn fold_inference_const(&mut self, var: InferenceVar, binders: usize) -> Fallible<TF::Const>;
 }
 
 /// A convenience trait. If you implement this, you get an
@@ -252,6 +290,14 @@ impl<T: DefaultInferenceFolder, TF: TypeFamily> InferenceFolder<TF> for T {
             Ok(var.to_lifetime::<TF>())
         }
     }
+
+    fn fold_inference_const(&mut self, var: InferenceVar, _binders: usize) -> Fallible<TF::Const> {
+        if T::forbid() {
+            panic!("unexpected inference const `{:?}`", var)
+        } else {
+            Ok(var.to_const::<TF>())
+        }
+    }
 }
 
 pub trait ReflexiveFold<TF: TypeFamily>: Fold<TF, Result = Self> + Sized {}
@@ -382,7 +428,18 @@ impl<TF: TypeFamily> Fold<TF> for ApplicationTy<TF> {
                 folder.fold_free_placeholder_ty(ui, binders)
             }
 
-            TypeName::TypeKindId(_) | TypeName::AssociatedType(_) | TypeName::Error => {
+            TypeName::TypeKindId(_)
+            | TypeName::AssociatedType(_)
+            | TypeName::Ref(_)
+            | TypeName::Raw(_)
+            | TypeName::Tuple(_)
+            | TypeName::FnPtr(_)
+            | TypeName::Slice
+            | TypeName::Array
+            | TypeName::Str
+            | TypeName::Scalar(_)
+            | TypeName::Never
+            | TypeName::Error => {
                 let parameters = parameters.fold_with(folder, binders)?;
                 Ok(ApplicationTy { name, parameters }.cast().intern())
             }
@@ -463,6 +520,26 @@ pub fn super_fold_lifetime<TF: TypeFamily>(
     }
 }
 
+pub fn super_fold_const<TF: TypeFamily>(
+    folder: &mut dyn Folder<TF>,
+    constant: &TF::Const,
+    binders: usize,
+) -> Fallible<TF::Const> {
+    match constant.lookup_ref() {
+        Const::BoundVar(depth) => {
+            if *depth >= binders {
+                folder.fold_free_var_const(depth - binders, binders)
+            } else {
+                Ok(Const::<TF>::BoundVar(*depth).intern())
+            }
+        }
+        Const::InferenceVar(var) => folder.fold_inference_const(*var, binders),
+        Const::Placeholder(universe) => folder.fold_free_placeholder_const(*universe, binders),
+        Const::Value(value) => Ok(Const::<TF>::Value(*value).intern()),
+        Const::Phantom(..) => unreachable!(),
+    }
+}
+
 impl<TF: TypeFamily> Fold<TF> for Substitution<TF> {
     type Result = Substitution<TF>;
     fn fold_with(&self, folder: &mut dyn Folder<TF>, binders: usize) -> Fallible<Self::Result> {
@@ -499,23 +576,29 @@ copy_fold!(TF => Identifier);
 copy_fold!(TF => UniverseIndex);
 copy_fold!(TF => ImplId);
 copy_fold!(TF => StructId);
+copy_fold!(TF => EnumId);
+copy_fold!(TF => ClosureId);
+copy_fold!(TF => OpaqueTyId);
 copy_fold!(TF => TraitId);
 copy_fold!(TF => TypeId);
 copy_fold!(TF => TypeKindId);
 copy_fold!(TF => usize);
 copy_fold!(TF => QuantifierKind);
+copy_fold!(TF => Mutability);
+copy_fold!(TF => Scalar);
 copy_fold!(TF => chalk_engine::TableIndex);
 copy_fold!(TF => chalk_engine::TimeStamp);
 // copy_fold!(TypeName); -- intentionally omitted! This is folded via `fold_ap`
 copy_fold!(TF => ());
 copy_fold!(TF => PhantomData<TF>);
 
-impl<TF: TypeFamily, T, L> Fold<TF> for ParameterKind<T, L>
+impl<TF: TypeFamily, T, L, C> Fold<TF> for ParameterKind<T, L, C>
 where
     T: Fold<TF>,
     L: Fold<TF>,
+    C: Fold<TF>,
 {
-    type Result = ParameterKind<T::Result, L::Result>;
+    type Result = ParameterKind<T::Result, L::Result, C::Result>;
 
     fn fold_with(&self, folder: &mut dyn Folder<TF>, binders: usize) -> Fallible<Self::Result> {
         match self {
@@ -523,6 +606,7 @@ where
             ParameterKind::Lifetime(a) => {
                 Ok(ParameterKind::Lifetime(a.fold_with(folder, binders)?))
             }
+            ParameterKind::Const(a) => Ok(ParameterKind::Const(a.fold_with(folder, binders)?)),
         }
     }
 }
@@ -96,6 +96,10 @@ impl<TF: TypeFamily> FreeVarFolder<TF> for Shifter {
     fn fold_free_var_lifetime(&mut self, depth: usize, binders: usize) -> Fallible<TF::Lifetime> {
         Ok(Lifetime::<TF>::BoundVar(self.adjust(depth, binders)).intern())
     }
+
+    fn fold_free_var_const(&mut self, depth: usize, binders: usize) -> Fallible<TF::Const> {
+        Ok(Const::<TF>::BoundVar(self.adjust(depth, binders)).intern())
+    }
 }
 
 impl DefaultPlaceholderFolder for Shifter {}
@@ -138,6 +142,10 @@ impl<TF: TypeFamily> FreeVarFolder<TF> for DownShifter {
     fn fold_free_var_lifetime(&mut self, depth: usize, binders: usize) -> Fallible<TF::Lifetime> {
         Ok(Lifetime::<TF>::BoundVar(self.adjust(depth, binders)?).intern())
     }
+
+    fn fold_free_var_const(&mut self, depth: usize, binders: usize) -> Fallible<TF::Const> {
+        Ok(Const::<TF>::BoundVar(self.adjust(depth, binders)?).intern())
+    }
 }
 
 impl DefaultPlaceholderFolder for DownShifter {}
@@ -45,6 +45,17 @@ impl<'b, TF: TypeFamily> FreeVarFolder<TF> for Subst<'b, TF> {
             }
         }
     }
+
+    fn fold_free_var_const(&mut self, depth: usize, binders: usize) -> Fallible<TF::Const> {
+        if depth >= self.parameters.len() {
+            Ok(Const::<TF>::BoundVar(depth - self.parameters.len() + binders).intern())
+        } else {
+            match self.parameters[depth].0 {
+                ParameterKind::Const(ref c) => Ok(c.shifted_in(binders)),
+                _ => panic!("mismatched kinds in substitution"),
+            }
+        }
+    }
 }
 
 impl<'b, TF: TypeFamily> DefaultPlaceholderFolder for Subst<'b, TF> {}
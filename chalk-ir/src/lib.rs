@@ -1,4 +1,4 @@
-use crate::cast::Cast;
+use crate::cast::{Cast, Caster};
 use crate::family::Lookup;
 use crate::fold::shift::Shift;
 use crate::fold::{
@@ -128,11 +128,148 @@ pub enum TypeName {
     /// an associated type like `Iterator::Item`; see `AssociatedType` for details
     AssociatedType(TypeId),
 
+    /// a reference type like `&'a T` or `&'a mut T`; the parameters are
+    /// the lifetime `'a` followed by the referent type `T`
+    Ref(Mutability),
+
+    /// a raw pointer type like `*const T` or `*mut T`; the single
+    /// parameter is the pointee type `T`
+    Raw(Mutability),
+
+    /// a tuple type like `(A, B)` of the given arity; the parameters are
+    /// the element types
+    Tuple(usize),
+
+    /// a function pointer type like `fn(A, B) -> R` taking the given
+    /// number of arguments; the parameters are the argument types
+    /// followed by the return type. Higher-ranked function pointers like
+    /// `for<'a> fn(&'a T)` are wrapped in a `Ty::ForAll`.
+    FnPtr(usize),
+
+    /// the slice type `[T]`; the single parameter is the element type
+    Slice,
+
+    /// an array type `[T; N]`; the parameters are the element type `T`
+    /// followed by the length `N`, which is a const
+    Array,
+
+    /// the string slice type `str`
+    Str,
+
+    /// a scalar type like `bool`, `char`, `i32` or `f64`
+    Scalar(Scalar),
+
+    /// the never type `!`
+    Never,
+
     /// This can be used to represent an error, e.g. during name resolution of a type.
     /// Chalk itself will not produce this, just pass it through when given.
     Error,
 }
 
+/// The primitive scalar types.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Scalar {
+    Bool,
+    Char,
+    Int(IntTy),
+    Uint(UintTy),
+    Float(FloatTy),
+}
+
+impl Scalar {
+    /// The name of this type as written in Rust source, like `u8`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Scalar::Bool => "bool",
+            Scalar::Char => "char",
+            Scalar::Int(IntTy::Isize) => "isize",
+            Scalar::Int(IntTy::I8) => "i8",
+            Scalar::Int(IntTy::I16) => "i16",
+            Scalar::Int(IntTy::I32) => "i32",
+            Scalar::Int(IntTy::I64) => "i64",
+            Scalar::Int(IntTy::I128) => "i128",
+            Scalar::Uint(UintTy::Usize) => "usize",
+            Scalar::Uint(UintTy::U8) => "u8",
+            Scalar::Uint(UintTy::U16) => "u16",
+            Scalar::Uint(UintTy::U32) => "u32",
+            Scalar::Uint(UintTy::U64) => "u64",
+            Scalar::Uint(UintTy::U128) => "u128",
+            Scalar::Float(FloatTy::F32) => "f32",
+            Scalar::Float(FloatTy::F64) => "f64",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IntTy {
+    Isize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UintTy {
+    Usize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FloatTy {
+    F32,
+    F64,
+}
+
+/// Whether a reference (or, more generally, a place) may be mutated
+/// through.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Mutability {
+    Mut,
+    Not,
+}
+
+/// How subtyping of a type's parameter relates to subtyping of the type
+/// itself. For example, `&'a T` is covariant in `T`: `&'a A <: &'a B`
+/// if `A <: B`. Variance also describes the relation we are establishing
+/// when unifying two parameters: `Invariant` means equality, `Covariant`
+/// means `a <: b` and `Contravariant` means `b <: a`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Variance {
+    Covariant,
+    Invariant,
+    Contravariant,
+}
+
+impl Variance {
+    /// The variance of a position with variance `other` nested inside
+    /// a position with variance `self`. For example, the `T` in
+    /// `fn(&T)` is contravariant (`Contravariant.xform(Covariant)`).
+    pub fn xform(self, other: Variance) -> Variance {
+        match (self, other) {
+            (Variance::Invariant, _) | (_, Variance::Invariant) => Variance::Invariant,
+            (Variance::Covariant, other) => other,
+            (Variance::Contravariant, other) => other.invert(),
+        }
+    }
+
+    /// The variance in the opposite direction: swaps `Covariant` and
+    /// `Contravariant`.
+    pub fn invert(self) -> Variance {
+        match self {
+            Variance::Covariant => Variance::Contravariant,
+            Variance::Invariant => Variance::Invariant,
+            Variance::Contravariant => Variance::Covariant,
+        }
+    }
+}
+
 /// An universe index is how a universally quantified parameter is
 /// represented when it's binder is moved into the environment.
 /// An example chain of transformations would be:
@@ -166,6 +303,15 @@ impl UniverseIndex {
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StructId(pub RawId);
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EnumId(pub RawId);
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClosureId(pub RawId);
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OpaqueTyId(pub RawId);
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TraitId(pub RawId);
 
@@ -185,6 +331,9 @@ pub enum TypeKindId {
     TypeId(TypeId),
     TraitId(TraitId),
     StructId(StructId),
+    EnumId(EnumId),
+    ClosureId(ClosureId),
+    OpaqueTyId(OpaqueTyId),
 }
 
 impl TypeKindId {
@@ -193,11 +342,14 @@ impl TypeKindId {
             TypeKindId::TypeId(id) => id.0,
             TypeKindId::TraitId(id) => id.0,
             TypeKindId::StructId(id) => id.0,
+            TypeKindId::EnumId(id) => id.0,
+            TypeKindId::ClosureId(id) => id.0,
+            TypeKindId::OpaqueTyId(id) => id.0,
         }
     }
 }
 
-impl_froms!(TypeKindId: TypeId, TraitId, StructId);
+impl_froms!(TypeKindId: TypeId, TraitId, StructId, EnumId, ClosureId, OpaqueTyId);
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(non_camel_case_types)]
@@ -208,6 +360,8 @@ pub struct RawId {
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum TypeSort {
     Struct,
+    Enum,
+    Closure,
     Trait,
 }
 
@@ -349,6 +503,10 @@ impl InferenceVar {
     pub fn to_lifetime<TF: TypeFamily>(self) -> TF::Lifetime {
         Lifetime::<TF>::InferenceVar(self).intern()
     }
+
+    pub fn to_const<TF: TypeFamily>(self) -> TF::Const {
+        Const::<TF>::InferenceVar(self).intern()
+    }
 }
 
 /// for<'a...'z> X -- all binders are instantiated at once,
@@ -398,6 +556,44 @@ impl<TF: TypeFamily> Lifetime<TF> {
     }
 }
 
+/// A const generic argument, like the `3` in `[u8; 3]` or the `N` in
+/// `Foo<N>`. Consts are always of type `usize` for now, so a concrete
+/// value is just a number.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Const<TF: TypeFamily> {
+    /// See Ty::Var(_).
+    BoundVar(usize),
+    InferenceVar(InferenceVar),
+    Placeholder(PlaceholderIndex),
+    Value(u64),
+    Phantom(Void, PhantomData<TF>),
+}
+
+impl<TF: TypeFamily> Const<TF> {
+    fn intern(self) -> TF::Const {
+        TF::intern_const(self)
+    }
+
+    /// If this is a `Const::InferenceVar(d)`, returns `Some(d)` else `None`.
+    pub fn inference_var(&self) -> Option<InferenceVar> {
+        if let Const::InferenceVar(depth) = *self {
+            Some(depth)
+        } else {
+            None
+        }
+    }
+
+    /// True if this const is a "bound" const, and hence
+    /// needs to be shifted across binders. Meant for debug assertions.
+    pub fn needs_shift(&self) -> bool {
+        match self {
+            Const::BoundVar(_) => true,
+            Const::InferenceVar(_) | Const::Placeholder(_) | Const::Value(_) => false,
+            Const::Phantom(..) => unreachable!(),
+        }
+    }
+}
+
 /// Index of an universally quantified parameter in the environment.
 /// Two indexes are required, the one of the universe itself
 /// and the relative index inside the universe.
@@ -414,6 +610,10 @@ impl PlaceholderIndex {
         Lifetime::<TF>::Placeholder(self).intern()
     }
 
+    pub fn to_const<TF: TypeFamily>(self) -> TF::Const {
+        Const::<TF>::Placeholder(self).intern()
+    }
+
     pub fn to_ty<TF: TypeFamily>(self) -> TF::Type {
         Ty::Apply(ApplicationTy::<TF> {
             name: TypeName::Placeholder(self),
@@ -439,6 +639,14 @@ impl<TF: TypeFamily> ApplicationTy<TF> {
         self.parameters.iter().cloned().filter_map(|p| p.ty())
     }
 
+    /// If this is an application of a named opaque type, returns its id.
+    pub fn opaque_ty_id(&self) -> Option<OpaqueTyId> {
+        match self.name {
+            TypeName::TypeKindId(TypeKindId::OpaqueTyId(id)) => Some(id),
+            _ => None,
+        }
+    }
+
     pub fn first_type_parameter(&self) -> Option<TF::Type> {
         self.type_parameters().next()
     }
@@ -449,9 +657,10 @@ impl<TF: TypeFamily> ApplicationTy<TF> {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub enum ParameterKind<T, L = T> {
+pub enum ParameterKind<T, L = T, C = T> {
     Ty(T),
     Lifetime(L),
+    Const(C),
 }
 
 impl<T> ParameterKind<T> {
@@ -459,6 +668,7 @@ impl<T> ParameterKind<T> {
         match self {
             ParameterKind::Ty(t) => t,
             ParameterKind::Lifetime(t) => t,
+            ParameterKind::Const(t) => t,
         }
     }
 
@@ -469,11 +679,12 @@ impl<T> ParameterKind<T> {
         match self {
             ParameterKind::Ty(t) => ParameterKind::Ty(op(t)),
             ParameterKind::Lifetime(t) => ParameterKind::Lifetime(op(t)),
+            ParameterKind::Const(t) => ParameterKind::Const(op(t)),
         }
     }
 }
 
-impl<T, L> ParameterKind<T, L> {
+impl<T, L, C> ParameterKind<T, L, C> {
     pub fn assert_ty_ref(&self) -> &T {
         self.as_ref().ty().unwrap()
     }
@@ -482,17 +693,22 @@ impl<T, L> ParameterKind<T, L> {
         self.as_ref().lifetime().unwrap()
     }
 
-    pub fn as_ref(&self) -> ParameterKind<&T, &L> {
+    pub fn assert_const_ref(&self) -> &C {
+        self.as_ref().constant().unwrap()
+    }
+
+    pub fn as_ref(&self) -> ParameterKind<&T, &L, &C> {
         match *self {
             ParameterKind::Ty(ref t) => ParameterKind::Ty(t),
             ParameterKind::Lifetime(ref l) => ParameterKind::Lifetime(l),
+            ParameterKind::Const(ref c) => ParameterKind::Const(c),
         }
     }
 
     pub fn is_ty(&self) -> bool {
         match self {
             ParameterKind::Ty(_) => true,
-            ParameterKind::Lifetime(_) => false,
+            ParameterKind::Lifetime(_) | ParameterKind::Const(_) => false,
         }
     }
 
@@ -509,10 +725,17 @@ impl<T, L> ParameterKind<T, L> {
             _ => None,
         }
     }
+
+    pub fn constant(self) -> Option<C> {
+        match self {
+            ParameterKind::Const(c) => Some(c),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct Parameter<TF: TypeFamily>(pub ParameterKind<TF::Type, TF::Lifetime>);
+pub struct Parameter<TF: TypeFamily>(pub ParameterKind<TF::Type, TF::Lifetime, TF::Const>);
 
 impl<TF: TypeFamily> HasTypeFamily for Parameter<TF> {
     type TypeFamily = TF;
@@ -527,17 +750,22 @@ impl<TF: TypeFamily> Parameter<TF> {
         self.as_ref().lifetime().unwrap()
     }
 
-    pub fn as_ref(&self) -> ParameterKind<&TF::Type, &TF::Lifetime> {
+    pub fn assert_const_ref(&self) -> &TF::Const {
+        self.as_ref().constant().unwrap()
+    }
+
+    pub fn as_ref(&self) -> ParameterKind<&TF::Type, &TF::Lifetime, &TF::Const> {
         match &self.0 {
             ParameterKind::Ty(t) => ParameterKind::Ty(t),
             ParameterKind::Lifetime(l) => ParameterKind::Lifetime(l),
+            ParameterKind::Const(c) => ParameterKind::Const(c),
         }
     }
 
     pub fn is_ty(&self) -> bool {
         match self.0 {
             ParameterKind::Ty(_) => true,
-            ParameterKind::Lifetime(_) => false,
+            ParameterKind::Lifetime(_) | ParameterKind::Const(_) => false,
         }
     }
 
@@ -554,6 +782,13 @@ impl<TF: TypeFamily> Parameter<TF> {
             _ => None,
         }
     }
+
+    pub fn constant(self) -> Option<TF::Const> {
+        match self.0 {
+            ParameterKind::Const(c) => Some(c),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Fold)]
@@ -599,6 +834,8 @@ impl<TF: TypeFamily> TraitRef<TF> {
 pub enum WhereClause<TF: TypeFamily> {
     Implemented(TraitRef<TF>),
     ProjectionEq(ProjectionEq<TF>),
+    LifetimeOutlives(LifetimeOutlives<TF>),
+    TypeOutlives(TypeOutlives<TF>),
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Fold)]
@@ -681,6 +918,8 @@ pub enum DomainGoal<TF: TypeFamily> {
 
     Normalize(Normalize<TF>),
 
+    OpaqueEq(OpaqueEq<TF>),
+
     /// True if a type is considered to have been "defined" by the current crate. This is true for
     /// a `struct Foo { }` but false for a `#[upstream] struct Foo { }`. However, for fundamental types
     /// like `Box<T>`, it is true if `T` is local.
@@ -732,6 +971,28 @@ pub enum DomainGoal<TF: TypeFamily> {
     ///
     /// This makes a new type `T` available and makes `DownstreamType(T)` provable for that type.
     DownstreamType(TF::Type),
+
+    /// Used to activate the rules that reveal the hidden types of named opaque types (see
+    /// `OpaqueEq`); without it, a named opaque type is only known to meet its bounds. Like
+    /// `Compatible`, this is introduced with implication, via the `reveal { G }` goal.
+    ///
+    /// (HACK: Having `()` makes some of our macros work better.)
+    Reveal(()),
+
+    /// Used to indicate the crate from whose perspective we are reasoning, in programs made up
+    /// of several crates: the types and traits of that crate are local, and those of the crates
+    /// it depends on are upstream (see `UpstreamCrate`). Introduced with implication, via the
+    /// `in crate c { G }` goal.
+    InCrate(Identifier),
+
+    /// Used to indicate that the crate is a dependency, direct or indirect, of the crate given
+    /// by `InCrate`. Always introduced together with `InCrate`.
+    UpstreamCrate(Identifier),
+
+    /// True if the trait can be used as a trait object, `dyn Trait`. This is the case if the
+    /// trait does not require `Self: Sized`, its associated types have no parameters of their
+    /// own, and its supertraits are object safe as well. `WellFormed(dyn Trait)` requires it.
+    ObjectSafe(TraitId),
 }
 
 pub type QuantifiedWhereClause<TF> = Binders<WhereClause<TF>>;
@@ -800,6 +1061,7 @@ impl<TF: TypeFamily> DomainGoal<TF> {
 /// with unification etc.
 pub enum LeafGoal<TF: TypeFamily> {
     EqGoal(EqGoal<TF>),
+    SubtypeGoal(SubtypeGoal<TF>),
     DomainGoal(DomainGoal<TF>),
 }
 
@@ -809,6 +1071,16 @@ pub struct EqGoal<TF: TypeFamily> {
     pub b: Parameter<TF>,
 }
 
+/// Proves that `a` is a subtype of `b` (`a <: b`). Like equality, this
+/// is handled by unification, which relates the parameters of `a` and
+/// `b` according to their variance; lifetimes that must be related by
+/// subtyping yield outlives constraints.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Fold)]
+pub struct SubtypeGoal<TF: TypeFamily> {
+    pub a: TF::Type,
+    pub b: TF::Type,
+}
+
 /// Proves that the given projection **normalizes** to the given
 /// type. A projection `T::Foo` normalizes to the type `U` if we can
 /// **match it to an impl** and that impl has a `type Foo = V` where
@@ -819,6 +1091,17 @@ pub struct Normalize<TF: TypeFamily> {
     pub ty: TF::Type,
 }
 
+/// Proves **equality** between a named opaque type `Foo<T>` and some
+/// other type `U`. This holds only if the hidden type of `Foo<T>` is
+/// revealed (see `DomainGoal::Reveal`) and is equal to `U`; otherwise,
+/// `Foo<T>` is only equal to itself.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Fold)]
+pub struct OpaqueEq<TF: TypeFamily> {
+    pub opaque_ty_id: OpaqueTyId,
+    pub parameters: Vec<Parameter<TF>>,
+    pub ty: TF::Type,
+}
+
 /// Proves **equality** between a projection `T::Foo` and a type
 /// `U`. Equality can be proven via normalization, but we can also
 /// prove that `T::Foo = V::Foo` if `T = V` without normalizing.
@@ -828,6 +1111,25 @@ pub struct ProjectionEq<TF: TypeFamily> {
     pub ty: TF::Type,
 }
 
+/// Proves that the lifetime `a` outlives the lifetime `b` (`'a: 'b`).
+/// Region relationships are not decided by the solver: proving this
+/// goal always succeeds, yielding a `Constraint::LifetimeOutlives` that
+/// the caller must check.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Fold)]
+pub struct LifetimeOutlives<TF: TypeFamily> {
+    pub a: TF::Lifetime,
+    pub b: TF::Lifetime,
+}
+
+/// Proves that the type `ty` outlives the lifetime `lifetime`
+/// (`T: 'a`). As with `LifetimeOutlives`, this is reported back to the
+/// caller as a region constraint.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Fold)]
+pub struct TypeOutlives<TF: TypeFamily> {
+    pub ty: TF::Type,
+    pub lifetime: TF::Lifetime,
+}
+
 /// Indicates that the `value` is universally quantified over `N`
 /// parameters of the given kinds, where `N == self.binders.len()`. A
 /// variable with depth `i < N` refers to the value at
@@ -961,6 +1263,11 @@ impl<V: IntoIterator> Iterator for BindersIntoIterator<V> {
 pub struct ProgramClauseImplication<TF: TypeFamily> {
     pub consequence: DomainGoal<TF>,
     pub conditions: Vec<Goal<TF>>,
+
+    /// Region constraints that must hold for the clause to apply. These
+    /// are not proven by the solver, but are added to the constraints of
+    /// any answer that uses this clause.
+    pub constraints: Vec<Constraint<TF>>,
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Fold)]
@@ -977,6 +1284,7 @@ impl<TF: TypeFamily> ProgramClause<TF> {
                     ProgramClause::Implies(ProgramClauseImplication {
                         consequence: implication.consequence.into_from_env_goal(),
                         conditions: vec![],
+                        constraints: implication.constraints,
                     })
                 } else {
                     ProgramClause::Implies(implication)
@@ -1064,6 +1372,23 @@ impl<TF: TypeFamily> Goal<TF> {
         Goal::Not(Box::new(self))
     }
 
+    /// Takes a goal `G` and turns it into `reveal { G }`, which desugars
+    /// into `if (Reveal) { G }`
+    pub fn reveal(self) -> Self {
+        Goal::Implies(vec![DomainGoal::Reveal(()).cast()], Box::new(self))
+    }
+
+    /// Takes a goal `G` and turns it into `in crate c { G }`, which
+    /// desugars into `if (InCrate(c), UpstreamCrate(d)...) { G }` for
+    /// the crates `d` that `c` depends on.
+    pub fn in_crate(self, krate: Identifier, dependencies: &[Identifier]) -> Self {
+        let hypotheses = iter::once(DomainGoal::InCrate(krate))
+            .chain(dependencies.iter().map(|&d| DomainGoal::UpstreamCrate(d)))
+            .casted()
+            .collect();
+        Goal::Implies(hypotheses, Box::new(self))
+    }
+
     /// Takes a goal `G` and turns it into `compatible { G }`
     pub fn compatible(self) -> Self {
         // compatible { G } desugars into: forall<T> { if (Compatible, DownstreamType(T)) { G } }
@@ -1106,6 +1431,8 @@ pub enum QuantifierKind {
 #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Fold)]
 pub enum Constraint<TF: TypeFamily> {
     LifetimeEq(TF::Lifetime, TF::Lifetime),
+    LifetimeOutlives(TF::Lifetime, TF::Lifetime),
+    TypeOutlives(TF::Type, TF::Lifetime),
 }
 
 impl<TF: TypeFamily> HasTypeFamily for Constraint<TF> {
@@ -1136,7 +1463,7 @@ impl<TF: TypeFamily> Substitution<TF> {
     /// ...
     /// ```
     ///
-    /// Basically, each value is mapped to a type or lifetime with its
+    /// Basically, each value is mapped to a type, lifetime or const with its
     /// same index.
     pub fn is_identity_subst(&self) -> bool {
         self.parameters
@@ -1151,6 +1478,10 @@ impl<TF: TypeFamily> Substitution<TF> {
                     Lifetime::BoundVar(depth) => index == *depth,
                     _ => false,
                 },
+                ParameterKind::Const(constant) => match constant.lookup_ref() {
+                    Const::BoundVar(depth) => index == *depth,
+                    _ => false,
+                },
             })
     }
 }
@@ -1171,6 +1502,12 @@ impl<'a, TF: TypeFamily> FreeVarFolder<TF> for &'a Substitution<TF> {
         let l = l.assert_lifetime_ref();
         Ok(l.shifted_in(binders))
     }
+
+    fn fold_free_var_const(&mut self, depth: usize, binders: usize) -> Fallible<TF::Const> {
+        let c = &self.parameters[depth];
+        let c = c.assert_const_ref();
+        Ok(c.shifted_in(binders))
+    }
 }
 
 impl<'a, TF: TypeFamily> DefaultPlaceholderFolder for &'a Substitution<TF> {}
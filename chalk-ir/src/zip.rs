@@ -28,6 +28,10 @@ pub trait Zipper<TF: TypeFamily> {
     /// matching spots, beneath `binders` levels of binders.
     fn zip_lifetimes(&mut self, a: &Lifetime<TF>, b: &Lifetime<TF>) -> Fallible<()>;
 
+    /// Indicates that the two consts `a` and `b` were found in
+    /// matching spots, beneath `binders` levels of binders.
+    fn zip_consts(&mut self, a: &Const<TF>, b: &Const<TF>) -> Fallible<()>;
+
     /// Zips two values appearing beneath binders.
     fn zip_binders<T>(&mut self, a: &Binders<T>, b: &Binders<T>) -> Fallible<()>
     where
@@ -47,6 +51,10 @@ where
         (**self).zip_lifetimes(a, b)
     }
 
+    fn zip_consts(&mut self, a: &Const<TF>, b: &Const<TF>) -> Fallible<()> {
+        (**self).zip_consts(a, b)
+    }
+
     fn zip_binders<T>(&mut self, a: &Binders<T>, b: &Binders<T>) -> Fallible<()>
     where
         T: Zip<TF> + Fold<TF, Result = T>,
@@ -133,6 +141,12 @@ impl<TF: TypeFamily> Zip<TF> for Lifetime<TF> {
     }
 }
 
+impl<TF: TypeFamily> Zip<TF> for Const<TF> {
+    fn zip_with<Z: Zipper<TF>>(zipper: &mut Z, a: &Self, b: &Self) -> Fallible<()> {
+        zipper.zip_consts(a, b)
+    }
+}
+
 impl<TF: TypeFamily, T: Zip<TF> + Fold<TF, Result = T>> Zip<TF> for Binders<T> {
     fn zip_with<Z: Zipper<TF>>(zipper: &mut Z, a: &Self, b: &Self) -> Fallible<()> {
         zipper.zip_binders(a, b)
@@ -155,12 +169,17 @@ macro_rules! eq_zip {
 }
 
 eq_zip!(TF => StructId);
+eq_zip!(TF => EnumId);
+eq_zip!(TF => ClosureId);
+eq_zip!(TF => OpaqueTyId);
 eq_zip!(TF => TraitId);
 eq_zip!(TF => TypeId);
 eq_zip!(TF => TypeKindId);
 eq_zip!(TF => TypeName);
 eq_zip!(TF => Identifier);
 eq_zip!(TF => QuantifierKind);
+eq_zip!(TF => Mutability);
+eq_zip!(TF => Scalar);
 eq_zip!(TF => PhantomData<TF>);
 
 /// Generates a Zip impl that zips each field of the struct in turn.
@@ -196,13 +215,41 @@ struct_zip!(impl[TF: TypeFamily] Zip<TF> for ProjectionTy<TF> {
     parameters,
 });
 struct_zip!(impl[TF: TypeFamily] Zip<TF> for Normalize<TF> { projection, ty });
+struct_zip!(impl[TF: TypeFamily] Zip<TF> for OpaqueEq<TF> {
+    opaque_ty_id,
+    parameters,
+    ty
+});
 struct_zip!(impl[TF: TypeFamily] Zip<TF> for ProjectionEq<TF> { projection, ty });
+struct_zip!(impl[TF: TypeFamily] Zip<TF> for LifetimeOutlives<TF> { a, b });
+struct_zip!(impl[TF: TypeFamily] Zip<TF> for TypeOutlives<TF> { ty, lifetime });
 struct_zip!(impl[TF: TypeFamily] Zip<TF> for EqGoal<TF> { a, b });
+struct_zip!(impl[TF: TypeFamily] Zip<TF> for SubtypeGoal<TF> { a, b });
 struct_zip!(impl[TF: TypeFamily] Zip<TF> for ProgramClauseImplication<TF> {
     consequence,
-    conditions
+    conditions,
+    constraints
 });
 
+impl<TF: TypeFamily> Zip<TF> for Constraint<TF> {
+    fn zip_with<Z: Zipper<TF>>(zipper: &mut Z, a: &Self, b: &Self) -> Fallible<()> {
+        match (a, b) {
+            (Constraint::LifetimeEq(a1, a2), Constraint::LifetimeEq(b1, b2))
+            | (Constraint::LifetimeOutlives(a1, a2), Constraint::LifetimeOutlives(b1, b2)) => {
+                Zip::zip_with(zipper, a1, b1)?;
+                Zip::zip_with(zipper, a2, b2)
+            }
+            (Constraint::TypeOutlives(ty_a, l_a), Constraint::TypeOutlives(ty_b, l_b)) => {
+                Zip::zip_with(zipper, ty_a, ty_b)?;
+                Zip::zip_with(zipper, l_a, l_b)
+            }
+            (Constraint::LifetimeEq(..), _)
+            | (Constraint::LifetimeOutlives(..), _)
+            | (Constraint::TypeOutlives(..), _) => Err(NoSolution),
+        }
+    }
+}
+
 impl<TF: TypeFamily> Zip<TF> for Environment<TF> {
     fn zip_with<Z: Zipper<TF>>(zipper: &mut Z, a: &Self, b: &Self) -> Fallible<()> {
         assert_eq!(a.clauses.len(), b.clauses.len()); // or different numbers of clauses
@@ -236,20 +283,34 @@ macro_rules! enum_zip {
 
 enum_zip!(impl<TF> for WellFormed<TF> { Trait, Ty });
 enum_zip!(impl<TF> for FromEnv<TF> { Trait, Ty });
-enum_zip!(impl<TF> for WhereClause<TF> { Implemented, ProjectionEq });
+enum_zip!(impl<TF> for WhereClause<TF> {
+    Implemented,
+    ProjectionEq,
+    LifetimeOutlives,
+    TypeOutlives
+});
 enum_zip!(impl<TF> for DomainGoal<TF> {
     Holds,
     WellFormed,
     FromEnv,
     Normalize,
+    OpaqueEq,
     IsLocal,
     IsUpstream,
     IsFullyVisible,
     LocalImplAllowed,
     Compatible,
-    DownstreamType
+    DownstreamType,
+    Reveal,
+    InCrate,
+    UpstreamCrate,
+    ObjectSafe
+});
+enum_zip!(impl<TF> for LeafGoal<TF> {
+    DomainGoal,
+    EqGoal,
+    SubtypeGoal
 });
-enum_zip!(impl<TF> for LeafGoal<TF> { DomainGoal, EqGoal });
 enum_zip!(impl<TF> for ProgramClause<TF> { Implies, ForAll });
 
 // Annoyingly, Goal cannot use `enum_zip` because some variants have
@@ -286,12 +347,15 @@ impl<TF: TypeFamily> Zip<TF> for Goal<TF> {
 }
 
 // I'm too lazy to make `enum_zip` support type parameters.
-impl<T: Zip<TF>, L: Zip<TF>, TF: TypeFamily> Zip<TF> for ParameterKind<T, L> {
+impl<T: Zip<TF>, L: Zip<TF>, C: Zip<TF>, TF: TypeFamily> Zip<TF> for ParameterKind<T, L, C> {
     fn zip_with<Z: Zipper<TF>>(zipper: &mut Z, a: &Self, b: &Self) -> Fallible<()> {
         match (a, b) {
             (ParameterKind::Ty(a), ParameterKind::Ty(b)) => Zip::zip_with(zipper, a, b),
             (ParameterKind::Lifetime(a), ParameterKind::Lifetime(b)) => Zip::zip_with(zipper, a, b),
-            (ParameterKind::Ty(_), _) | (ParameterKind::Lifetime(_), _) => {
+            (ParameterKind::Const(a), ParameterKind::Const(b)) => Zip::zip_with(zipper, a, b),
+            (ParameterKind::Ty(_), _)
+            | (ParameterKind::Lifetime(_), _)
+            | (ParameterKind::Const(_), _) => {
                 panic!("zipping things of mixed kind")
             }
         }
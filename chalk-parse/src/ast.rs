@@ -21,15 +21,23 @@ pub struct Program {
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum Item {
     StructDefn(StructDefn),
+    EnumDefn(EnumDefn),
+    ClosureDefn(ClosureDefn),
+    OpaqueTyDefn(OpaqueTyDefn),
     TraitDefn(TraitDefn),
+    TraitAlias(TraitAliasDefn),
     Impl(Impl),
     Clause(Clause),
+    Module(ModuleDefn),
+    Crate(CrateDefn),
+    Include(Include),
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct StructDefn {
     pub name: Identifier,
     pub parameter_kinds: Vec<ParameterKind>,
+    pub parameter_bounds: Vec<ParameterBounds>,
     pub where_clauses: Vec<QuantifiedWhereClause>,
     pub fields: Vec<Field>,
     pub flags: StructFlags,
@@ -41,13 +49,120 @@ pub struct StructFlags {
     pub fundamental: bool,
 }
 
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EnumDefn {
+    pub name: Identifier,
+    pub parameter_kinds: Vec<ParameterKind>,
+    pub parameter_bounds: Vec<ParameterBounds>,
+    pub where_clauses: Vec<QuantifiedWhereClause>,
+    pub variants: Vec<Variant>,
+    pub flags: EnumFlags,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+/// A variant of an enum. The fields of a tuple variant like `Some(T)`
+/// are named by their position, as in Rust.
+pub struct Variant {
+    pub name: Identifier,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EnumFlags {
+    pub upstream: bool,
+}
+
+/// A closure type, declared like a method whose `self` says how the
+/// closure uses its upvars:
+///
+/// ```notrust
+/// closure foo<T>(&mut self, x: u32) -> T { T }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ClosureDefn {
+    pub name: Identifier,
+    pub kind: ClosureKind,
+    pub parameter_kinds: Vec<ParameterKind>,
+    pub argument_types: Vec<Ty>,
+    pub return_type: Ty,
+    pub upvars: Vec<Ty>,
+}
+
+/// A named opaque type, like
+/// `opaque type Foo<T>: Iterator<Item = T> = Bar<T>;`, where `Bar<T>`
+/// is the hidden type.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OpaqueTyDefn {
+    pub name: Identifier,
+    pub parameter_kinds: Vec<ParameterKind>,
+    pub bounds: Vec<QuantifiedInlineBound>,
+    pub ty: Ty,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ClosureKind {
+    Fn,
+    FnMut,
+    FnOnce,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct TraitDefn {
     pub name: Identifier,
     pub parameter_kinds: Vec<ParameterKind>,
+    pub parameter_bounds: Vec<ParameterBounds>,
+    /// Bounds on `Self`, like the `Clone` in `trait Copy: Clone { }`.
+    pub supertraits: Vec<QuantifiedInlineBound>,
     pub where_clauses: Vec<QuantifiedWhereClause>,
     pub assoc_ty_defns: Vec<AssocTyDefn>,
     pub flags: TraitFlags,
+    /// Set by `#[lang(..)]`, which marks the trait as being the one the
+    /// compiler has built-in knowledge of, like `Copy` or `FnOnce`.
+    pub lang_item: Option<LangItem>,
+}
+
+/// A trait alias, which names a combination of bounds:
+///
+/// ```notrust
+/// trait Alias<T> = Foo<T> + Bar where T: Baz;
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TraitAliasDefn {
+    pub name: Identifier,
+    pub parameter_kinds: Vec<ParameterKind>,
+    pub parameter_bounds: Vec<ParameterBounds>,
+    pub bounds: Vec<QuantifiedInlineBound>,
+    pub where_clauses: Vec<QuantifiedWhereClause>,
+}
+
+impl TraitAliasDefn {
+    /// The names of the traits the alias is defined in terms of.
+    pub fn trait_names(&self) -> Vec<Identifier> {
+        let parameter_bounds = self
+            .parameter_bounds
+            .iter()
+            .flat_map(|b| &b.bounds)
+            .filter_map(|bound| match bound {
+                ParameterBound::Bound(qil) => Some(qil),
+                ParameterBound::Relaxed(_) => None,
+            });
+        let bounds = self
+            .bounds
+            .iter()
+            .chain(parameter_bounds)
+            .map(|qil| qil.bound.trait_name());
+        let where_clauses = self
+            .where_clauses
+            .iter()
+            .filter_map(|qwc| match &qwc.where_clause {
+                WhereClause::Implemented { trait_ref } => Some(trait_ref.trait_name),
+                WhereClause::ProjectionEq { projection, .. } => {
+                    Some(projection.trait_ref.trait_name)
+                }
+                _ => None,
+            });
+        bounds.chain(where_clauses).collect()
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -59,24 +174,56 @@ pub struct TraitFlags {
     pub non_enumerable: bool,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LangItem {
+    Sized,
+    Copy,
+    Clone,
+    Drop,
+    FnOnce,
+    FnMut,
+    Fn,
+    Send,
+    Sync,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct AssocTyDefn {
     pub name: Identifier,
     pub parameter_kinds: Vec<ParameterKind>,
     pub bounds: Vec<QuantifiedInlineBound>,
     pub where_clauses: Vec<QuantifiedWhereClause>,
+    pub default: Option<Ty>,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum ParameterKind {
     Ty(Identifier),
     Lifetime(Identifier),
+    Const(Identifier),
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum Parameter {
     Ty(Ty),
     Lifetime(Lifetime),
+    Const(Const),
+}
+
+/// The bounds written on a type parameter in the parameter list of an
+/// item, like `T: Clone + ?Sized` in `struct Box<T: Clone + ?Sized>`.
+/// These are sugar for the where clauses `T: Clone, T: ?Sized`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParameterBounds {
+    pub name: Identifier,
+    pub bounds: Vec<ParameterBound>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParameterBound {
+    Bound(QuantifiedInlineBound),
+    /// `?Sized`; see `WhereClause::Relaxed`.
+    Relaxed(Identifier),
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -86,6 +233,16 @@ pub enum InlineBound {
     ProjectionEqBound(ProjectionEqBound),
 }
 
+impl InlineBound {
+    /// The name of the trait being bounded on.
+    pub fn trait_name(&self) -> Identifier {
+        match self {
+            InlineBound::TraitBound(b) => b.trait_name,
+            InlineBound::ProjectionEqBound(b) => b.trait_bound.trait_name,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct QuantifiedInlineBound {
     pub parameter_kinds: Vec<ParameterKind>,
@@ -114,6 +271,7 @@ pub struct ProjectionEqBound {
 pub enum Kind {
     Ty,
     Lifetime,
+    Const,
 }
 
 impl fmt::Display for Kind {
@@ -121,13 +279,41 @@ impl fmt::Display for Kind {
         f.write_str(match *self {
             Kind::Ty => "type",
             Kind::Lifetime => "lifetime",
+            Kind::Const => "const",
         })
     }
 }
 
+/// A `crate b: a { ... }` block, defining the crate `b` that depends on
+/// the crate `a`. Like a module, its items are named by paths like
+/// `b::Foo` from outside it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CrateDefn {
+    pub name: Identifier,
+    pub dependencies: Vec<Identifier>,
+    pub items: Vec<Item>,
+}
+
+/// An `include "prelude.chalk";` directive, which stands for the items
+/// of the file at `path`, relative to the directory of the including
+/// file.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Include {
+    pub path: String,
+}
+
+/// A `mod foo { ... }` block. The items inside are named by paths
+/// like `foo::Bar` from outside the module.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ModuleDefn {
+    pub name: Identifier,
+    pub items: Vec<Item>,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Impl {
     pub parameter_kinds: Vec<ParameterKind>,
+    pub parameter_bounds: Vec<ParameterBounds>,
     pub trait_ref: TraitRef,
     pub polarity: Polarity,
     pub where_clauses: Vec<QuantifiedWhereClause>,
@@ -171,6 +357,77 @@ pub enum Ty {
         lifetime_names: Vec<Identifier>,
         ty: Box<Ty>,
     },
+    Ref {
+        lifetime: Lifetime,
+        mutability: Mutability,
+        ty: Box<Ty>,
+    },
+    Raw {
+        mutability: Mutability,
+        ty: Box<Ty>,
+    },
+    Tuple {
+        types: Vec<Ty>,
+    },
+    /// A function pointer type `fn(A, B) -> R`; a missing return type
+    /// is parsed as `()`.
+    Fn {
+        args: Vec<Ty>,
+        ret: Box<Ty>,
+    },
+    Slice {
+        ty: Box<Ty>,
+    },
+    Array {
+        ty: Box<Ty>,
+        len: Const,
+    },
+    Str,
+    Scalar {
+        ty: ScalarType,
+    },
+    Never,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ScalarType {
+    Bool,
+    Char,
+    Int(IntTy),
+    Uint(UintTy),
+    Float(FloatTy),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum IntTy {
+    Isize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum UintTy {
+    Usize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FloatTy {
+    F32,
+    F64,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Mutability {
+    Mut,
+    Not,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -178,6 +435,15 @@ pub enum Lifetime {
     Id { name: Identifier },
 }
 
+/// A const generic argument. In argument lists like `Foo<N>`, a const
+/// parameter `N` parses as a type and is only recognized as a const
+/// during lowering.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Const {
+    Id { name: Identifier },
+    Value(u64),
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct ProjectionTy {
     pub trait_ref: TraitRef,
@@ -224,8 +490,29 @@ impl fmt::Display for Identifier {
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum WhereClause {
-    Implemented { trait_ref: TraitRef },
-    ProjectionEq { projection: ProjectionTy, ty: Ty },
+    Implemented {
+        trait_ref: TraitRef,
+    },
+    ProjectionEq {
+        projection: ProjectionTy,
+        ty: Ty,
+    },
+    /// `'a: 'b`
+    LifetimeOutlives {
+        a: Lifetime,
+        b: Lifetime,
+    },
+    /// `T: 'a`
+    TypeOutlives {
+        ty: Ty,
+        lifetime: Lifetime,
+    },
+    /// `T: ?Sized`, which removes the implicit `T: Sized` bound on a type
+    /// parameter.
+    Relaxed {
+        ty: Ty,
+        trait_name: Identifier,
+    },
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -242,6 +529,8 @@ pub enum DomainGoal {
     LocalImplAllowed { trait_ref: TraitRef },
     Compatible,
     DownstreamType { ty: Ty },
+    Reveal,
+    ObjectSafe { id: Identifier },
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -249,6 +538,7 @@ pub enum LeafGoal {
     DomainGoal { goal: DomainGoal },
     UnifyTys { a: Ty, b: Ty },
     UnifyLifetimes { a: Lifetime, b: Lifetime },
+    Subtype { a: Ty, b: Ty },
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -283,6 +573,12 @@ pub enum Goal {
     /// The `compatible { G }` syntax
     Compatible(Box<Goal>),
 
+    /// The `reveal { G }` syntax
+    Reveal(Box<Goal>),
+
+    /// The `in crate c { G }` syntax
+    InCrate(Identifier, Box<Goal>),
+
     // Additional kinds of goals:
     Leaf(LeafGoal),
 }
@@ -4,16 +4,30 @@
 
 use chalk_derive::Fold;
 use chalk_ir::cast::Cast;
-use chalk_ir::family::{ChalkIr, HasTypeFamily};
+use chalk_ir::copy_fold;
+use chalk_ir::family::{ChalkIr, HasTypeFamily, TypeFamily};
 use chalk_ir::fold::{shift::Shift, Fold, Folder};
 use chalk_ir::{
-    Binders, Identifier, ImplId, Lifetime, Parameter, ParameterKind, ProjectionEq, ProjectionTy,
-    QuantifiedWhereClause, RawId, StructId, TraitId, TraitRef, Ty, TypeId, TypeName, WhereClause,
+    Binders, ClosureId, Const, EnumId, Goal, Identifier, ImplId, Lifetime, OpaqueTyId, Parameter,
+    ParameterKind, ProjectionEq, ProjectionTy, QuantifiedWhereClause, RawId, StructId, TraitId,
+    TraitRef, Ty, TypeId, TypeName, WhereClause,
 };
 use std::iter;
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub enum LangItem {}
+/// A trait that the compiler has built-in knowledge of, as declared by
+/// `#[lang(..)]`. Built-in types get impls of some of these for free.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LangItem {
+    Sized,
+    Copy,
+    Clone,
+    Drop,
+    FnOnce,
+    FnMut,
+    Fn,
+    Send,
+    Sync,
+}
 
 /// Identifier for an "associated type value" found in some impl.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -24,6 +38,9 @@ pub struct ImplDatum {
     pub polarity: Polarity,
     pub binders: Binders<ImplDatumBound>,
     pub impl_type: ImplType,
+    /// The crate that defines the impl, if the program is made up of
+    /// several crates (see `CrateDatum`).
+    pub krate: Option<Identifier>,
     pub associated_ty_value_ids: Vec<AssociatedTyValueId>,
 }
 
@@ -83,6 +100,149 @@ pub struct StructDatumBound {
 pub struct StructFlags {
     pub upstream: bool,
     pub fundamental: bool,
+    /// The crate that defines the struct, if any; when set, whether
+    /// the struct is local or upstream depends on the crate we are
+    /// reasoning from, and `upstream` is ignored.
+    pub krate: Option<Identifier>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EnumDatum {
+    pub binders: Binders<EnumDatumBound>,
+    pub id: EnumId,
+    pub flags: EnumFlags,
+}
+
+impl EnumDatum {
+    pub fn name(&self) -> TypeName {
+        self.id.cast()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EnumDatumBound {
+    pub variants: Vec<EnumVariant>,
+    pub where_clauses: Vec<QuantifiedWhereClause<ChalkIr>>,
+}
+
+impl EnumDatumBound {
+    /// The types of all fields of all variants, in declaration order.
+    pub fn fields(&self) -> impl Iterator<Item = &Ty<ChalkIr>> {
+        self.variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EnumVariant {
+    pub name: Identifier,
+    pub fields: Vec<Ty<ChalkIr>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EnumFlags {
+    pub upstream: bool,
+    /// The crate that defines the enum, if any (see `StructFlags`).
+    pub krate: Option<Identifier>,
+}
+
+/// The type of a closure. A closure is generic over the parameters of
+/// the item that defines it; its upvars are the types of the values it
+/// captures, and play the role of its fields.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ClosureDatum {
+    pub binders: Binders<ClosureDatumBound>,
+    pub id: ClosureId,
+    pub kind: ClosureKind,
+    /// The crate that defines the closure, if any; see `StructFlags::krate`.
+    pub krate: Option<Identifier>,
+}
+
+impl ClosureDatum {
+    pub fn name(&self) -> TypeName {
+        self.id.cast()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Fold)]
+#[has_type_family(ChalkIr)]
+pub struct ClosureDatumBound {
+    /// The argument types of the closure's signature.
+    pub inputs: Vec<Ty<ChalkIr>>,
+    pub output: Ty<ChalkIr>,
+    pub upvars: Vec<Ty<ChalkIr>>,
+}
+
+impl HasTypeFamily for ClosureDatumBound {
+    type TypeFamily = ChalkIr;
+}
+
+/// Which of the `Fn` traits a closure implements, judging by how it uses
+/// its upvars. Every closure is `FnOnce`; an `FnMut` closure is also
+/// `FnOnce`, and an `Fn` closure is all three.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ClosureKind {
+    Fn,
+    FnMut,
+    FnOnce,
+}
+
+/// One crate of a program made up of several crates, declared like
+/// `crate b: a { ... }`. Whether the items of a crate are local or
+/// upstream is relative to the crate we are reasoning from; see
+/// `DomainGoal::InCrate`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CrateDatum {
+    pub name: Identifier,
+    /// All the crates this crate depends on, directly or indirectly.
+    pub dependencies: Vec<Identifier>,
+}
+
+impl CrateDatum {
+    /// Turns `goal` into a goal asked from the perspective of this crate.
+    pub fn perspective(&self, goal: Goal<ChalkIr>) -> Goal<ChalkIr> {
+        goal.in_crate(self.name, &self.dependencies)
+    }
+}
+
+/// A named opaque type, declared like
+/// `opaque type Foo<T>: Iterator<Item = T> = Bar<T>;`. Outside of
+/// `reveal { .. }` goals, all that is known about `Foo<T>` is that it
+/// meets its bounds; its hidden type `Bar<T>` is only visible when
+/// revealed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OpaqueTyDatum {
+    pub id: OpaqueTyId,
+
+    /// The bounds and hidden type, in the scope of the parameters of
+    /// the opaque type.
+    pub binders: Binders<OpaqueTyDatumBound>,
+
+    /// The crate that defines the opaque type, if any; see
+    /// `StructFlags::krate`.
+    pub krate: Option<Identifier>,
+}
+
+impl OpaqueTyDatum {
+    pub fn name(&self) -> TypeName {
+        self.id.cast()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Fold)]
+#[has_type_family(ChalkIr)]
+pub struct OpaqueTyDatumBound {
+    /// The bounds that the opaque type is known to meet, like the
+    /// `Iterator<Item = T>` in the example above.
+    pub bounds: Vec<QuantifiedInlineBound>,
+
+    /// The hidden type, like the `Bar<T>` in the example above.
+    pub hidden_ty: Ty<ChalkIr>,
+}
+
+impl HasTypeFamily for OpaqueTyDatumBound {
+    type TypeFamily = ChalkIr;
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -108,6 +268,10 @@ impl TraitDatum {
     pub fn is_non_enumerable_trait(&self) -> bool {
         self.flags.non_enumerable
     }
+
+    pub fn is_alias(&self) -> bool {
+        self.flags.alias
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -128,6 +292,11 @@ pub struct TraitFlags {
     pub upstream: bool,
     pub fundamental: bool,
     pub non_enumerable: bool,
+    /// The crate that defines the trait, if any (see `StructFlags`).
+    pub krate: Option<Identifier>,
+    /// A trait alias, like `trait Alias = Foo + Bar;`, which is
+    /// implemented exactly when its where clauses hold.
+    pub alias: bool,
 }
 
 /// An inline bound, e.g. `: Foo<K>` in `impl<K, T: Foo<K>> SomeType<T>`.
@@ -267,6 +436,7 @@ impl<'a> ToParameter for (&'a ParameterKind<()>, usize) {
         match *binder {
             ParameterKind::Lifetime(_) => Lifetime::BoundVar(index).cast(),
             ParameterKind::Ty(_) => Ty::BoundVar(index).cast(),
+            ParameterKind::Const(_) => Const::BoundVar(index).cast(),
         }
     }
 }
@@ -307,7 +477,7 @@ pub struct AssociatedTyDatum {
 }
 
 /// Encodes the parts of `AssociatedTyDatum` where the parameters
-/// `P0..Pm` are in scope (`bounds` and `where_clauses`).
+/// `P0..Pm` are in scope (`bounds`, `where_clauses` and `default`).
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Fold)]
 #[has_type_family(ChalkIr)]
 pub struct AssociatedTyDatumBound {
@@ -319,6 +489,11 @@ pub struct AssociatedTyDatumBound {
 
     /// Where clauses that must hold for the projection to be well-formed.
     pub where_clauses: Vec<QuantifiedWhereClause<ChalkIr>>,
+
+    /// The default value, like the `Vec<Self>` in `type Item = Vec<Self>;`,
+    /// which is used by impls that do not give the associated type a value
+    /// themselves.
+    pub default: Option<Ty<ChalkIr>>,
 }
 
 impl HasTypeFamily for AssociatedTyDatumBound {
@@ -396,6 +571,16 @@ pub struct AssociatedTyValue {
     /// ```
     pub associated_ty_id: TypeId,
 
+    /// Whether impls that specialize this one may give the associated
+    /// type another value.
+    ///
+    /// ```ignore
+    /// impl<T> Iterator for Foo<T> {
+    ///     default type Item = XXX; // <-- `Defaultness::Default`
+    /// }
+    /// ```
+    pub defaultness: Defaultness,
+
     /// Additional binders declared on the associated type itself,
     /// beyond those from the impl. This would be empty for normal
     /// associated types, but non-empty for generic associated types.
@@ -409,6 +594,25 @@ pub struct AssociatedTyValue {
     pub value: Binders<AssociatedTyValueBound>,
 }
 
+/// Whether an item of an impl is marked `default`, and so can be
+/// overridden by the impls that specialize it (RFC 1210).
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Defaultness {
+    Default,
+    Final,
+}
+
+impl Defaultness {
+    pub fn is_default(&self) -> bool {
+        match *self {
+            Defaultness::Default => true,
+            Defaultness::Final => false,
+        }
+    }
+}
+
+copy_fold!(TF => Defaultness);
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Fold)]
 #[has_type_family(ChalkIr)]
 pub struct AssociatedTyValueBound {
@@ -430,6 +634,9 @@ pub struct TypeKind {
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum TypeSort {
     Struct,
+    Enum,
+    Closure,
+    Opaque,
     Trait,
 }
 
@@ -1,6 +1,12 @@
 use self::builder::ClauseBuilder;
+use self::builtin_traits::{
+    builtin_auto_trait_opt_out, fn_ptr_signature, push_builtin_normalize_clauses,
+    push_builtin_trait_impls, push_closure_impls,
+};
+use self::builtin_types::{builtin_type_fields, push_builtin_type_clauses};
 use self::env_elaborator::elaborate_env_clauses;
 use self::program_clauses::ToProgramClauses;
+use crate::object_safety;
 use crate::split::Split;
 use crate::RustIrDatabase;
 use chalk_ir::cast::Cast;
@@ -8,13 +14,17 @@ use chalk_ir::could_match::CouldMatch;
 use chalk_ir::family::ChalkIr;
 use chalk_ir::*;
 use rustc_hash::FxHashSet;
+use std::iter;
+use std::marker::PhantomData;
 
 pub mod builder;
+mod builtin_traits;
+mod builtin_types;
 mod env_elaborator;
 pub mod program_clauses;
 
-/// For auto-traits, we generate a default rule for every struct,
-/// unless there is a manual impl for that struct given explicitly.
+/// For auto-traits, we generate a default rule for every struct, enum
+/// and built-in type, unless there is a manual impl for that type given explicitly.
 ///
 /// So, if you have `impl Send for MyList<Foo>`, then we would
 /// generate no rule for `MyList` at all -- similarly if you have
@@ -43,18 +53,25 @@ pub mod program_clauses;
 ///         Implemented(Box<Option<MyList<T>>>: Send).
 /// }
 /// ```
+///
+/// For an enum, the conditions include the fields of every variant;
+/// for a closure, its upvars; and for a built-in type like `&'a T`,
+/// they are the types it is built from.
 pub fn push_auto_trait_impls(
     builder: &mut ClauseBuilder<'_>,
     auto_trait_id: TraitId,
-    struct_id: StructId,
+    type_name: TypeName,
 ) {
     debug_heading!(
         "push_auto_trait_impls({:?}, {:?})",
         auto_trait_id,
-        struct_id
+        type_name
     );
 
-    let struct_datum = &builder.db.struct_datum(struct_id);
+    let binders = match type_fields(builder.db, type_name) {
+        Some(binders) => binders,
+        None => return,
+    };
 
     // Must be an auto trait.
     assert!(builder.db.trait_datum(auto_trait_id).is_auto_trait());
@@ -63,17 +80,21 @@ pub fn push_auto_trait_impls(
     assert_eq!(builder.db.trait_datum(auto_trait_id).binders.len(), 1);
 
     // If there is a `impl AutoTrait for Foo<..>` or `impl !AutoTrait
-    // for Foo<..>`, where `Foo` is the struct we're looking at, then
+    // for Foo<..>`, where `Foo` is the type we're looking at, then
     // we don't generate our own rules.
-    if builder.db.impl_provided_for(auto_trait_id, struct_id) {
+    if builder.db.impl_provided_for(auto_trait_id, type_name) {
         debug!("impl provided");
         return;
     }
 
-    let binders = struct_datum.binders.map_ref(|b| &b.fields);
+    if builtin_auto_trait_opt_out(builder.db, auto_trait_id, type_name) {
+        debug!("built-in type opts out");
+        return;
+    }
+
     builder.push_binders(&binders, |builder, fields| {
         let self_ty: Ty<_> = ApplicationTy {
-            name: struct_id.cast(),
+            name: type_name,
             parameters: builder.placeholders_in_scope().to_vec(),
         }
         .cast();
@@ -100,6 +121,117 @@ pub fn push_auto_trait_impls(
     });
 }
 
+/// Pushes the impls of the auto trait `auto_trait_id` for a higher-ranked
+/// function pointer type `self_ty`, like `for<'a> fn(&'a u8)`, which
+/// `push_auto_trait_impls` cannot name. Function pointers have no fields,
+/// so unless the program provides its own impl for them, we get:
+///
+/// ```notrust
+/// Implemented(for<'a> fn(&'a u8): Send)
+/// ```
+fn push_fn_ptr_auto_trait_impls(
+    builder: &mut ClauseBuilder<'_>,
+    auto_trait_id: TraitId,
+    self_ty: &Ty<ChalkIr>,
+) {
+    let signature = match fn_ptr_signature(self_ty) {
+        Some(signature) => signature,
+        None => return,
+    };
+    let type_name = match &signature.value {
+        Ty::Apply(apply) => apply.name,
+        _ => unreachable!(),
+    };
+
+    if builder.db.impl_provided_for(auto_trait_id, type_name)
+        || builtin_auto_trait_opt_out(builder.db, auto_trait_id, type_name)
+    {
+        return;
+    }
+
+    builder.push_fact(TraitRef {
+        trait_id: auto_trait_id,
+        parameters: vec![self_ty.clone().cast()],
+    });
+}
+
+/// When its hidden type is revealed, a named opaque type implements
+/// whatever traits its hidden type does. So, given
+/// `opaque type Foo<T>: Clone = Bar<T>;`, we generate for the trait
+/// `Trait` (where `Trait` may be any trait, including auto traits):
+///
+/// ```notrust
+/// forall<T, P1..Pn> {
+///     Implemented(Foo<T>: Trait<P1..Pn>) :-
+///         Reveal,
+///         Implemented(Bar<T>: Trait<P1..Pn>).
+/// }
+/// ```
+fn push_revealed_trait_impl(
+    builder: &mut ClauseBuilder<'_>,
+    opaque_ty_id: OpaqueTyId,
+    trait_id: TraitId,
+) {
+    let opaque_ty_datum = builder.db.opaque_ty_data(opaque_ty_id);
+    let trait_datum = builder.db.trait_datum(trait_id);
+
+    builder.push_binders(&opaque_ty_datum.binders, |builder, opaque_ty_bound| {
+        let opaque_ty: Ty<_> = ApplicationTy {
+            name: opaque_ty_datum.name(),
+            parameters: builder.placeholders_in_scope().to_vec(),
+        }
+        .cast();
+
+        // The parameters of the trait, apart from `Self`.
+        let trait_binders = Binders {
+            binders: trait_datum.binders.binders[1..].to_vec(),
+            value: PhantomData::<ChalkIr>,
+        };
+        builder.push_binders(&trait_binders, |builder, PhantomData| {
+            let num_trait_parameters = trait_binders.binders.len();
+            let parameters = builder.placeholders_in_scope();
+            let trait_parameters = &parameters[parameters.len() - num_trait_parameters..];
+            let trait_ref = |self_ty: Ty<ChalkIr>| TraitRef {
+                trait_id,
+                parameters: iter::once(self_ty.cast())
+                    .chain(trait_parameters.iter().cloned())
+                    .collect(),
+            };
+
+            let conditions: Vec<Goal<_>> = vec![
+                DomainGoal::Reveal(()).cast(),
+                trait_ref(opaque_ty_bound.hidden_ty.clone()).cast(),
+            ];
+            builder.push_clause(trait_ref(opaque_ty), conditions);
+        });
+    });
+}
+
+/// Returns the types that a value of type `type_name` is built from,
+/// bound by the type's parameters: the fields of a struct, the fields of
+/// every variant of an enum, the upvars of a closure, or, for a built-in
+/// type, its `builtin_type_fields`.
+fn type_fields(db: &dyn RustIrDatabase, type_name: TypeName) -> Option<Binders<Vec<Ty<ChalkIr>>>> {
+    match type_name {
+        TypeName::TypeKindId(TypeKindId::StructId(struct_id)) => Some(
+            db.struct_datum(struct_id)
+                .binders
+                .map_ref(|b| b.fields.clone()),
+        ),
+        TypeName::TypeKindId(TypeKindId::EnumId(enum_id)) => Some(
+            db.enum_datum(enum_id)
+                .binders
+                .map_ref(|b| b.fields().cloned().collect()),
+        ),
+        TypeName::TypeKindId(TypeKindId::ClosureId(closure_id)) => Some(
+            db.closure_datum(closure_id)
+                .binders
+                .map_ref(|b| b.upvars.clone()),
+        ),
+        _ => builtin_type_fields(type_name),
+    }
+}
+
 /// Given some goal `goal` that must be proven, along with
 /// its `environment`, figures out the program clauses that apply
 /// to this goal from the Rust program. So for example if the goal
@@ -156,10 +288,9 @@ fn program_clauses_that_could_match(
             let trait_datum = db.trait_datum(trait_id);
             if trait_datum.is_auto_trait() {
                 match trait_ref.parameters[0].assert_ty_ref() {
-                    Ty::Apply(apply) => {
-                        if let TypeName::TypeKindId(TypeKindId::StructId(struct_id)) = apply.name {
-                            push_auto_trait_impls(builder, trait_id, struct_id);
-                        }
+                    Ty::Apply(apply) => push_auto_trait_impls(builder, trait_id, apply.name),
+                    self_ty @ Ty::ForAll(_) => {
+                        push_fn_ptr_auto_trait_impls(builder, trait_id, self_ty)
                     }
                     Ty::InferenceVar(_) => {
                         panic!("auto-traits should flounder if nothing is known")
@@ -210,6 +341,10 @@ fn program_clauses_that_could_match(
             // that goal, because they let us prove other things but
             // not `Clone`.
             let self_ty = trait_ref.self_type_parameter().unwrap(); // This cannot be None
+
+            // Built-in types get some impls for free, e.g. `&'a T: Copy`.
+            push_builtin_trait_impls(builder, trait_id, &self_ty);
+
             match &self_ty {
                 Ty::Opaque(exists_qwcs) | Ty::Dyn(exists_qwcs) => {
                     // In this arm, `self_ty` is the `dyn Fn(&u8)`,
@@ -230,6 +365,21 @@ fn program_clauses_that_could_match(
                         });
                     }
                 }
+                // Closures implement the `Fn` traits that their kind
+                // allows, with their own signature; see `push_closure_impls`.
+                Ty::Apply(ApplicationTy {
+                    name: TypeName::TypeKindId(TypeKindId::ClosureId(closure_id)),
+                    ..
+                }) => push_closure_impls(builder, trait_id, *closure_id),
+                // Named opaque types implement the traits in their bounds,
+                // and, when revealed, those of their hidden type.
+                Ty::Apply(ApplicationTy {
+                    name: TypeName::TypeKindId(TypeKindId::OpaqueTyId(opaque_ty_id)),
+                    ..
+                }) => {
+                    db.opaque_ty_data(*opaque_ty_id).to_program_clauses(builder);
+                    push_revealed_trait_impl(builder, *opaque_ty_id, trait_id);
+                }
                 _ => {}
             }
 
@@ -239,6 +389,39 @@ fn program_clauses_that_could_match(
             db.associated_ty_data(projection_predicate.projection.associated_ty_id)
                 .to_program_clauses(builder);
         }
+        // We do not reason about regions, so outlives requirements always
+        // hold, but are passed back to the caller as region constraints:
+        //
+        // ```
+        // forall<'a, 'b> { Outlives('a: 'b) } with constraints ['a: 'b]
+        // forall<T, 'a> { Outlives(T: 'a) } with constraints [T: 'a]
+        // ```
+        //
+        // Facts from the environment (e.g. implied bounds) may prove the
+        // same goals without constraints.
+        DomainGoal::Holds(WhereClause::LifetimeOutlives(..)) => {
+            builder.push_bound_lifetime(|builder, a| {
+                builder.push_bound_lifetime(|builder, b| {
+                    builder.push_fact_with_constraints(
+                        LifetimeOutlives { a, b },
+                        Some(Constraint::LifetimeOutlives(a, b)),
+                    );
+                });
+            });
+        }
+        DomainGoal::Holds(WhereClause::TypeOutlives(..)) => {
+            builder.push_bound_ty(|builder, ty| {
+                builder.push_bound_lifetime(|builder, lifetime| {
+                    builder.push_fact_with_constraints(
+                        TypeOutlives {
+                            ty: ty.clone(),
+                            lifetime,
+                        },
+                        Some(Constraint::TypeOutlives(ty, lifetime)),
+                    );
+                });
+            });
+        }
         DomainGoal::WellFormed(WellFormed::Trait(trait_predicate)) => {
             db.trait_datum(trait_predicate.trait_id)
                 .to_program_clauses(builder);
@@ -270,11 +453,32 @@ fn program_clauses_that_could_match(
                 trait_id,
                 trait_parameters,
             );
+
+            // Function pointers and closures get their `FnOnce::Output` for free.
+            push_builtin_normalize_clauses(builder, projection);
+
+            // Named opaque types normalize as their bounds say.
+            if let Ty::Apply(apply) = trait_parameters[0].assert_ty_ref() {
+                if let Some(opaque_ty_id) = apply.opaque_ty_id() {
+                    db.opaque_ty_data(opaque_ty_id).to_program_clauses(builder);
+                }
+            }
         }
+        DomainGoal::OpaqueEq(opaque_eq) => db
+            .opaque_ty_data(opaque_eq.opaque_ty_id)
+            .to_program_clauses(builder),
         DomainGoal::LocalImplAllowed(trait_ref) => db
             .trait_datum(trait_ref.trait_id)
             .to_program_clauses(builder),
-        DomainGoal::Compatible(()) => (),
+        DomainGoal::Compatible(())
+        | DomainGoal::Reveal(())
+        | DomainGoal::InCrate(_)
+        | DomainGoal::UpstreamCrate(_) => (),
+        DomainGoal::ObjectSafe(trait_id) => {
+            if object_safety::is_object_safe(db, *trait_id) {
+                builder.push_fact(DomainGoal::ObjectSafe(*trait_id));
+            }
+        }
     };
 }
 
@@ -313,9 +517,8 @@ fn push_program_clauses_for_associated_type_values_in_impls_of(
 
         debug!("impl_id = {:?}", impl_id);
 
-        for &atv_id in &impl_datum.associated_ty_value_ids {
-            let atv = builder.db.associated_ty_value(atv_id);
-            debug!("atv_id = {:?} atv = {:#?}", atv_id, atv);
+        for atv in builder.db.impl_associated_ty_values(impl_id) {
+            debug!("atv = {:#?}", atv);
             atv.to_program_clauses(builder);
         }
     }
@@ -336,6 +539,15 @@ fn match_ty(builder: &mut ClauseBuilder<'_>, environment: &Environment<ChalkIr>,
     match ty {
         Ty::Apply(application_ty) => match application_ty.name {
             TypeName::TypeKindId(type_kind_id) => match_type_kind(builder, type_kind_id),
+            TypeName::Ref(_)
+            | TypeName::Raw(_)
+            | TypeName::Tuple(_)
+            | TypeName::FnPtr(_)
+            | TypeName::Slice
+            | TypeName::Array
+            | TypeName::Str
+            | TypeName::Scalar(_)
+            | TypeName::Never => push_builtin_type_clauses(builder, application_ty.name),
             TypeName::Placeholder(_) | TypeName::Error => {}
             TypeName::AssociatedType(type_id) => builder
                 .db
@@ -349,7 +561,13 @@ fn match_ty(builder: &mut ClauseBuilder<'_>, environment: &Environment<ChalkIr>,
         Ty::ForAll(quantified_ty) => match_ty(builder, environment, &quantified_ty.ty),
         Ty::BoundVar(_) => {}
         Ty::InferenceVar(_) => panic!("should have floundered"),
-        Ty::Dyn(_) | Ty::Opaque(_) => {}
+        // A trait object is well-formed if the traits it is made of are
+        // object safe: `WellFormed(dyn Foo + Bar) :- ObjectSafe(Foo), ObjectSafe(Bar)`.
+        Ty::Dyn(exists_qwcs) => builder.push_clause(
+            WellFormed::Ty(ty.clone()),
+            object_safety::trait_object_traits(exists_qwcs).map(DomainGoal::ObjectSafe),
+        ),
+        Ty::Opaque(_) => {}
     }
 }
 
@@ -366,6 +584,15 @@ fn match_type_kind(builder: &mut ClauseBuilder<'_>, type_kind_id: TypeKindId) {
             .db
             .struct_datum(struct_id)
             .to_program_clauses(builder),
+        TypeKindId::EnumId(enum_id) => builder.db.enum_datum(enum_id).to_program_clauses(builder),
+        TypeKindId::ClosureId(closure_id) => builder
+            .db
+            .closure_datum(closure_id)
+            .to_program_clauses(builder),
+        TypeKindId::OpaqueTyId(opaque_ty_id) => builder
+            .db
+            .opaque_ty_data(opaque_ty_id)
+            .to_program_clauses(builder),
     }
 }
 
@@ -35,6 +35,23 @@ impl<'me> ClauseBuilder<'me> {
         self.push_clause(consequence, None::<Goal<_>>);
     }
 
+    /// Pushes a "fact" `forall<..> { consequence }` that only holds if
+    /// the region `constraints` are satisfied. The solver does not try to
+    /// prove these; it reports them in the constraints of the answers
+    /// that use the fact.
+    pub fn push_fact_with_constraints(
+        &mut self,
+        consequence: impl CastTo<DomainGoal<ChalkIr>>,
+        constraints: impl IntoIterator<Item = Constraint<ChalkIr>>,
+    ) {
+        let clause = ProgramClauseImplication {
+            consequence: consequence.cast(),
+            conditions: vec![],
+            constraints: constraints.into_iter().collect(),
+        };
+        self.push_implication(clause);
+    }
+
     /// Pushes a clause `forall<..> { consequence :- conditions }`
     /// into the set of program clauses, meaning that `consequence`
     /// can be proven if `conditions` are all true.  The `forall<..>`
@@ -47,8 +64,12 @@ impl<'me> ClauseBuilder<'me> {
         let clause = ProgramClauseImplication {
             consequence: consequence.cast(),
             conditions: conditions.into_iter().casted().collect(),
+            constraints: vec![],
         };
+        self.push_implication(clause);
+    }
 
+    fn push_implication(&mut self, clause: ProgramClauseImplication<ChalkIr>) {
         if self.binders.len() == 0 {
             self.clauses.push(ProgramClause::Implies(clause));
         } else {
@@ -99,7 +120,6 @@ impl<'me> ClauseBuilder<'me> {
     /// unaffected and hence the context remains usable. Invokes `op`,
     /// passing a type representing this new type variable in as an
     /// argument.
-    #[allow(dead_code)]
     pub fn push_bound_ty(&mut self, op: impl FnOnce(&mut Self, Ty<ChalkIr>)) {
         let binders = Binders {
             binders: vec![ParameterKind::Ty(())],
@@ -115,4 +135,21 @@ impl<'me> ClauseBuilder<'me> {
             op(this, ty)
         });
     }
+
+    /// Push a single binder, for a lifetime, at the end of the binder
+    /// list. See `push_bound_ty`.
+    pub fn push_bound_lifetime(&mut self, op: impl FnOnce(&mut Self, Lifetime<ChalkIr>)) {
+        let binders = Binders {
+            binders: vec![ParameterKind::Lifetime(())],
+            value: PhantomData::<ChalkIr>,
+        };
+        self.push_binders(&binders, |this, PhantomData| {
+            let lifetime = *this
+                .placeholders_in_scope()
+                .last()
+                .unwrap()
+                .assert_lifetime_ref();
+            op(this, lifetime)
+        });
+    }
 }
@@ -0,0 +1,370 @@
+//! Impls that the compiler provides for built-in types, such as
+//! `impl<'a, T> Copy for &'a T`, so that programs need not write them.
+//! Structs and enums also get their `Sized` impls from here.
+
+use super::builder::ClauseBuilder;
+use super::type_fields;
+use crate::split::Split;
+use crate::RustIrDatabase;
+use chalk_ir::cast::{Cast, Caster};
+use chalk_ir::family::ChalkIr;
+use chalk_ir::*;
+use chalk_rust_ir::{ClosureKind, LangItem};
+
+/// The lang items that built-in types may get impls of.
+const WELL_KNOWN_TRAITS: &[LangItem] = &[
+    LangItem::Sized,
+    LangItem::Copy,
+    LangItem::Clone,
+    LangItem::FnOnce,
+    LangItem::FnMut,
+    LangItem::Fn,
+    LangItem::Send,
+    LangItem::Sync,
+];
+
+/// Returns the lang item that `trait_id` was declared as, if it is one
+/// that built-in types get impls of.
+fn well_known_trait(db: &dyn RustIrDatabase, trait_id: TraitId) -> Option<LangItem> {
+    WELL_KNOWN_TRAITS
+        .iter()
+        .cloned()
+        .find(|&lang_item| db.well_known_trait_id(lang_item) == Some(trait_id))
+}
+
+/// Returns true if the built-in type `name` opts out of the auto trait
+/// `auto_trait_id`, as if by `impl<T> !Send for *const T`. Raw pointers
+/// are neither `Send` nor `Sync`, whatever they point to; only an
+/// explicit impl (usually on a wrapper type) can make them so.
+pub(super) fn builtin_auto_trait_opt_out(
+    db: &dyn RustIrDatabase,
+    auto_trait_id: TraitId,
+    name: TypeName,
+) -> bool {
+    match name {
+        TypeName::Raw(_) => match well_known_trait(db, auto_trait_id) {
+            Some(LangItem::Send) | Some(LangItem::Sync) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Which fields of a built-in type must implement a trait for the
+/// type itself to implement it.
+enum Requires {
+    Nothing,
+    AllFields,
+    LastField,
+}
+
+/// Pushes the built-in impls of the trait `trait_id` for `self_ty`, if
+/// it is a built-in type (or a struct or enum, for `Sized`). For
+/// example, for tuples of arity 2 and `Copy`, we generate:
+///
+/// ```notrust
+/// forall<A, B> {
+///     Implemented((A, B): Copy) :- Implemented(A: Copy), Implemented(B: Copy).
+/// }
+/// ```
+pub(super) fn push_builtin_trait_impls(
+    builder: &mut ClauseBuilder<'_>,
+    trait_id: TraitId,
+    self_ty: &Ty<ChalkIr>,
+) {
+    let well_known = match well_known_trait(builder.db, trait_id) {
+        Some(well_known) => well_known,
+        None => return,
+    };
+
+    if let Some(signature) = fn_ptr_signature(self_ty) {
+        push_fn_ptr_impls(builder, well_known, trait_id, self_ty, &signature);
+        return;
+    }
+
+    let name = match self_ty {
+        Ty::Apply(apply) => apply.name,
+        _ => return,
+    };
+
+    let requires = match (well_known, name) {
+        (LangItem::Sized, TypeName::Ref(_)) => Requires::Nothing,
+        (LangItem::Copy, TypeName::Ref(Mutability::Not))
+        | (LangItem::Clone, TypeName::Ref(Mutability::Not)) => Requires::Nothing,
+        (LangItem::Sized, TypeName::Raw(_))
+        | (LangItem::Copy, TypeName::Raw(_))
+        | (LangItem::Clone, TypeName::Raw(_)) => Requires::Nothing,
+        (LangItem::Sized, TypeName::Tuple(_)) => Requires::LastField,
+        (LangItem::Copy, TypeName::Tuple(_)) | (LangItem::Clone, TypeName::Tuple(_)) => {
+            Requires::AllFields
+        }
+        // `[T; N]` is always sized: `T` must be for the array to be
+        // well-formed, as `push_builtin_type_clauses` requires. It is `Copy`
+        // if its elements are. Slices and `str` are unsized and get no
+        // impls at all.
+        (LangItem::Sized, TypeName::Array) => Requires::Nothing,
+        (LangItem::Copy, TypeName::Array) | (LangItem::Clone, TypeName::Array) => {
+            Requires::AllFields
+        }
+        (LangItem::Sized, TypeName::Scalar(_))
+        | (LangItem::Copy, TypeName::Scalar(_))
+        | (LangItem::Clone, TypeName::Scalar(_)) => Requires::Nothing,
+        // A struct is `Sized` if its last field is; its other fields must
+        // be for the struct to be well-formed, as must all of an enum's.
+        (LangItem::Sized, TypeName::TypeKindId(TypeKindId::StructId(_))) => Requires::LastField,
+        (LangItem::Sized, TypeName::TypeKindId(TypeKindId::EnumId(_))) => Requires::Nothing,
+        (LangItem::Sized, TypeName::Never)
+        | (LangItem::Copy, TypeName::Never)
+        | (LangItem::Clone, TypeName::Never) => Requires::Nothing,
+        _ => return,
+    };
+
+    let binders = type_fields(builder.db, name).unwrap();
+    builder.push_binders(&binders, |builder, fields| {
+        let self_ty: Ty<_> = ApplicationTy {
+            name,
+            parameters: builder.placeholders_in_scope().to_vec(),
+        }
+        .cast();
+
+        let required_fields = match requires {
+            Requires::Nothing => &[],
+            Requires::AllFields => &fields[..],
+            Requires::LastField => &fields[fields.len().saturating_sub(1)..],
+        };
+
+        builder.push_clause(
+            TraitRef {
+                trait_id,
+                parameters: vec![self_ty.cast()],
+            },
+            required_fields.iter().map(|ty| TraitRef {
+                trait_id,
+                parameters: vec![ty.clone().cast()],
+            }),
+        );
+    });
+}
+
+/// Pushes the impls of the well-known trait `trait_id` for the function
+/// pointer type `self_ty`, whose signature is `signature`. Like the
+/// clauses for `dyn` types, these are specific to `self_ty`; any
+/// lifetimes bound by a `for<'a>` on the function pointer are quantified
+/// over by the clause instead. So for `for<'a> fn(&'a u8)`, we get:
+///
+/// ```notrust
+/// forall<'a> { Implemented(for<'a> fn(&'a u8): FnOnce<(&'a u8,)>) }
+/// ```
+fn push_fn_ptr_impls(
+    builder: &mut ClauseBuilder<'_>,
+    well_known: LangItem,
+    trait_id: TraitId,
+    self_ty: &Ty<ChalkIr>,
+    signature: &Binders<Ty<ChalkIr>>,
+) {
+    let takes_args = match well_known {
+        LangItem::Sized | LangItem::Copy | LangItem::Clone => false,
+        LangItem::FnOnce | LangItem::FnMut | LangItem::Fn => true,
+        // These are auto traits, implemented through `push_auto_trait_impls`.
+        LangItem::Send | LangItem::Sync => return,
+        LangItem::Drop => return,
+    };
+
+    // We can only provide the `Fn` impls if the trait is declared with
+    // an `Args` parameter, as in `trait FnOnce<Args> { .. }`.
+    if takes_args && builder.db.trait_datum(trait_id).binders.len() != 2 {
+        return;
+    }
+
+    builder.push_binders(signature, |builder, signature| {
+        let mut parameters = vec![self_ty.clone().cast()];
+        if takes_args {
+            let (args, _) = split_fn_ptr(&signature);
+            parameters.push(args_tuple(args).cast());
+        }
+        builder.push_fact(TraitRef {
+            trait_id,
+            parameters,
+        });
+    });
+}
+
+/// Pushes the impls of the well-known trait `trait_id` for the closure
+/// type `closure_id`. Every closure is `FnOnce`; it is also `FnMut` or
+/// `Fn` if its kind says so. Closures are always `Sized`, and are `Copy`
+/// or `Clone` if their upvars are. So for
+/// `closure foo<T>(&self, x: u32) -> T { T }`, we get:
+///
+/// ```notrust
+/// forall<T> { Implemented(foo<T>: Fn<(u32,)>) }
+/// forall<T> { Implemented(foo<T>: Clone) :- Implemented(T: Clone) }
+/// ```
+pub(super) fn push_closure_impls(
+    builder: &mut ClauseBuilder<'_>,
+    trait_id: TraitId,
+    closure_id: ClosureId,
+) {
+    let well_known = match well_known_trait(builder.db, trait_id) {
+        Some(well_known) => well_known,
+        None => return,
+    };
+
+    let closure_datum = builder.db.closure_datum(closure_id);
+    let (takes_args, requires) = match well_known {
+        LangItem::Sized => (false, Requires::Nothing),
+        LangItem::Copy | LangItem::Clone => (false, Requires::AllFields),
+        LangItem::FnOnce => (true, Requires::Nothing),
+        LangItem::FnMut if closure_datum.kind != ClosureKind::FnOnce => (true, Requires::Nothing),
+        LangItem::Fn if closure_datum.kind == ClosureKind::Fn => (true, Requires::Nothing),
+        LangItem::FnMut | LangItem::Fn => return,
+        // These are auto traits, implemented through `push_auto_trait_impls`.
+        LangItem::Send | LangItem::Sync => return,
+        LangItem::Drop => return,
+    };
+
+    if takes_args && builder.db.trait_datum(trait_id).binders.len() != 2 {
+        return;
+    }
+
+    builder.push_binders(&closure_datum.binders, |builder, bound| {
+        let self_ty: Ty<_> = ApplicationTy {
+            name: closure_datum.name(),
+            parameters: builder.placeholders_in_scope().to_vec(),
+        }
+        .cast();
+
+        let mut parameters = vec![self_ty.cast()];
+        if takes_args {
+            parameters.push(closure_args(&bound.inputs).cast());
+        }
+
+        let required_upvars = match requires {
+            Requires::AllFields => &bound.upvars[..],
+            _ => &[],
+        };
+
+        builder.push_clause(
+            TraitRef {
+                trait_id,
+                parameters,
+            },
+            required_upvars.iter().map(|ty| TraitRef {
+                trait_id,
+                parameters: vec![ty.clone().cast()],
+            }),
+        );
+    });
+}
+
+/// Pushes the built-in `Normalize` clauses for `projection`: the
+/// `Output` of the `FnOnce` impl of a function pointer or closure is
+/// its return type. `Output` is whatever single associated type the
+/// `FnOnce` lang item declares.
+///
+/// ```notrust
+/// forall<'a> { Normalize(<for<'a> fn(&'a u8) -> &'a u8 as FnOnce<(&'a u8,)>>::Output -> &'a u8) }
+/// ```
+pub(super) fn push_builtin_normalize_clauses(
+    builder: &mut ClauseBuilder<'_>,
+    projection: &ProjectionTy<ChalkIr>,
+) {
+    let (associated_ty, trait_params, other_params) = builder.db.split_projection(projection);
+    if well_known_trait(builder.db, associated_ty.trait_id) != Some(LangItem::FnOnce)
+        || builder
+            .db
+            .trait_datum(associated_ty.trait_id)
+            .associated_ty_ids
+            != [associated_ty.id]
+        || trait_params.len() != 2
+        || !other_params.is_empty()
+    {
+        return;
+    }
+
+    let self_ty = trait_params[0].assert_ty_ref();
+    if let Ty::Apply(ApplicationTy {
+        name: TypeName::TypeKindId(TypeKindId::ClosureId(closure_id)),
+        ..
+    }) = self_ty
+    {
+        let closure_datum = builder.db.closure_datum(*closure_id);
+        builder.push_binders(&closure_datum.binders, |builder, bound| {
+            let self_ty: Ty<_> = ApplicationTy {
+                name: closure_datum.name(),
+                parameters: builder.placeholders_in_scope().to_vec(),
+            }
+            .cast();
+            builder.push_fact(Normalize {
+                projection: ProjectionTy {
+                    associated_ty_id: associated_ty.id,
+                    parameters: vec![self_ty.cast(), closure_args(&bound.inputs).cast()],
+                },
+                ty: bound.output,
+            });
+        });
+        return;
+    }
+
+    let signature = match fn_ptr_signature(self_ty) {
+        Some(signature) => signature,
+        None => return,
+    };
+
+    builder.push_binders(&signature, |builder, signature| {
+        let (args, ret) = split_fn_ptr(&signature);
+        builder.push_fact(Normalize {
+            projection: ProjectionTy {
+                associated_ty_id: associated_ty.id,
+                parameters: vec![self_ty.clone().cast(), args_tuple(args).cast()],
+            },
+            ty: ret.clone(),
+        });
+    });
+}
+
+/// If `ty` is a function pointer type, returns its signature (the
+/// underlying `fn(..) -> R` type) bound by the lifetimes that the
+/// pointer quantifies over, if any.
+pub(super) fn fn_ptr_signature(ty: &Ty<ChalkIr>) -> Option<Binders<Ty<ChalkIr>>> {
+    let (num_binders, signature) = match ty {
+        Ty::ForAll(quantified_ty) => (quantified_ty.num_binders, &quantified_ty.ty),
+        _ => (0, ty),
+    };
+
+    match signature {
+        Ty::Apply(ApplicationTy {
+            name: TypeName::FnPtr(_),
+            ..
+        }) => Some(Binders {
+            binders: vec![ParameterKind::Lifetime(()); num_binders],
+            value: signature.clone(),
+        }),
+        _ => None,
+    }
+}
+
+/// Splits a `fn(..) -> R` type into its argument types and return type.
+fn split_fn_ptr(ty: &Ty<ChalkIr>) -> (&[Parameter<ChalkIr>], &Ty<ChalkIr>) {
+    match ty {
+        Ty::Apply(ApplicationTy {
+            name: TypeName::FnPtr(arity),
+            parameters,
+        }) => (&parameters[..*arity], parameters[*arity].assert_ty_ref()),
+        _ => panic!("expected a function pointer type, found `{:?}`", ty),
+    }
+}
+
+/// The argument tuple of a closure whose signature takes `inputs`.
+fn closure_args(inputs: &[Ty<ChalkIr>]) -> Ty<ChalkIr> {
+    let args: Vec<_> = inputs.iter().cloned().casted().collect();
+    args_tuple(&args)
+}
+
+/// The tuple of argument types that the `Fn` traits are generic over.
+fn args_tuple(args: &[Parameter<ChalkIr>]) -> Ty<ChalkIr> {
+    ApplicationTy {
+        name: TypeName::Tuple(args.len()),
+        parameters: args.to_vec(),
+    }
+    .cast()
+}
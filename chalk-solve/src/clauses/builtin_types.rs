@@ -0,0 +1,104 @@
+//! Program clauses for the built-in types (like `&'a T` or `(A, B)`),
+//! which, unlike structs and enums, have no datum in the `RustIrDatabase`.
+
+use super::builder::ClauseBuilder;
+use super::program_clauses::push_adt_clauses;
+use chalk_ir::cast::Cast;
+use chalk_ir::family::ChalkIr;
+use chalk_ir::*;
+use chalk_rust_ir::LangItem;
+
+/// Returns the parameters of the built-in type `name`, bound around the
+/// types that a value of that type is built from -- its "fields", as far
+/// as auto traits are concerned. For `&'a T`, this is `for<'a, T> { [T] }`;
+/// for `(A, B)`, it is `for<A, B> { [A, B] }`; for `[T; N]`, it is
+/// `for<T, const N> { [T] }`; and for `fn(A) -> R`, it is
+/// `for<A, R> { [] }`. Returns `None` if `name` is not a built-in type.
+pub(super) fn builtin_type_fields(name: TypeName) -> Option<Binders<Vec<Ty<ChalkIr>>>> {
+    match name {
+        TypeName::Ref(_) => Some(Binders {
+            binders: vec![ParameterKind::Lifetime(()), ParameterKind::Ty(())],
+            value: vec![Ty::BoundVar(1)],
+        }),
+        TypeName::Raw(_) | TypeName::Slice => Some(Binders {
+            binders: vec![ParameterKind::Ty(())],
+            value: vec![Ty::BoundVar(0)],
+        }),
+        TypeName::Tuple(arity) => Some(Binders {
+            binders: vec![ParameterKind::Ty(()); arity],
+            value: (0..arity).map(Ty::BoundVar).collect(),
+        }),
+        // A function pointer contains no data of its argument or
+        // return types, so it has no fields.
+        TypeName::FnPtr(arity) => Some(Binders {
+            binders: vec![ParameterKind::Ty(()); arity + 1],
+            value: vec![],
+        }),
+        TypeName::Array => Some(Binders {
+            binders: vec![ParameterKind::Ty(()), ParameterKind::Const(())],
+            value: vec![Ty::BoundVar(0)],
+        }),
+        TypeName::Str | TypeName::Scalar(_) | TypeName::Never => Some(Binders {
+            binders: vec![],
+            value: vec![],
+        }),
+        TypeName::TypeKindId(_)
+        | TypeName::Placeholder(_)
+        | TypeName::AssociatedType(_)
+        | TypeName::Error => None,
+    }
+}
+
+/// Pushes the well-formedness, implied bound and orphan-rule clauses for
+/// the built-in type `name`. These are the rules of an `#[upstream]`
+/// struct; references are also `#[fundamental]`, so that e.g.
+/// `&'a MyType` is local whenever `MyType` is. Raw pointers are not:
+/// `*const MyType` is never local.
+pub(super) fn push_builtin_type_clauses(builder: &mut ClauseBuilder<'_>, name: TypeName) {
+    let fields = match builtin_type_fields(name) {
+        Some(fields) => fields,
+        None => return,
+    };
+
+    let fundamental = match name {
+        TypeName::Ref(_) => true,
+        _ => false,
+    };
+
+    // `&'a T` is only well-formed if `T: 'a`; this also gives us the
+    // implied bound `T: 'a` from `FromEnv(&'a T)`. Likewise, the elements
+    // of arrays and slices, and all fields of a tuple but the last, must
+    // be `Sized` (if the program has a `Sized` trait).
+    let sized_trait_id = builder.db.well_known_trait_id(LangItem::Sized);
+    let sized = |ty: Ty<ChalkIr>| {
+        sized_trait_id.map(|trait_id| Binders {
+            binders: vec![],
+            value: WhereClause::Implemented(TraitRef {
+                trait_id,
+                parameters: vec![ty.cast()],
+            }),
+        })
+    };
+    let where_clauses = fields.map_ref(|_| match name {
+        TypeName::Ref(_) => vec![Binders {
+            binders: vec![],
+            value: WhereClause::TypeOutlives(TypeOutlives {
+                ty: Ty::BoundVar(1),
+                lifetime: Lifetime::BoundVar(0),
+            }),
+        }],
+        TypeName::Array | TypeName::Slice => sized(Ty::BoundVar(0)).into_iter().collect(),
+        TypeName::Tuple(arity) => (0..arity.saturating_sub(1))
+            .filter_map(|i| sized(Ty::BoundVar(i)))
+            .collect(),
+        _ => Vec::<QuantifiedWhereClause<ChalkIr>>::new(),
+    });
+    push_adt_clauses(
+        builder,
+        name,
+        &where_clauses.map_ref(|wcs| wcs),
+        true,
+        None,
+        fundamental,
+    );
+}
@@ -1,5 +1,6 @@
 use super::program_clauses::ToProgramClauses;
 use crate::clauses::builder::ClauseBuilder;
+use crate::clauses::builtin_types::push_builtin_type_clauses;
 use crate::clauses::match_type_kind;
 use crate::DomainGoal;
 use crate::FromEnv;
@@ -55,6 +56,17 @@ impl<'me> EnvElaborator<'me> {
                 TypeName::TypeKindId(type_kind_id) => {
                     match_type_kind(&mut self.builder, type_kind_id)
                 }
+                TypeName::Ref(_)
+                | TypeName::Raw(_)
+                | TypeName::Tuple(_)
+                | TypeName::FnPtr(_)
+                | TypeName::Slice
+                | TypeName::Array
+                | TypeName::Str
+                | TypeName::Scalar(_)
+                | TypeName::Never => {
+                    push_builtin_type_clauses(&mut self.builder, application_ty.name)
+                }
                 TypeName::Placeholder(_) | TypeName::Error => (),
                 TypeName::AssociatedType(type_id) => {
                     self.db
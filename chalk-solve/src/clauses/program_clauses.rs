@@ -1,6 +1,7 @@
 use crate::clauses::builder::ClauseBuilder;
 use crate::split::Split;
 use chalk_ir::cast::{Cast, Caster};
+use chalk_ir::family::ChalkIr;
 use chalk_ir::*;
 use chalk_rust_ir::*;
 use std::iter;
@@ -62,10 +63,25 @@ impl ToProgramClauses for AssociatedTyValue {
     ///         Implemented(Iter<'a, T>: 'a).   // (2)
     /// }
     /// ```
+    ///
+    /// If the value is declared `default type` and some other impl
+    /// specializes this one, we generate nothing: the value may be
+    /// overridden, so the projection is left opaque (RFC 1210), and
+    /// only the values of the more specialized impls are used.
     fn to_program_clauses(&self, builder: &mut ClauseBuilder<'_>) {
         let impl_datum = builder.db.impl_datum(self.impl_id);
         let associated_ty = builder.db.associated_ty_data(self.associated_ty_id);
 
+        if self.defaultness.is_default() {
+            let is_final = match builder.db.specialization_priorities(impl_datum.trait_id()) {
+                Some(priorities) => priorities.is_final(self.impl_id),
+                None => true,
+            };
+            if !is_final {
+                return;
+            }
+        }
+
         builder.push_binders(&self.value, |builder, assoc_ty_value| {
             let all_parameters = builder.placeholders_in_scope().to_vec();
 
@@ -116,6 +132,87 @@ impl ToProgramClauses for AssociatedTyValue {
     }
 }
 
+impl ToProgramClauses for OpaqueTyDatum {
+    /// Given the following named opaque type:
+    ///
+    /// ```notrust
+    /// opaque type Foo<T>: Iterator<Item = T> = Bar<T>;
+    /// ```
+    ///
+    /// we generate:
+    ///
+    /// ```notrust
+    /// -- Rule OpaqueEq-Reveal
+    /// forall<T> {
+    ///     OpaqueEq(Foo<T> = Bar<T>) :- Reveal.
+    /// }
+    ///
+    /// -- Rule Implemented-From-Opaque-Bounds
+    /// forall<T> {
+    ///     Implemented(Foo<T>: Iterator).
+    /// }
+    ///
+    /// -- Rule Normalize-From-Opaque-Bounds
+    /// forall<T> {
+    ///     Normalize(<Foo<T> as Iterator>::Item -> T).
+    /// }
+    ///
+    /// -- Rule WellFormed-Opaque
+    /// forall<T> {
+    ///     WellFormed(Foo<T>).
+    /// }
+    /// ```
+    ///
+    /// Outside of `reveal { .. }` goals, `Foo<T>` is thus only equal to
+    /// itself (see `OpaqueEq`), and is only known to meet its bounds.
+    /// Like a closure type, it is local to the crate that defines it.
+    fn to_program_clauses(&self, builder: &mut ClauseBuilder<'_>) {
+        builder.push_binders(&self.binders, |builder, bound| {
+            let parameters = builder.placeholders_in_scope().to_vec();
+            let opaque_ty: Ty<_> = ApplicationTy {
+                name: self.name(),
+                parameters: parameters.clone(),
+            }
+            .cast();
+
+            builder.push_clause(
+                OpaqueEq {
+                    opaque_ty_id: self.id,
+                    parameters,
+                    ty: bound.hidden_ty,
+                },
+                Some(DomainGoal::Reveal(())),
+            );
+
+            for qwc in bound
+                .bounds
+                .iter()
+                .flat_map(|b| b.into_where_clauses(opaque_ty.clone()))
+            {
+                builder.push_binders(&qwc, |builder, wc| match wc {
+                    WhereClause::ProjectionEq(ProjectionEq { projection, ty }) => {
+                        builder.push_fact(Normalize { projection, ty })
+                    }
+                    wc => builder.push_fact(wc),
+                });
+            }
+        });
+
+        // The well-formedness rule above, along with the locality rules.
+        let where_clauses = self
+            .binders
+            .map_ref(|_| Vec::<QuantifiedWhereClause<ChalkIr>>::new());
+        push_adt_clauses(
+            builder,
+            self.name(),
+            &where_clauses.map_ref(|wcs| wcs),
+            false,
+            self.krate,
+            false,
+        );
+    }
+}
+
 impl ToProgramClauses for StructDatum {
     /// Given the following type definition: `struct Foo<T: Eq> { }`, generate:
     ///
@@ -165,110 +262,214 @@ impl ToProgramClauses for StructDatum {
     /// forall<T> { DownstreamType(Box<T>) :- DownstreamType(T). }
     /// ```
     ///
+    /// If the type is defined in the crate `a` of a program made up of
+    /// several crates, it is local or upstream depending on the crate we
+    /// are reasoning from:
+    ///
+    /// ```notrust
+    /// forall<T> { IsLocal(Foo<T>) :- InCrate(a). }
+    ///
+    /// forall<T> { IsUpstream(Foo<T>) :- UpstreamCrate(a). }
+    ///
+    /// // For each crate `c` that `a` depends on
+    /// forall<T> { DownstreamType(Foo<T>) :- InCrate(c). }
+    /// ```
+    ///
+    /// and likewise for the rules of fundamental types, where the
+    /// `IsLocal` and `IsUpstream` rules above gain an `UpstreamCrate(a)`
+    /// condition.
     fn to_program_clauses(&self, builder: &mut ClauseBuilder<'_>) {
         debug_heading!("StructDatum::to_program_clauses(self={:?})", self);
 
         let binders = self.binders.map_ref(|b| &b.where_clauses);
-        builder.push_binders(&binders, |builder, where_clauses| {
-            let self_ty = &ApplicationTy {
-                name: self.id.cast(),
-                parameters: builder.placeholders_in_scope().to_vec(),
-            };
+        push_adt_clauses(
+            builder,
+            self.id.cast(),
+            &binders,
+            self.flags.upstream,
+            self.flags.krate,
+            self.flags.fundamental,
+        );
+    }
+}
 
-            // forall<T> {
-            //     WF(Foo<T>) :- WF(T: Eq).
-            // }
-            builder.push_clause(
-                WellFormed::Ty(self_ty.clone().cast()),
-                where_clauses
-                    .iter()
-                    .cloned()
-                    .map(|qwc| qwc.into_well_formed_goal()),
-            );
+impl ToProgramClauses for EnumDatum {
+    /// Enums get the same rules as structs (see the `StructDatum`
+    /// impl above), keyed on the enum's own where clauses. Enums are
+    /// never fundamental.
+    fn to_program_clauses(&self, builder: &mut ClauseBuilder<'_>) {
+        debug_heading!("EnumDatum::to_program_clauses(self={:?})", self);
 
-            // forall<T> {
-            //     IsFullyVisible(Foo<T>) :- IsFullyVisible(T).
-            // }
-            builder.push_clause(
-                DomainGoal::IsFullyVisible(self_ty.clone().cast()),
-                self_ty
-                    .type_parameters()
-                    .map(|ty| DomainGoal::IsFullyVisible(ty).cast::<Goal<_>>()),
-            );
+        let binders = self.binders.map_ref(|b| &b.where_clauses);
+        push_adt_clauses(
+            builder,
+            self.id.cast(),
+            &binders,
+            self.flags.upstream,
+            self.flags.krate,
+            false,
+        );
+    }
+}
 
-            // Fundamental types often have rules in the form of:
-            //     Goal(FundamentalType<T>) :- Goal(T)
-            // This macro makes creating that kind of clause easy
-            macro_rules! fundamental_rule {
-                ($goal:ident) => {
-                    // Fundamental types must always have at least one
-                    // type parameter for this rule to make any
-                    // sense. We currently do not have have any
-                    // fundamental types with more than one type
-                    // parameter, nor do we know what the behaviour
-                    // for that should be. Thus, we are asserting here
-                    // that there is only a single type parameter
-                    // until the day when someone makes a decision
-                    // about how that should behave.
-                    assert_eq!(
-                        self_ty.len_type_parameters(),
-                        1,
-                        "Only fundamental types with a single parameter are supported"
-                    );
+impl ToProgramClauses for ClosureDatum {
+    /// A closure type is always local to the crate that defines it, and
+    /// is well-formed without conditions of its own: whatever its
+    /// signature and upvars require was already checked for the
+    /// enclosing item.
+    fn to_program_clauses(&self, builder: &mut ClauseBuilder<'_>) {
+        debug_heading!("ClosureDatum::to_program_clauses(self={:?})", self);
+
+        let where_clauses = self
+            .binders
+            .map_ref(|_| Vec::<QuantifiedWhereClause<ChalkIr>>::new());
+        push_adt_clauses(
+            builder,
+            self.id.cast(),
+            &where_clauses.map_ref(|wcs| wcs),
+            false,
+            self.krate,
+            false,
+        );
+    }
+}
 
-                    builder.push_clause(
-                        DomainGoal::$goal(self_ty.clone().cast()),
-                        Some(DomainGoal::$goal(
+/// Generates the well-formedness, implied bound and orphan-rule
+/// clauses for an ADT named `name`; see `StructDatum::to_program_clauses`.
+pub(super) fn push_adt_clauses(
+    builder: &mut ClauseBuilder<'_>,
+    name: TypeName,
+    where_clauses: &Binders<&Vec<QuantifiedWhereClause<ChalkIr>>>,
+    upstream: bool,
+    krate: Option<Identifier>,
+    fundamental: bool,
+) {
+    builder.push_binders(where_clauses, |builder, where_clauses| {
+        let self_ty = &ApplicationTy {
+            name,
+            parameters: builder.placeholders_in_scope().to_vec(),
+        };
+
+        // forall<T> {
+        //     WF(Foo<T>) :- WF(T: Eq).
+        // }
+        builder.push_clause(
+            WellFormed::Ty(self_ty.clone().cast()),
+            where_clauses
+                .iter()
+                .cloned()
+                .map(|qwc| qwc.into_well_formed_goal()),
+        );
+
+        // forall<T> {
+        //     IsFullyVisible(Foo<T>) :- IsFullyVisible(T).
+        // }
+        builder.push_clause(
+            DomainGoal::IsFullyVisible(self_ty.clone().cast()),
+            self_ty
+                .type_parameters()
+                .map(|ty| DomainGoal::IsFullyVisible(ty).cast::<Goal<_>>()),
+        );
+
+        // Fundamental types often have rules in the form of:
+        //     Goal(FundamentalType<T>) :- Goal(T)
+        // This macro makes creating that kind of clause easy
+        macro_rules! fundamental_rule {
+            ($goal:ident $(, $condition:expr)*) => {
+                // Fundamental types must always have at least one
+                // type parameter for this rule to make any
+                // sense. We currently do not have have any
+                // fundamental types with more than one type
+                // parameter, nor do we know what the behaviour
+                // for that should be. Thus, we are asserting here
+                // that there is only a single type parameter
+                // until the day when someone makes a decision
+                // about how that should behave.
+                assert_eq!(
+                    self_ty.len_type_parameters(),
+                    1,
+                    "Only fundamental types with a single parameter are supported"
+                );
+
+                builder.push_clause(
+                    DomainGoal::$goal(self_ty.clone().cast()),
+                    vec![
+                        $($condition,)*
+                        DomainGoal::$goal(
                             // This unwrap is safe because we asserted
                             // above for the presence of a type
                             // parameter
                             self_ty.first_type_parameter().unwrap(),
-                        )),
-                    );
-                };
-            }
+                        ),
+                    ],
+                );
+            };
+        }
 
-            // Types that are not marked `#[upstream]` satisfy IsLocal(TypeName)
-            if !self.flags.upstream {
-                // `IsLocalTy(Ty)` depends *only* on whether the type
-                // is marked #[upstream] and nothing else
-                builder.push_fact(DomainGoal::IsLocal(self_ty.clone().cast()));
-            } else if self.flags.fundamental {
-                // If a type is `#[upstream]`, but is also
-                // `#[fundamental]`, it satisfies IsLocal if and only
-                // if its parameters satisfy IsLocal
-                fundamental_rule!(IsLocal);
-                fundamental_rule!(IsUpstream);
+        if let Some(krate) = krate {
+            // Types defined in a crate are local to that crate, and
+            // upstream of the crates that depend on it
+            builder.push_clause(
+                DomainGoal::IsLocal(self_ty.clone().cast()),
+                Some(DomainGoal::InCrate(krate)),
+            );
+            if fundamental {
+                fundamental_rule!(IsLocal, DomainGoal::UpstreamCrate(krate));
+                fundamental_rule!(IsUpstream, DomainGoal::UpstreamCrate(krate));
             } else {
-                // The type is just upstream and not fundamental
-                builder.push_fact(DomainGoal::IsUpstream(self_ty.clone().cast()));
+                builder.push_clause(
+                    DomainGoal::IsUpstream(self_ty.clone().cast()),
+                    Some(DomainGoal::UpstreamCrate(krate)),
+                );
             }
 
-            if self.flags.fundamental {
-                fundamental_rule!(DownstreamType);
+            // ...and downstream of the crates it depends on
+            for &dependency in &builder.db.crate_datum(krate).dependencies {
+                builder.push_clause(
+                    DomainGoal::DownstreamType(self_ty.clone().cast()),
+                    Some(DomainGoal::InCrate(dependency)),
+                );
             }
+        } else if !upstream {
+            // Types that are not marked `#[upstream]` satisfy IsLocal(TypeName)
+            // `IsLocalTy(Ty)` depends *only* on whether the type
+            // is marked #[upstream] and nothing else
+            builder.push_fact(DomainGoal::IsLocal(self_ty.clone().cast()));
+        } else if fundamental {
+            // If a type is `#[upstream]`, but is also
+            // `#[fundamental]`, it satisfies IsLocal if and only
+            // if its parameters satisfy IsLocal
+            fundamental_rule!(IsLocal);
+            fundamental_rule!(IsUpstream);
+        } else {
+            // The type is just upstream and not fundamental
+            builder.push_fact(DomainGoal::IsUpstream(self_ty.clone().cast()));
+        }
 
-            for qwc in where_clauses {
-                // Generate implied bounds rules. We have to push the binders from the where-clauses
-                // too -- e.g., if we had `struct Foo<T: for<'a> Bar<&'a i32>>`, we would
-                // create a reverse rule like:
-                //
-                // ```notrust
-                // forall<T, 'a> { FromEnv(T: Bar<&'a i32>) :- FromEnv(Foo<T>) }
-                // ```
-                //
-                // In other words, you can assume `T: Bar<&'a i32>`
-                // for any `'a` *if* you are assuming that `Foo<T>` is
-                // well formed.
-                builder.push_binders(&qwc, |builder, wc| {
-                    builder.push_clause(
-                        wc.into_from_env_goal(),
-                        Some(FromEnv::Ty(self_ty.clone().cast())),
-                    );
-                });
-            }
-        });
-    }
+        if fundamental {
+            fundamental_rule!(DownstreamType);
+        }
+
+        for qwc in where_clauses {
+            // Generate implied bounds rules. We have to push the binders from the where-clauses
+            // too -- e.g., if we had `struct Foo<T: for<'a> Bar<&'a i32>>`, we would
+            // create a reverse rule like:
+            //
+            // ```notrust
+            // forall<T, 'a> { FromEnv(T: Bar<&'a i32>) :- FromEnv(Foo<T>) }
+            // ```
+            //
+            // In other words, you can assume `T: Bar<&'a i32>`
+            // for any `'a` *if* you are assuming that `Foo<T>` is
+            // well formed.
+            builder.push_binders(&qwc, |builder, wc| {
+                builder.push_clause(
+                    wc.into_from_env_goal(),
+                    Some(FromEnv::Ty(self_ty.clone().cast())),
+                );
+            });
+        }
+    });
 }
 
 impl ToProgramClauses for TraitDatum {
@@ -339,6 +540,19 @@ impl ToProgramClauses for TraitDatum {
     /// }
     /// ```
     ///
+    /// A trait defined in the crate `a` of a program made up of several crates is treated as
+    /// local when reasoning from `a`, and as upstream from the crates that depend on `a`:
+    ///
+    /// ```notrust
+    /// forall<Self, T, U, V> { LocalImplAllowed(Self: Foo<T, U, V>) :- InCrate(a). }
+    ///
+    /// forall<Self, T, U, V> {
+    ///     LocalImplAllowed(Self: Foo<T, U, V>) :- UpstreamCrate(a), IsLocal(Self).
+    /// }
+    ///
+    /// // ... and so on, as for `#[upstream]` traits
+    /// ```
+    ///
     /// The overlap check uses compatible { ... } mode to ensure that it accounts for impls that
     /// may exist in some other *compatible* world. For every upstream trait, we add a rule to
     /// account for the fact that upstream crates are able to compatibly add impls of upstream
@@ -385,6 +599,15 @@ impl ToProgramClauses for TraitDatum {
     /// To implement fundamental traits, we simply just do not add the rule above that allows
     /// upstream types to implement upstream traits. Fundamental traits are not allowed to
     /// compatibly do that.
+    ///
+    /// A trait alias like `trait Alias<T> = Foo<T> where T: Bar;` instead gets a single rule
+    /// making it equivalent to the bounds it stands for:
+    ///
+    /// ```notrust
+    /// forall<Self, T> {
+    ///     Implemented(Self: Alias<T>) :- Implemented(Self: Foo<T>), Implemented(T: Bar).
+    /// }
+    /// ```
     fn to_program_clauses(&self, builder: &mut ClauseBuilder<'_>) {
         let binders = self.binders.map_ref(|b| &b.where_clauses);
         builder.push_binders(&binders, |builder, where_clauses| {
@@ -405,67 +628,90 @@ impl ToProgramClauses for TraitDatum {
                     .chain(Some(trait_ref.clone().cast())),
             );
 
-            // The number of parameters will always be at least 1
-            // because of the Self parameter that is automatically
-            // added to every trait. This is important because
-            // otherwise the added program clauses would not have any
-            // conditions.
-            let type_parameters: Vec<_> = trait_ref.type_parameters().collect();
-
-            // Add all cases for potential downstream impls that could exist
-            for i in 0..type_parameters.len() {
+            if self.is_alias() {
+                // A trait alias is implemented exactly when the bounds
+                // it stands for hold; with the reverse implied bound
+                // rules below, `Implemented(T: Alias)` is equivalent to
+                // its where clauses. No impls of an alias can exist, so
+                // the coherence and orphan rules do not apply.
                 builder.push_clause(
                     trait_ref.clone(),
-                    where_clauses
-                        .iter()
-                        .cloned()
-                        .casted()
-                        .chain(iter::once(DomainGoal::Compatible(()).cast()))
-                        .chain(
-                            (0..i).map(|j| {
-                                DomainGoal::IsFullyVisible(type_parameters[j].clone()).cast()
-                            }),
-                        )
-                        .chain(iter::once(
-                            DomainGoal::DownstreamType(type_parameters[i].clone()).cast(),
-                        ))
-                        .chain(iter::once(Goal::CannotProve(()))),
+                    where_clauses.iter().cloned().casted::<Goal<_>>(),
                 );
-            }
-
-            // Orphan rules:
-            if !self.flags.upstream {
-                // Impls for traits declared locally always pass the impl rules
-                builder.push_fact(DomainGoal::LocalImplAllowed(trait_ref.clone()));
             } else {
-                // Impls for remote traits must have a local type in the right place
+                // The number of parameters will always be at least 1
+                // because of the Self parameter that is automatically
+                // added to every trait. This is important because
+                // otherwise the added program clauses would not have any
+                // conditions.
+                let type_parameters: Vec<_> = trait_ref.type_parameters().collect();
+
+                // Add all cases for potential downstream impls that could exist
                 for i in 0..type_parameters.len() {
                     builder.push_clause(
-                        DomainGoal::LocalImplAllowed(trait_ref.clone()),
-                        (0..i)
-                            .map(|j| DomainGoal::IsFullyVisible(type_parameters[j].clone()))
-                            .chain(Some(DomainGoal::IsLocal(type_parameters[i].clone()))),
+                        trait_ref.clone(),
+                        where_clauses
+                            .iter()
+                            .cloned()
+                            .casted()
+                            .chain(iter::once(DomainGoal::Compatible(()).cast()))
+                            .chain((0..i).map(|j| {
+                                DomainGoal::IsFullyVisible(type_parameters[j].clone()).cast()
+                            }))
+                            .chain(iter::once(
+                                DomainGoal::DownstreamType(type_parameters[i].clone()).cast(),
+                            ))
+                            .chain(iter::once(Goal::CannotProve(()))),
                     );
                 }
-            }
 
-            // Fundamental traits can be reasoned about negatively without any ambiguity, so no
-            // need for this rule if the trait is fundamental.
-            if !self.flags.fundamental {
-                builder.push_clause(
-                    trait_ref.clone(),
-                    where_clauses
-                        .iter()
-                        .cloned()
-                        .casted()
-                        .chain(iter::once(DomainGoal::Compatible(()).cast()))
-                        .chain(
-                            trait_ref
-                                .type_parameters()
-                                .map(|ty| DomainGoal::IsUpstream(ty).cast()),
-                        )
-                        .chain(iter::once(Goal::CannotProve(()))),
-                );
+                // Orphan rules. A trait defined in a crate is local to
+                // that crate and remote to the crates depending on it.
+                let (local, remote) = match self.flags.krate {
+                    Some(krate) => (
+                        Some(DomainGoal::InCrate(krate)),
+                        Some(DomainGoal::UpstreamCrate(krate)),
+                    ),
+                    None => (None, None),
+                };
+                if self.flags.krate.is_some() || !self.flags.upstream {
+                    // Impls for traits declared locally always pass the impl rules
+                    builder.push_clause(DomainGoal::LocalImplAllowed(trait_ref.clone()), local);
+                }
+                if self.flags.krate.is_some() || self.flags.upstream {
+                    // Impls for remote traits must have a local type in the right place
+                    for i in 0..type_parameters.len() {
+                        builder.push_clause(
+                            DomainGoal::LocalImplAllowed(trait_ref.clone()),
+                            remote
+                                .iter()
+                                .cloned()
+                                .chain((0..i).map(|j| {
+                                    DomainGoal::IsFullyVisible(type_parameters[j].clone())
+                                }))
+                                .chain(Some(DomainGoal::IsLocal(type_parameters[i].clone()))),
+                        );
+                    }
+                }
+
+                // Fundamental traits can be reasoned about negatively without any ambiguity, so no
+                // need for this rule if the trait is fundamental.
+                if !self.flags.fundamental {
+                    builder.push_clause(
+                        trait_ref.clone(),
+                        where_clauses
+                            .iter()
+                            .cloned()
+                            .casted()
+                            .chain(iter::once(DomainGoal::Compatible(()).cast()))
+                            .chain(
+                                trait_ref
+                                    .type_parameters()
+                                    .map(|ty| DomainGoal::IsUpstream(ty).cast()),
+                            )
+                            .chain(iter::once(Goal::CannotProve(()))),
+                    );
+                }
             }
 
             // Reverse implied bound rules: given (e.g.) `trait Foo: Bar + Baz`,
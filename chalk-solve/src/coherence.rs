@@ -3,7 +3,7 @@ use petgraph::prelude::*;
 use crate::solve::SolverChoice;
 use crate::RustIrDatabase;
 use chalk_ir::{self, Identifier, ImplId, TraitId};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::sync::Arc;
 
@@ -45,6 +45,9 @@ impl std::error::Error for CoherenceError {}
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct SpecializationPriorities {
     map: BTreeMap<ImplId, SpecializationPriority>,
+
+    /// The impls that are specialized by some other impl.
+    specialized: BTreeSet<ImplId>,
 }
 
 impl SpecializationPriorities {
@@ -59,6 +62,12 @@ impl SpecializationPriorities {
         let old_value = self.map.insert(impl_id, p);
         assert!(old_value.is_none());
     }
+
+    /// True if no impl in the set specializes the impl `impl_id`; the
+    /// values of its `default` items are then never overridden.
+    pub fn is_final(&self, impl_id: ImplId) -> bool {
+        !self.specialized.contains(&impl_id)
+    }
 }
 
 /// Impls with higher priority take precedence over impls with lower
@@ -130,6 +139,7 @@ where
 
         // Visit all children of this node, setting their priority to this + 1
         for child_idx in forest.neighbors(idx) {
+            map.specialized.insert(forest[idx]);
             self.set_priorities(child_idx, forest, p + 1, map);
         }
     }
@@ -12,7 +12,9 @@ use chalk_ir::*;
 //
 //     forall<T> { LocalImplAllowed(MyType<T>: Trait) }
 //
-// This must be provable in order to pass the orphan check.
+// This must be provable in order to pass the orphan check. In a program
+// made up of several crates, it must be provable from the perspective of
+// the crate that defines the impl.
 pub fn perform_orphan_check(
     db: &dyn RustIrDatabase,
     solver_choice: SolverChoice,
@@ -30,6 +32,10 @@ pub fn perform_orphan_check(
             DomainGoal::LocalImplAllowed(bound_impl.trait_ref.clone())
         })
         .cast();
+    let impl_allowed = match impl_datum.krate {
+        Some(krate) => db.crate_datum(krate).perspective(impl_allowed),
+        None => impl_allowed,
+    };
 
     let canonical_goal = &impl_allowed.into_closed_goal();
     let is_allowed = solver_choice
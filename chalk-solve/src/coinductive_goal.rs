@@ -19,7 +19,9 @@ impl IsCoinductive for Goal<ChalkIr> {
         match self {
             Goal::Leaf(LeafGoal::DomainGoal(DomainGoal::Holds(wca))) => match wca {
                 WhereClause::Implemented(tr) => db.trait_datum(tr.trait_id).is_auto_trait(),
-                WhereClause::ProjectionEq(..) => false,
+                WhereClause::ProjectionEq(..)
+                | WhereClause::LifetimeOutlives(..)
+                | WhereClause::TypeOutlives(..) => false,
             },
             Goal::Leaf(LeafGoal::DomainGoal(DomainGoal::WellFormed(WellFormed::Trait(..)))) => true,
             Goal::Quantified(QuantifierKind::ForAll, goal) => goal.value.is_coinductive(db),
@@ -150,6 +150,25 @@ impl InferenceTable {
         Some(v1)
     }
 
+    /// Resolves `lifetime` as far as the table allows: a bound inference
+    /// variable is replaced by its value, and an unbound one by the root
+    /// of its equivalence class. Two lifetimes that have been unified
+    /// thus resolve to the same value.
+    pub(crate) fn resolve_lifetime(&mut self, lifetime: &Lifetime<ChalkIr>) -> Lifetime<ChalkIr> {
+        let lifetime = self.normalize_lifetime(lifetime).unwrap_or(*lifetime);
+        match lifetime.inference_var() {
+            Some(var) => self.unify.find(EnaVariable::from(var)).to_lifetime(),
+            None => lifetime,
+        }
+    }
+
+    /// If `leaf` represents an inference variable `X`, and `X` is bound,
+    /// returns `Some(v)` where `v` is the value to which `X` is bound.
+    pub(crate) fn normalize_const(&mut self, leaf: &Const<ChalkIr>) -> Option<Const<ChalkIr>> {
+        let var = EnaVariable::from(leaf.inference_var()?);
+        self.probe_const_var(var)
+    }
+
     /// Returns true if `var` has been bound.
     pub(crate) fn var_is_bound(&mut self, var: InferenceVar) -> bool {
         match self.unify.probe_value(EnaVariable::from(var)) {
@@ -187,6 +206,20 @@ impl InferenceTable {
         }
     }
 
+    /// Finds the const to which `var` is bound, returning `None` if it is not yet
+    /// bound.
+    ///
+    /// # Panics
+    ///
+    /// This method is only valid for inference variables of kind
+    /// const. If this variable is of a different kind, then the function may panic.
+    fn probe_const_var(&mut self, var: EnaVariable) -> Option<Const<ChalkIr>> {
+        match self.unify.probe_value(var) {
+            InferenceValue::Unbound(_) => None,
+            InferenceValue::Bound(ref val) => Some(val.as_ref().constant().unwrap().clone()),
+        }
+    }
+
     /// Given an unbound variable, returns its universe.
     ///
     /// # Panics
@@ -209,6 +242,7 @@ impl ParameterEnaVariableExt for ParameterEnaVariable {
         match self {
             ParameterKind::Ty(v) => v.to_ty().cast(),
             ParameterKind::Lifetime(v) => v.to_lifetime().cast(),
+            ParameterKind::Const(v) => v.to_const().cast(),
         }
     }
 }
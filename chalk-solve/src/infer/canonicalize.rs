@@ -114,6 +114,15 @@ impl<'q> PlaceholderFolder<ChalkIr> for Canonicalizer<'q> {
         self.max_universe = max(self.max_universe, universe.ui);
         Ok(universe.to_lifetime::<ChalkIr>())
     }
+
+    fn fold_free_placeholder_const(
+        &mut self,
+        universe: PlaceholderIndex,
+        _binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        self.max_universe = max(self.max_universe, universe.ui);
+        Ok(universe.to_const::<ChalkIr>())
+    }
 }
 
 impl<'q> DefaultFreeVarFolder for Canonicalizer<'q> {
@@ -168,4 +177,29 @@ impl<'q> InferenceFolder<ChalkIr> for Canonicalizer<'q> {
             }
         }
     }
+
+    fn fold_inference_const(
+        &mut self,
+        var: InferenceVar,
+        binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        debug_heading!(
+            "fold_inference_const(depth={:?}, binders={:?})",
+            var,
+            binders
+        );
+        let var = EnaVariable::from(var);
+        match self.table.probe_const_var(var) {
+            Some(c) => {
+                debug!("bound to {:?}", c);
+                Ok(c.fold_with(self, 0)?.shifted_in(binders))
+            }
+            None => {
+                let free_var = ParameterKind::Const(self.table.unify.find(var));
+                let position = self.add(free_var);
+                debug!("not yet unified: position={:?}", position);
+                Ok(Const::BoundVar(position + binders))
+            }
+        }
+    }
 }
@@ -92,6 +92,7 @@ impl InferenceTable {
                         lt.cast()
                     }
                     ParameterKind::Ty(()) => placeholder_idx.to_ty::<ChalkIr>().cast(),
+                    ParameterKind::Const(()) => placeholder_idx.to_const::<ChalkIr>().cast(),
                 }
             })
             .collect();
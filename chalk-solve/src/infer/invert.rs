@@ -31,8 +31,6 @@ impl InferenceTable {
     ///
     /// ```rust,ignore
     /// struct Vec<T> {}
-    /// struct i32 {}
-    /// struct u32 {}
     /// trait Foo {}
     /// impl Foo for Vec<u32> {}
     /// ```
@@ -102,6 +100,7 @@ struct Inverter<'q> {
     table: &'q mut InferenceTable,
     inverted_ty: HashMap<PlaceholderIndex, EnaVariable>,
     inverted_lifetime: HashMap<PlaceholderIndex, EnaVariable>,
+    inverted_const: HashMap<PlaceholderIndex, EnaVariable>,
 }
 
 impl<'q> Inverter<'q> {
@@ -110,6 +109,7 @@ impl<'q> Inverter<'q> {
             table,
             inverted_ty: HashMap::new(),
             inverted_lifetime: HashMap::new(),
+            inverted_const: HashMap::new(),
         }
     }
 }
@@ -144,6 +144,20 @@ impl<'q> PlaceholderFolder<ChalkIr> for Inverter<'q> {
             .to_lifetime()
             .shifted_in(binders))
     }
+
+    fn fold_free_placeholder_const(
+        &mut self,
+        universe: PlaceholderIndex,
+        binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        let table = &mut self.table;
+        Ok(self
+            .inverted_const
+            .entry(universe)
+            .or_insert_with(|| table.new_variable(universe.ui))
+            .to_const()
+            .shifted_in(binders))
+    }
 }
 
 impl<'q> DefaultFreeVarFolder for Inverter<'q> {
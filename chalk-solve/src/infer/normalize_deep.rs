@@ -55,6 +55,18 @@ impl<'table> InferenceFolder<ChalkIr> for DeepNormalizer<'table> {
             None => Ok(var.to_lifetime()), // FIXME shift
         }
     }
+
+    fn fold_inference_const(
+        &mut self,
+        var: InferenceVar,
+        binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        let var = EnaVariable::from(var);
+        match self.table.probe_const_var(var) {
+            Some(c) => Ok(c.fold_with(self, 0)?.shifted_in(binders)),
+            None => Ok(var.to_const()),
+        }
+    }
 }
 
 impl<'table> DefaultFreeVarFolder for DeepNormalizer<'table> {
@@ -247,6 +247,15 @@ impl<'q> PlaceholderFolder<ChalkIr> for UCollector<'q> {
         self.universes.add(universe.ui);
         Ok(universe.to_lifetime::<ChalkIr>())
     }
+
+    fn fold_free_placeholder_const(
+        &mut self,
+        universe: PlaceholderIndex,
+        _binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        self.universes.add(universe.ui);
+        Ok(universe.to_const::<ChalkIr>())
+    }
 }
 
 impl<'q> DefaultFreeVarFolder for UCollector<'q> {}
@@ -295,6 +304,19 @@ impl<'q> PlaceholderFolder<ChalkIr> for UMapToCanonical<'q> {
         }
         .to_lifetime::<ChalkIr>())
     }
+
+    fn fold_free_placeholder_const(
+        &mut self,
+        universe0: PlaceholderIndex,
+        _binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        let universe = self.universes.map_universe_to_canonical(universe0.ui);
+        Ok(PlaceholderIndex {
+            ui: universe,
+            idx: universe0.idx,
+        }
+        .to_const::<ChalkIr>())
+    }
 }
 
 impl<'q> DefaultFreeVarFolder for UMapToCanonical<'q> {}
@@ -331,6 +353,19 @@ impl<'q> PlaceholderFolder<ChalkIr> for UMapFromCanonical<'q> {
         }
         .to_lifetime::<ChalkIr>())
     }
+
+    fn fold_free_placeholder_const(
+        &mut self,
+        universe0: PlaceholderIndex,
+        _binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        let universe = self.universes.map_universe_from_canonical(universe0.ui);
+        Ok(PlaceholderIndex {
+            ui: universe,
+            idx: universe0.idx,
+        }
+        .to_const::<ChalkIr>())
+    }
 }
 
 impl<'q> DefaultFreeVarFolder for UMapFromCanonical<'q> {}
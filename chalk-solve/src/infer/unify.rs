@@ -8,6 +8,8 @@ use chalk_ir::zip::{Zip, Zipper};
 
 use super::var::*;
 use super::*;
+use crate::variance;
+use crate::RustIrDatabase;
 
 impl InferenceTable {
     pub(crate) fn unify<T>(
@@ -25,8 +27,51 @@ impl InferenceTable {
             a,
             b
         );
+        self.unify_with(None, environment, Variance::Invariant, a, b)
+    }
+
+    /// Like `unify`, but relates `a` and `b` according to `variance`:
+    /// `Covariant` requires `a <: b`, `Contravariant` requires `b <: a`,
+    /// and `Invariant` is plain unification. Lifetimes related by
+    /// subtyping produce outlives constraints rather than equalities.
+    pub(crate) fn relate<T>(
+        &mut self,
+        db: &dyn RustIrDatabase,
+        environment: &Environment<ChalkIr>,
+        variance: Variance,
+        a: &T,
+        b: &T,
+    ) -> Fallible<UnificationResult>
+    where
+        T: ?Sized + Zip<ChalkIr>,
+    {
+        debug_heading!(
+            "relate(variance={:?}\
+             ,\n       a={:?}\
+             ,\n       b={:?})",
+            variance,
+            a,
+            b
+        );
+        self.unify_with(Some(db), environment, variance, a, b)
+    }
+
+    fn unify_with<T>(
+        &mut self,
+        db: Option<&dyn RustIrDatabase>,
+        environment: &Environment<ChalkIr>,
+        variance: Variance,
+        a: &T,
+        b: &T,
+    ) -> Fallible<UnificationResult>
+    where
+        T: ?Sized + Zip<ChalkIr>,
+    {
         let snapshot = self.snapshot();
-        match Unifier::new(self, environment).unify(a, b) {
+        let mut unifier = Unifier::new(self, environment);
+        unifier.db = db;
+        unifier.variance = variance;
+        match unifier.unify(a, b) {
             Ok(r) => {
                 self.commit(snapshot);
                 Ok(r)
@@ -44,6 +89,14 @@ struct Unifier<'t> {
     environment: &'t Environment<ChalkIr>,
     goals: Vec<InEnvironment<DomainGoal<ChalkIr>>>,
     constraints: Vec<InEnvironment<Constraint<ChalkIr>>>,
+
+    /// The database, used to look up the variance of struct parameters.
+    /// Only needed when relating types by subtyping.
+    db: Option<&'t dyn RustIrDatabase>,
+
+    /// The relation we are establishing between the values being
+    /// zipped; this changes as we descend into type parameters.
+    variance: Variance,
 }
 
 #[derive(Debug)]
@@ -59,6 +112,8 @@ impl<'t> Unifier<'t> {
             table: table,
             goals: vec![],
             constraints: vec![],
+            db: None,
+            variance: Variance::Invariant,
         }
     }
 
@@ -82,7 +137,8 @@ impl<'t> Unifier<'t> {
     where
         T: Zip<ChalkIr> + Fold<ChalkIr>,
     {
-        let sub_unifier = Unifier::new(self.table, &self.environment);
+        let mut sub_unifier = Unifier::new(self.table, &self.environment);
+        sub_unifier.db = self.db;
         let UnificationResult { goals, constraints } = sub_unifier.unify(&ty1, &ty2)?;
         self.goals.extend(goals);
         self.constraints.extend(constraints);
@@ -104,6 +160,10 @@ impl<'t> Unifier<'t> {
             b
         );
 
+        if self.variance != Variance::Invariant {
+            return self.relate_ty_ty(a, b);
+        }
+
         match (a, b) {
             // Unifying two inference variables: unify them in the underlying
             // ena table.
@@ -144,6 +204,17 @@ impl<'t> Unifier<'t> {
             }
 
             (&Ty::Apply(ref apply1), &Ty::Apply(ref apply2)) => {
+                // A named opaque type can only be equal to another type
+                // if its hidden type is revealed.
+                if apply1.name != apply2.name {
+                    if let Some(opaque_ty_id) = apply1.opaque_ty_id() {
+                        return self.unify_opaque_ty(opaque_ty_id, apply1, b);
+                    }
+                    if let Some(opaque_ty_id) = apply2.opaque_ty_id() {
+                        return self.unify_opaque_ty(opaque_ty_id, apply2, a);
+                    }
+                }
+
                 // Cannot unify (e.g.) some struct type `Foo` and some struct type `Bar`
                 if apply1.name != apply2.name {
                     return Err(NoSolution);
@@ -193,6 +264,59 @@ impl<'t> Unifier<'t> {
         }
     }
 
+    /// Relates `a` and `b` by subtyping, in the direction given by
+    /// `self.variance`. Two applications of the same type are related by
+    /// relating their parameters according to the type's variances; any
+    /// other types must be equal.
+    ///
+    /// An unbound inference variable related to an application is first
+    /// bound to a "generalized" copy of it, with fresh lifetime variables
+    /// in place of its lifetimes (as rustc does), so `?X <: &'a u32`
+    /// binds `?X` to `&'?0 u32` and requires `'?0: 'a`, rather than
+    /// forcing `?X` to be exactly `&'a u32`.
+    fn relate_ty_ty(&mut self, a: &Ty<ChalkIr>, b: &Ty<ChalkIr>) -> Fallible<()> {
+        if let Some(n_a) = self.table.normalize_shallow(a) {
+            return self.relate_ty_ty(&n_a, b);
+        } else if let Some(n_b) = self.table.normalize_shallow(b) {
+            return self.relate_ty_ty(a, &n_b);
+        }
+
+        match (a, b) {
+            (Ty::Apply(apply1), Ty::Apply(apply2)) if apply1.name == apply2.name => {
+                let db = self
+                    .db
+                    .expect("relating types by subtyping requires a database");
+                let variances = variance::type_variances(db, apply1.name, apply1.parameters.len());
+
+                let ambient = self.variance;
+                let parameters = apply1.parameters.iter().zip(&apply2.parameters);
+                for ((a, b), variance) in parameters.zip(variances.iter().cloned()) {
+                    self.variance = ambient.xform(variance);
+                    let result = Zip::zip_with(self, a, b);
+                    self.variance = ambient;
+                    result?;
+                }
+                Ok(())
+            }
+
+            (&Ty::InferenceVar(var), ty @ Ty::Apply(_))
+            | (ty @ Ty::Apply(_), &Ty::InferenceVar(var)) => {
+                let universe_index = self.table.universe_of_unbound_var(EnaVariable::from(var));
+                let generalized = ty.fold_with(&mut Generalize::new(self, universe_index), 0)?;
+                self.unify_var_ty(var, &generalized)?;
+                self.relate_ty_ty(a, b)
+            }
+
+            _ => {
+                let ambient = self.variance;
+                self.variance = Variance::Invariant;
+                let result = self.unify_ty_ty(a, b);
+                self.variance = ambient;
+                result
+            }
+        }
+    }
+
     fn unify_forall_tys(
         &mut self,
         ty1: &QuantifiedTy<ChalkIr>,
@@ -249,6 +373,30 @@ impl<'t> Unifier<'t> {
         )))
     }
 
+    /// Unify an application `opaque_ty` of a named opaque type like
+    /// `Foo<T>` with some other type `ty`. Creates a goal like
+    ///
+    /// ```notrust
+    /// OpaqueEq(Foo<T> = U)
+    /// ```
+    fn unify_opaque_ty(
+        &mut self,
+        opaque_ty_id: OpaqueTyId,
+        opaque_ty: &ApplicationTy<ChalkIr>,
+        ty: &Ty<ChalkIr>,
+    ) -> Fallible<()> {
+        self.goals.push(InEnvironment::new(
+            self.environment,
+            OpaqueEq {
+                opaque_ty_id,
+                parameters: opaque_ty.parameters.clone(),
+                ty: ty.clone(),
+            }
+            .cast(),
+        ));
+        Ok(())
+    }
+
     /// Unifying `forall<X> { T }` with some other type `U` --
     /// to do so, we create a fresh placeholder `P` for `X` and
     /// see if `[X/Px] T` can be unified with `U`. This should
@@ -311,6 +459,20 @@ impl<'t> Unifier<'t> {
 
         debug_heading!("unify_lifetime_lifetime({:?}, {:?})", a, b);
 
+        // `&'a T <: &'b T` if `'a: 'b`.
+        match self.variance {
+            Variance::Invariant => {}
+            _ if a == b => return Ok(()),
+            Variance::Covariant => {
+                self.push_lifetime_outlives_constraint(*a, *b);
+                return Ok(());
+            }
+            Variance::Contravariant => {
+                self.push_lifetime_outlives_constraint(*b, *a);
+                return Ok(());
+            }
+        }
+
         match (a, b) {
             (&Lifetime::InferenceVar(var_a), &Lifetime::InferenceVar(var_b)) => {
                 let var_a = EnaVariable::from(var_a);
@@ -364,12 +526,85 @@ impl<'t> Unifier<'t> {
         }
     }
 
+    fn unify_const_const(&mut self, a: &Const<ChalkIr>, b: &Const<ChalkIr>) -> Fallible<()> {
+        if let Some(n_a) = self.table.normalize_const(a) {
+            return self.unify_const_const(&n_a, b);
+        } else if let Some(n_b) = self.table.normalize_const(b) {
+            return self.unify_const_const(a, &n_b);
+        }
+
+        debug_heading!("unify_const_const({:?}, {:?})", a, b);
+
+        match (a, b) {
+            (&Const::InferenceVar(var_a), &Const::InferenceVar(var_b)) => {
+                let var_a = EnaVariable::from(var_a);
+                let var_b = EnaVariable::from(var_b);
+                debug!("unify_const_const: var_a={:?} var_b={:?}", var_a, var_b);
+                self.table.unify.unify_var_var(var_a, var_b).unwrap();
+                Ok(())
+            }
+
+            (&Const::InferenceVar(var), c @ &Const::Value(_))
+            | (c @ &Const::Value(_), &Const::InferenceVar(var)) => {
+                let var = EnaVariable::from(var);
+                debug!("unify_const_const: {:?} set to {:?}", var, c);
+                self.table
+                    .unify
+                    .unify_var_value(var, InferenceValue::from(*c))
+                    .unwrap();
+                Ok(())
+            }
+
+            // Unlike lifetimes, there is no constraint we can defer to: a
+            // variable that cannot name the placeholder simply fails to unify.
+            (&Const::InferenceVar(var), &Const::Placeholder(idx))
+            | (&Const::Placeholder(idx), &Const::InferenceVar(var)) => {
+                let var = EnaVariable::from(var);
+                let var_ui = self.table.universe_of_unbound_var(var);
+                if var_ui.can_see(idx.ui) {
+                    self.table
+                        .unify
+                        .unify_var_value(var, InferenceValue::from(Const::Placeholder(idx)))
+                        .unwrap();
+                    Ok(())
+                } else {
+                    Err(NoSolution)
+                }
+            }
+
+            (&Const::Value(_), &Const::Value(_))
+            | (&Const::Placeholder(_), &Const::Placeholder(_))
+            | (&Const::Value(_), &Const::Placeholder(_))
+            | (&Const::Placeholder(_), &Const::Value(_)) => {
+                if a == b {
+                    Ok(())
+                } else {
+                    Err(NoSolution)
+                }
+            }
+
+            (Const::BoundVar(_), _) | (_, Const::BoundVar(_)) => panic!(
+                "unification encountered bound variable: a={:?} b={:?}",
+                a, b
+            ),
+
+            (Const::Phantom(..), _) | (_, Const::Phantom(..)) => unreachable!(),
+        }
+    }
+
     fn push_lifetime_eq_constraint(&mut self, a: Lifetime<ChalkIr>, b: Lifetime<ChalkIr>) {
         self.constraints.push(InEnvironment::new(
             self.environment,
             Constraint::LifetimeEq(a, b),
         ));
     }
+
+    fn push_lifetime_outlives_constraint(&mut self, a: Lifetime<ChalkIr>, b: Lifetime<ChalkIr>) {
+        self.constraints.push(InEnvironment::new(
+            self.environment,
+            Constraint::LifetimeOutlives(a, b),
+        ));
+    }
 }
 
 impl<'t> Zipper<ChalkIr> for Unifier<'t> {
@@ -381,11 +616,29 @@ impl<'t> Zipper<ChalkIr> for Unifier<'t> {
         self.unify_lifetime_lifetime(a, b)
     }
 
-    fn zip_binders<T>(&mut self, _: &Binders<T>, _: &Binders<T>) -> Fallible<()>
+    fn zip_consts(&mut self, a: &Const<ChalkIr>, b: &Const<ChalkIr>) -> Fallible<()> {
+        self.unify_const_const(a, b)
+    }
+
+    fn zip_binders<T>(&mut self, a: &Binders<T>, b: &Binders<T>) -> Fallible<()>
     where
         T: Zip<ChalkIr> + Fold<ChalkIr, Result = T>,
     {
-        panic!("cannot unify things with binders (other than types)")
+        // As for `forall` types (see `unify_forall_tys`), `for<X...> T`
+        // and `for<Y...> U` (e.g. the bounds of two `dyn` types) are
+        // equal if:
+        //
+        // for<X...> exists<Y...> T == U &&
+        // for<Y...> exists<X...> T == U
+        debug!("zip_binders({:?}, {:?})", a, b);
+
+        let a_universal = self.table.instantiate_binders_universally(a);
+        let b_existential = self.table.instantiate_binders_existentially(b);
+        Zip::zip_with(self, &a_universal, &b_existential)?;
+
+        let b_universal = self.table.instantiate_binders_universally(b);
+        let a_existential = self.table.instantiate_binders_existentially(a);
+        Zip::zip_with(self, &a_existential, &b_universal)
     }
 }
 
@@ -449,6 +702,18 @@ impl<'u, 't> PlaceholderFolder<ChalkIr> for OccursCheck<'u, 't> {
             Ok(ui.to_lifetime::<ChalkIr>()) // no need to shift, not relative to depth
         }
     }
+
+    fn fold_free_placeholder_const(
+        &mut self,
+        universe: PlaceholderIndex,
+        _binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        if self.universe_index < universe.ui {
+            Err(NoSolution)
+        } else {
+            Ok(universe.to_const::<ChalkIr>()) // no need to shift, not relative to depth
+        }
+    }
 }
 
 impl<'u, 't> InferenceFolder<ChalkIr> for OccursCheck<'u, 't> {
@@ -524,6 +789,33 @@ impl<'u, 't> InferenceFolder<ChalkIr> for OccursCheck<'u, 't> {
             }
         }
     }
+
+    fn fold_inference_const(
+        &mut self,
+        var: InferenceVar,
+        binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        let var = EnaVariable::from(var);
+        match self.unifier.table.unify.probe_value(var) {
+            InferenceValue::Unbound(ui) => {
+                if self.universe_index < ui {
+                    self.unifier
+                        .table
+                        .unify
+                        .unify_var_value(var, InferenceValue::Unbound(self.universe_index))
+                        .unwrap();
+                }
+                Ok(var.to_const())
+            }
+
+            InferenceValue::Bound(c) => {
+                let c = c.constant().unwrap();
+                let c = c.fold_with(self, binders)?;
+                assert!(!c.needs_shift());
+                Ok(c)
+            }
+        }
+    }
 }
 
 impl<'u, 't> DefaultFreeVarFolder for OccursCheck<'u, 't> {
@@ -531,3 +823,79 @@ impl<'u, 't> DefaultFreeVarFolder for OccursCheck<'u, 't> {
         true
     }
 }
+
+/// Replaces the free lifetimes of a type with fresh lifetime variables in
+/// `universe_index`, leaving the rest of the type as it is; see
+/// `Unifier::relate_ty_ty`.
+struct Generalize<'u, 't: 'u> {
+    unifier: &'u mut Unifier<'t>,
+    universe_index: UniverseIndex,
+}
+
+impl<'u, 't> Generalize<'u, 't> {
+    fn new(unifier: &'u mut Unifier<'t>, universe_index: UniverseIndex) -> Self {
+        Generalize {
+            unifier,
+            universe_index,
+        }
+    }
+
+    fn fresh_lifetime(&mut self) -> Lifetime<ChalkIr> {
+        self.unifier
+            .table
+            .new_variable(self.universe_index)
+            .to_lifetime()
+    }
+}
+
+impl<'u, 't> DefaultTypeFolder for Generalize<'u, 't> {}
+
+impl<'u, 't> PlaceholderFolder<ChalkIr> for Generalize<'u, 't> {
+    fn fold_free_placeholder_ty(
+        &mut self,
+        universe: PlaceholderIndex,
+        _binders: usize,
+    ) -> Fallible<Ty<ChalkIr>> {
+        Ok(universe.to_ty::<ChalkIr>())
+    }
+
+    fn fold_free_placeholder_lifetime(
+        &mut self,
+        _universe: PlaceholderIndex,
+        _binders: usize,
+    ) -> Fallible<Lifetime<ChalkIr>> {
+        Ok(self.fresh_lifetime())
+    }
+
+    fn fold_free_placeholder_const(
+        &mut self,
+        universe: PlaceholderIndex,
+        _binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        Ok(universe.to_const::<ChalkIr>())
+    }
+}
+
+impl<'u, 't> InferenceFolder<ChalkIr> for Generalize<'u, 't> {
+    fn fold_inference_ty(&mut self, var: InferenceVar, _binders: usize) -> Fallible<Ty<ChalkIr>> {
+        Ok(var.to_ty::<ChalkIr>())
+    }
+
+    fn fold_inference_lifetime(
+        &mut self,
+        _var: InferenceVar,
+        _binders: usize,
+    ) -> Fallible<Lifetime<ChalkIr>> {
+        Ok(self.fresh_lifetime())
+    }
+
+    fn fold_inference_const(
+        &mut self,
+        var: InferenceVar,
+        _binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        Ok(var.to_const::<ChalkIr>())
+    }
+}
+
+impl<'u, 't> DefaultFreeVarFolder for Generalize<'u, 't> {}
@@ -57,6 +57,13 @@ impl EnaVariable {
     pub(crate) fn to_lifetime(self) -> Lifetime<ChalkIr> {
         self.0.to_lifetime::<ChalkIr>()
     }
+
+    /// Convert this inference variable into a const. When using this
+    /// method, naturally you should know from context that the kind
+    /// of this inference variable is a const (we can't check it).
+    pub(crate) fn to_const(self) -> Const<ChalkIr> {
+        self.0.to_const::<ChalkIr>()
+    }
 }
 
 impl UnifyKey for EnaVariable {
@@ -96,6 +103,12 @@ impl From<Lifetime<ChalkIr>> for InferenceValue {
     }
 }
 
+impl From<Const<ChalkIr>> for InferenceValue {
+    fn from(constant: Const<ChalkIr>) -> Self {
+        InferenceValue::Bound(constant.cast())
+    }
+}
+
 impl UnifyValue for InferenceValue {
     type Error = (InferenceValue, InferenceValue);
 
@@ -1,6 +1,7 @@
 use chalk_ir::family::ChalkIr;
 use chalk_ir::*;
 use chalk_rust_ir::*;
+use coherence::SpecializationPriorities;
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -12,8 +13,10 @@ pub mod coherence;
 mod coinductive_goal;
 pub mod ext;
 mod infer;
+pub mod object_safety;
 mod solve;
 pub mod split;
+pub mod variance;
 pub mod wf;
 
 pub trait RustIrDatabase: Debug {
@@ -30,9 +33,22 @@ pub trait RustIrDatabase: Debug {
     /// Returns the datum for the impl with the given id.
     fn struct_datum(&self, struct_id: StructId) -> Arc<StructDatum>;
 
+    /// Returns the datum for the enum with the given id.
+    fn enum_datum(&self, enum_id: EnumId) -> Arc<EnumDatum>;
+
+    /// Returns the datum for the closure type with the given id.
+    fn closure_datum(&self, closure_id: ClosureId) -> Arc<ClosureDatum>;
+
+    /// Returns the datum for the named opaque type with the given id.
+    fn opaque_ty_data(&self, id: OpaqueTyId) -> Arc<OpaqueTyDatum>;
+
     /// Returns the datum for the impl with the given id.
     fn impl_datum(&self, impl_id: ImplId) -> Arc<ImplDatum>;
 
+    /// Returns the datum for the crate with the given name, in programs
+    /// made up of several crates.
+    fn crate_datum(&self, krate: Identifier) -> Arc<CrateDatum>;
+
     /// Returns the `AssociatedTyValue` with the given id.
     fn associated_ty_value(&self, id: AssociatedTyValueId) -> Arc<AssociatedTyValue>;
 
@@ -56,15 +72,31 @@ pub trait RustIrDatabase: Debug {
     fn local_impls_to_coherence_check(&self, trait_id: TraitId) -> Vec<ImplId>;
 
     /// Returns true if there is an explicit impl of the auto trait
-    /// `auto_trait_id` for the struct `struct_id`. This is part of
-    /// the auto trait handling -- if there is no explicit impl given
-    /// by the user for the struct, then we provide default impls
+    /// `auto_trait_id` for the type `type_name` (a struct, an enum or
+    /// a built-in type like `&'a T`). This is
+    /// part of the auto trait handling -- if there is no explicit impl
+    /// given by the user for the type, then we provide default impls
     /// based on the field types (otherwise, we rely on the impls the
     /// user gave).
-    fn impl_provided_for(&self, auto_trait_id: TraitId, struct_id: StructId) -> bool;
+    fn impl_provided_for(&self, auto_trait_id: TraitId, type_name: TypeName) -> bool;
+
+    /// Returns the id of the trait marked as the lang item `lang_item`,
+    /// if the program declares one.
+    fn well_known_trait_id(&self, lang_item: LangItem) -> Option<TraitId>;
+
+    /// Returns the variances of the parameters of the struct or enum
+    /// `adt_id`, as computed by `variance::compute_adt_variances`.
+    fn adt_variances(&self, adt_id: TypeKindId) -> Arc<Vec<Variance>>;
 
     /// Returns the name for the type with the given id.
     fn type_name(&self, id: TypeKindId) -> Identifier;
+
+    /// Returns the specialization priorities of the impls of the trait
+    /// `trait_id`, which decide whether `default` items of those impls
+    /// may be overridden. Returns `None` if they are not known (yet);
+    /// every impl is then treated as final.
+    fn specialization_priorities(&self, trait_id: TraitId)
+        -> Option<Arc<SpecializationPriorities>>;
 }
 
 pub use solve::Guidance;
@@ -0,0 +1,83 @@
+//! Object safety: which traits can be used as trait objects, `dyn Trait`.
+//!
+//! We check a subset of the rules that rustc enforces, namely those that
+//! can be expressed in chalk programs: a trait is object safe unless
+//!
+//! * it requires `Self: Sized`;
+//! * one of its associated types has parameters of its own, like
+//!   `type Item<'a>`, as a trait object could not name all of them;
+//! * one of its supertraits is not object safe.
+
+use crate::RustIrDatabase;
+use chalk_ir::family::ChalkIr;
+use chalk_ir::*;
+use chalk_rust_ir::*;
+use std::collections::BTreeSet;
+
+/// Returns true if the trait `trait_id` is object safe.
+pub fn is_object_safe(db: &dyn RustIrDatabase, trait_id: TraitId) -> bool {
+    ObjectSafetyChecker {
+        db,
+        visited: BTreeSet::new(),
+    }
+    .check(trait_id)
+}
+
+struct ObjectSafetyChecker<'db> {
+    db: &'db dyn RustIrDatabase,
+    /// The traits that we have checked, or are checking. A trait that we
+    /// come back to is taken to be object safe: whether it really is gets
+    /// decided where we first came across it.
+    visited: BTreeSet<TraitId>,
+}
+
+impl ObjectSafetyChecker<'_> {
+    fn check(&mut self, trait_id: TraitId) -> bool {
+        if !self.visited.insert(trait_id) {
+            return true;
+        }
+
+        let trait_datum = self.db.trait_datum(trait_id);
+        let trait_binders_len = trait_datum.binders.binders.len();
+
+        let generic_assoc_ty = trait_datum.associated_ty_ids.iter().any(|&id| {
+            let assoc_ty_datum = self.db.associated_ty_data(id);
+            assoc_ty_datum.binders.binders.len() > trait_binders_len
+        });
+        if generic_assoc_ty {
+            return false;
+        }
+
+        let sized_trait_id = self.db.well_known_trait_id(LangItem::Sized);
+        for qwc in &trait_datum.binders.value.where_clauses {
+            let trait_ref = match &qwc.value {
+                WhereClause::Implemented(trait_ref) => trait_ref,
+                _ => continue,
+            };
+
+            // `Self` is the first parameter of the trait; within the
+            // where clause, it is shifted by the clause's own binders.
+            match trait_ref.parameters[0].assert_ty_ref() {
+                Ty::BoundVar(depth) if *depth == qwc.binders.len() => {}
+                _ => continue,
+            }
+
+            if Some(trait_ref.trait_id) == sized_trait_id || !self.check(trait_ref.trait_id) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Returns the traits that the trait object type `dyn_ty` (the clauses
+/// of a `Ty::Dyn`) is made of.
+pub fn trait_object_traits(
+    dyn_ty: &Binders<Vec<QuantifiedWhereClause<ChalkIr>>>,
+) -> impl Iterator<Item = TraitId> + '_ {
+    dyn_ty.value.iter().filter_map(|qwc| match &qwc.value {
+        WhereClause::Implemented(trait_ref) => Some(trait_ref.trait_id),
+        _ => None,
+    })
+}
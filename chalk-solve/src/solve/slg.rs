@@ -21,6 +21,7 @@ use chalk_engine::{Answer, ExClause, Literal};
 use std::fmt::Debug;
 
 mod aggregate;
+mod constraints;
 mod resolvent;
 
 #[derive(Clone, Debug)]
@@ -71,7 +72,7 @@ impl context::Context for SlgContext {
     type GoalInEnvironment = InEnvironment<Goal<ChalkIr>>;
     type Substitution = Substitution<ChalkIr>;
     type RegionConstraint = InEnvironment<Constraint<ChalkIr>>;
-    type Variance = ();
+    type Variance = Variance;
 
     fn goal_in_environment(
         environment: &Environment<ChalkIr>,
@@ -192,6 +193,21 @@ impl<'me> context::ContextOps<SlgContext> for SlgContextOps<'me> {
         Ok(clauses)
     }
 
+    fn unify_parameters_into_ex_clause(
+        &self,
+        infer: &mut TruncatingInferenceTable,
+        environment: &Environment<ChalkIr>,
+        variance: Variance,
+        a: &Parameter<ChalkIr>,
+        b: &Parameter<ChalkIr>,
+        ex_clause: &mut ExClause<SlgContext>,
+    ) -> Fallible<()> {
+        let result = infer
+            .infer
+            .relate(self.program, environment, variance, a, b)?;
+        Ok(into_ex_clause(result, ex_clause))
+    }
+
     fn instantiate_ucanonical_goal<R>(
         &self,
         arg: &UCanonical<InEnvironment<Goal<ChalkIr>>>,
@@ -268,7 +284,12 @@ impl context::InferenceTable<SlgContext> for TruncatingInferenceTable {
             Goal::Implies(dg, subgoal) => HhGoal::Implies(dg, *subgoal),
             Goal::And(g1, g2) => HhGoal::And(*g1, *g2),
             Goal::Not(g1) => HhGoal::Not(*g1),
-            Goal::Leaf(LeafGoal::EqGoal(EqGoal { a, b })) => HhGoal::Unify((), a, b),
+            Goal::Leaf(LeafGoal::EqGoal(EqGoal { a, b })) => {
+                HhGoal::Unify(Variance::Invariant, a, b)
+            }
+            Goal::Leaf(LeafGoal::SubtypeGoal(SubtypeGoal { a, b })) => {
+                HhGoal::Unify(Variance::Covariant, a.cast(), b.cast())
+            }
             Goal::Leaf(LeafGoal::DomainGoal(domain_goal)) => HhGoal::DomainGoal(domain_goal),
             Goal::CannotProve(()) => HhGoal::CannotProve,
         }
@@ -343,6 +364,7 @@ impl context::UnificationOps<SlgContext> for TruncatingInferenceTable {
         subst: Substitution<ChalkIr>,
         constraints: Vec<InEnvironment<Constraint<ChalkIr>>>,
     ) -> Canonical<ConstrainedSubst<ChalkIr>> {
+        let constraints = constraints::simplify_constraints(&mut self.infer, &subst, constraints);
         self.infer
             .canonicalize(&ConstrainedSubst { subst, constraints })
             .quantified
@@ -354,18 +376,6 @@ impl context::UnificationOps<SlgContext> for TruncatingInferenceTable {
     ) -> Option<InEnvironment<Goal<ChalkIr>>> {
         self.infer.invert(value)
     }
-
-    fn unify_parameters_into_ex_clause(
-        &mut self,
-        environment: &Environment<ChalkIr>,
-        _: (),
-        a: &Parameter<ChalkIr>,
-        b: &Parameter<ChalkIr>,
-        ex_clause: &mut ExClause<SlgContext>,
-    ) -> Fallible<()> {
-        let result = self.infer.unify(environment, a, b)?;
-        Ok(into_ex_clause(result, ex_clause))
-    }
 }
 
 /// Helper function
@@ -403,7 +413,10 @@ impl MayInvalidate {
             (ParameterKind::Lifetime(l1), ParameterKind::Lifetime(l2)) => {
                 self.aggregate_lifetimes(l1, l2)
             }
-            (ParameterKind::Ty(_), _) | (ParameterKind::Lifetime(_), _) => panic!(
+            (ParameterKind::Const(c1), ParameterKind::Const(c2)) => self.aggregate_consts(c1, c2),
+            (ParameterKind::Ty(_), _)
+            | (ParameterKind::Lifetime(_), _)
+            | (ParameterKind::Const(_), _) => panic!(
                 "mismatched parameter kinds: new={:?} current={:?}",
                 new, current
             ),
@@ -467,6 +480,25 @@ impl MayInvalidate {
         true
     }
 
+    fn aggregate_consts(&mut self, new: &Const<ChalkIr>, current: &Const<ChalkIr>) -> bool {
+        match (new, current) {
+            // As with types, a variable in the current aggregate cannot
+            // be generalized any further.
+            (_, Const::BoundVar(_)) => false,
+
+            (Const::BoundVar(_), _) => true,
+
+            (Const::InferenceVar(_), _) | (_, Const::InferenceVar(_)) => panic!(
+                "unexpected free inference variable in may-invalidate: {:?} vs {:?}",
+                new, current,
+            ),
+
+            (Const::Placeholder(_), _) | (Const::Value(_), _) => new != current,
+
+            (Const::Phantom(..), _) => unreachable!(),
+        }
+    }
+
     fn aggregate_application_tys(
         &mut self,
         new: &ApplicationTy<ChalkIr>,
@@ -24,7 +24,36 @@ impl<'me> context::AggregateOps<SlgContext> for SlgContextOps<'me> {
         if answers.peek_answer().is_none() {
             return None;
         }
-        let Answer { subst, ambiguous } = answers.next_answer().unwrap();
+        let Answer {
+            mut subst,
+            ambiguous,
+        } = answers.next_answer().unwrap();
+
+        // Answers with the same substitution that only differ in their
+        // region constraints are different proofs of the same thing, e.g.
+        // `T: 'a` proven from an implied bound (no constraints) and from
+        // the fallback rule (constraint `T: 'a`). If one answer's
+        // constraints are a subset of the other's, the caller only has to
+        // check the smaller set, so keep that answer.
+        if !ambiguous {
+            while let Some(next) = answers.peek_answer() {
+                if next.ambiguous
+                    || next.subst.binders != subst.binders
+                    || next.subst.value.subst != subst.value.subst
+                {
+                    break;
+                }
+
+                let (next_constraints, constraints) =
+                    (&next.subst.value.constraints, &subst.value.constraints);
+                if is_subset(next_constraints, constraints) {
+                    subst = next.subst;
+                } else if !is_subset(constraints, next_constraints) {
+                    break;
+                }
+                answers.next_answer();
+            }
+        }
 
         // Exactly 1 unconditional answer?
         if answers.peek_answer().is_none() && !ambiguous {
@@ -39,10 +68,10 @@ impl<'me> context::AggregateOps<SlgContext> for SlgContextOps<'me> {
         //
         // FIXME-- there is actually a 3rd possibility. We could have
         // >1 answer where all the answers have the same substitution,
-        // but different region constraints. We should collapse those
-        // cases into an `OR` region constraint at some point, but I
-        // leave that for future work. This is basically
-        // rust-lang/rust#21974.
+        // but different region constraints, neither of which subsumes
+        // the other (see above). We should collapse those cases into an
+        // `OR` region constraint at some point, but I leave that for
+        // future work. This is basically rust-lang/rust#21974.
         let mut subst = subst.map(|cs| cs.subst);
 
         // Extract answers and merge them into `subst`. Stop once we have
@@ -71,6 +100,10 @@ impl<'me> context::AggregateOps<SlgContext> for SlgContextOps<'me> {
     }
 }
 
+fn is_subset<T: PartialEq>(a: &[T], b: &[T]) -> bool {
+    a.iter().all(|x| b.contains(x))
+}
+
 /// Given a current substitution used as guidance for `root_goal`, and
 /// a new possible answer to `root_goal`, returns a new set of
 /// guidance that encompasses both of them. This is often more general
@@ -112,6 +145,14 @@ fn merge_into_guidance(
                     // creating guidance here anyway.
                     return infer.new_variable(universe).to_lifetime().cast();
                 }
+                ParameterKind::Const(c) => {
+                    let c1 = value1.assert_const_ref();
+                    let mut aggr = AntiUnifier {
+                        infer: &mut infer,
+                        universe,
+                    };
+                    return aggr.aggregate_consts(c, c1).cast();
+                }
             };
 
             let ty1 = value1.assert_ty_ref();
@@ -151,6 +192,12 @@ fn is_trivial(subst: &Canonical<Substitution<ChalkIr>>) -> bool {
             // And no lifetime mappings. (This is too strict, but we never
             // product substs with lifetimes.)
             ParameterKind::Lifetime(_) => false,
+
+            // Consts, like types, must be mapped to distinct variables.
+            ParameterKind::Const(c) => match c {
+                Const::BoundVar(depth) => *depth == index,
+                _ => false,
+            },
         })
 }
 
@@ -290,7 +337,12 @@ impl<'infer> AntiUnifier<'infer> {
             (ParameterKind::Lifetime(l1), ParameterKind::Lifetime(l2)) => {
                 self.aggregate_lifetimes(l1, l2).cast()
             }
-            (ParameterKind::Ty(_), _) | (ParameterKind::Lifetime(_), _) => {
+            (ParameterKind::Const(c1), ParameterKind::Const(c2)) => {
+                self.aggregate_consts(c1, c2).cast()
+            }
+            (ParameterKind::Ty(_), _)
+            | (ParameterKind::Lifetime(_), _)
+            | (ParameterKind::Const(_), _) => {
                 panic!("mismatched parameter kinds: p1={:?} p2={:?}", p1, p2)
             }
         }
@@ -320,6 +372,24 @@ impl<'infer> AntiUnifier<'infer> {
         }
     }
 
+    fn aggregate_consts(&mut self, c1: &Const<ChalkIr>, c2: &Const<ChalkIr>) -> Const<ChalkIr> {
+        match (c1, c2) {
+            (Const::InferenceVar(_), _) | (_, Const::InferenceVar(_)) => self.new_const_variable(),
+
+            (Const::BoundVar(_), _) | (_, Const::BoundVar(_)) => self.new_const_variable(),
+
+            (Const::Placeholder(_), _) | (Const::Value(_), _) => {
+                if c1 == c2 {
+                    *c1
+                } else {
+                    self.new_const_variable()
+                }
+            }
+
+            (Const::Phantom(..), _) => unreachable!(),
+        }
+    }
+
     fn new_variable(&mut self) -> Ty<ChalkIr> {
         self.infer.new_variable(self.universe).to_ty()
     }
@@ -327,6 +397,10 @@ impl<'infer> AntiUnifier<'infer> {
     fn new_lifetime_variable(&mut self) -> Lifetime<ChalkIr> {
         self.infer.new_variable(self.universe).to_lifetime()
     }
+
+    fn new_const_variable(&mut self) -> Const<ChalkIr> {
+        self.infer.new_variable(self.universe).to_const()
+    }
 }
 
 /// Test the equivalent of `Vec<i32>` vs `Vec<u32>`
@@ -0,0 +1,171 @@
+//! Simplification of the region constraints of an answer.
+//!
+//! Unification and program clauses produce region constraints one at a
+//! time, so the constraints of an answer are often redundant: the same
+//! constraint may appear several times, equalities may be chained through
+//! inference variables that the caller never sees, and so on. Before an
+//! answer is canonicalized, we reduce its constraints to a minimal set in
+//! a canonical order, so that equivalent answers compare equal.
+
+use crate::infer::InferenceTable;
+use chalk_ir::family::ChalkIr;
+use chalk_ir::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Simplifies the `constraints` of an answer whose substitution is
+/// `subst`. Within each environment, this:
+///
+/// - merges the `LifetimeEq` constraints into equivalence classes, each
+///   of which is reported as `'x == 'r` for every member `'x` other than
+///   the class's representative `'r`;
+/// - replaces the lifetimes of outlives constraints with the
+///   representative of their class;
+/// - drops trivial constraints like `'a == 'a` or `'a: 'a`;
+/// - drops constraints that only involve lifetimes local to the goal,
+///   i.e. inference variables that do not appear in `subst`, since the
+///   caller has no way to name (or check) them.
+///
+/// The result is deduplicated and sorted.
+pub(super) fn simplify_constraints(
+    infer: &mut InferenceTable,
+    subst: &Substitution<ChalkIr>,
+    constraints: Vec<InEnvironment<Constraint<ChalkIr>>>,
+) -> Vec<InEnvironment<Constraint<ChalkIr>>> {
+    let escaping: BTreeSet<Lifetime<ChalkIr>> = infer
+        .canonicalize(subst)
+        .free_vars
+        .into_iter()
+        .filter_map(|var| match var {
+            ParameterKind::Lifetime(var) => Some(var.to_lifetime()),
+            _ => None,
+        })
+        .collect();
+    let is_local = |lifetime: &Lifetime<ChalkIr>| {
+        lifetime.inference_var().is_some() && !escaping.contains(lifetime)
+    };
+
+    let mut by_environment: BTreeMap<Environment<ChalkIr>, Vec<Constraint<ChalkIr>>> =
+        BTreeMap::new();
+    for InEnvironment { environment, goal } in constraints {
+        let constraint = match goal {
+            Constraint::LifetimeEq(a, b) => {
+                Constraint::LifetimeEq(infer.resolve_lifetime(&a), infer.resolve_lifetime(&b))
+            }
+            Constraint::LifetimeOutlives(a, b) => {
+                Constraint::LifetimeOutlives(infer.resolve_lifetime(&a), infer.resolve_lifetime(&b))
+            }
+            Constraint::TypeOutlives(ty, lifetime) => Constraint::TypeOutlives(
+                infer.normalize_deep(&ty),
+                infer.resolve_lifetime(&lifetime),
+            ),
+        };
+        by_environment
+            .entry(environment)
+            .or_default()
+            .push(constraint);
+    }
+
+    let mut simplified = BTreeSet::new();
+    for (environment, constraints) in by_environment {
+        let mut classes = EquivalenceClasses::default();
+        for constraint in &constraints {
+            if let Constraint::LifetimeEq(a, b) = constraint {
+                classes.union(a, b);
+            }
+        }
+
+        let representatives = classes.representatives(is_local);
+        let representative =
+            |lifetime: &Lifetime<ChalkIr>| *representatives.get(lifetime).unwrap_or(lifetime);
+
+        let mut push = |goal| {
+            simplified.insert(InEnvironment::new(&environment, goal));
+        };
+        for (member, rep) in &representatives {
+            if member != rep && !is_local(member) {
+                push(Constraint::LifetimeEq(*member, *rep));
+            }
+        }
+        for constraint in constraints {
+            match constraint {
+                Constraint::LifetimeEq(..) => {}
+                Constraint::LifetimeOutlives(a, b) => {
+                    let (a, b) = (representative(&a), representative(&b));
+                    if a != b && !(is_local(&a) && is_local(&b)) {
+                        push(Constraint::LifetimeOutlives(a, b));
+                    }
+                }
+                Constraint::TypeOutlives(ty, lifetime) => {
+                    push(Constraint::TypeOutlives(ty, representative(&lifetime)));
+                }
+            }
+        }
+    }
+
+    simplified.into_iter().collect()
+}
+
+/// A small union-find over the lifetimes related by `LifetimeEq`
+/// constraints.
+#[derive(Default)]
+struct EquivalenceClasses {
+    indices: BTreeMap<Lifetime<ChalkIr>, usize>,
+    lifetimes: Vec<Lifetime<ChalkIr>>,
+    parents: Vec<usize>,
+}
+
+impl EquivalenceClasses {
+    fn index(&mut self, lifetime: &Lifetime<ChalkIr>) -> usize {
+        if let Some(&index) = self.indices.get(lifetime) {
+            return index;
+        }
+        let index = self.lifetimes.len();
+        self.indices.insert(*lifetime, index);
+        self.lifetimes.push(*lifetime);
+        self.parents.push(index);
+        index
+    }
+
+    fn find(&mut self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    fn union(&mut self, a: &Lifetime<ChalkIr>, b: &Lifetime<ChalkIr>) {
+        let (a, b) = (self.index(a), self.index(b));
+        let (a, b) = (self.find(a), self.find(b));
+        self.parents[a] = b;
+    }
+
+    /// Maps each lifetime to the representative of its class. We prefer
+    /// placeholders, which the caller can name, over inference variables
+    /// from the substitution, and those over local variables; ties are
+    /// broken by picking the least lifetime.
+    fn representatives(
+        &mut self,
+        is_local: impl Fn(&Lifetime<ChalkIr>) -> bool,
+    ) -> BTreeMap<Lifetime<ChalkIr>, Lifetime<ChalkIr>> {
+        let mut best: BTreeMap<usize, Lifetime<ChalkIr>> = BTreeMap::new();
+        for index in 0..self.lifetimes.len() {
+            let root = self.find(index);
+            let lifetime = &self.lifetimes[index];
+            let key = |l: &Lifetime<ChalkIr>| (is_local(l), l.inference_var().is_some(), *l);
+            match best.get(&root) {
+                Some(current) if key(current) <= key(lifetime) => {}
+                _ => {
+                    best.insert(root, *lifetime);
+                }
+            }
+        }
+
+        (0..self.lifetimes.len())
+            .map(|index| {
+                let root = self.find(index);
+                (self.lifetimes[index], best[&root])
+            })
+            .collect()
+    }
+}
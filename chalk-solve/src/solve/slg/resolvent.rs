@@ -84,6 +84,7 @@ impl context::ResolventOps<SlgContext> for TruncatingInferenceTable {
         let ProgramClauseImplication {
             consequence,
             conditions,
+            constraints,
         } = match clause {
             ProgramClause::Implies(implication) => implication.clone(),
             ProgramClause::ForAll(implication) => {
@@ -92,6 +93,7 @@ impl context::ResolventOps<SlgContext> for TruncatingInferenceTable {
         };
         debug!("consequence = {:?}", consequence);
         debug!("conditions = {:?}", conditions);
+        debug!("constraints = {:?}", constraints);
 
         // Unify the selected literal Li with C'.
         let unification_result = self.infer.unify(environment, goal, &consequence)?;
@@ -109,6 +111,14 @@ impl context::ResolventOps<SlgContext> for TruncatingInferenceTable {
         // Add the subgoals/region-constraints that unification gave us.
         slg::into_ex_clause(unification_result, &mut ex_clause);
 
+        // Add the region constraints that the program clause itself
+        // requires; these must hold in the environment of the goal.
+        ex_clause.constraints.extend(
+            constraints
+                .into_iter()
+                .map(|c| InEnvironment::new(environment, c)),
+        );
+
         // Add the `conditions` from the program clause into the result too.
         ex_clause
             .subgoals
@@ -276,7 +286,7 @@ impl<'t> AnswerSubstitutor<'t> {
     fn unify_free_answer_var(
         &mut self,
         answer_depth: usize,
-        pending: ParameterKind<&Ty<ChalkIr>, &Lifetime<ChalkIr>>,
+        pending: ParameterKind<&Ty<ChalkIr>, &Lifetime<ChalkIr>, &Const<ChalkIr>>,
     ) -> Fallible<bool> {
         // This variable is bound in the answer, not free, so it
         // doesn't represent a reference into the answer substitution.
@@ -419,6 +429,41 @@ impl<'t> Zipper<ChalkIr> for AnswerSubstitutor<'t> {
         }
     }
 
+    fn zip_consts(&mut self, answer: &Const<ChalkIr>, pending: &Const<ChalkIr>) -> Fallible<()> {
+        if let Some(pending) = self.table.normalize_const(pending) {
+            return Zip::zip_with(self, answer, &pending);
+        }
+
+        if let Const::BoundVar(answer_depth) = answer {
+            if self.unify_free_answer_var(*answer_depth, ParameterKind::Const(pending))? {
+                return Ok(());
+            }
+        }
+
+        match (answer, pending) {
+            (Const::BoundVar(answer_depth), Const::BoundVar(pending_depth)) => {
+                self.assert_matching_vars(*answer_depth, *pending_depth)
+            }
+
+            (Const::Placeholder(_), Const::Placeholder(_)) | (Const::Value(_), Const::Value(_)) => {
+                assert_eq!(answer, pending);
+                Ok(())
+            }
+
+            (Const::InferenceVar(_), _) | (_, Const::InferenceVar(_)) => panic!(
+                "unexpected inference var in answer `{:?}` or pending goal `{:?}`",
+                answer, pending,
+            ),
+
+            (Const::BoundVar(_), _) | (Const::Placeholder(_), _) | (Const::Value(_), _) => panic!(
+                "structural mismatch between answer `{:?}` and pending goal `{:?}`",
+                answer, pending,
+            ),
+
+            (Const::Phantom(..), _) => unreachable!(),
+        }
+    }
+
     fn zip_binders<T>(&mut self, answer: &Binders<T>, pending: &Binders<T>) -> Fallible<()>
     where
         T: Zip<ChalkIr> + Fold<ChalkIr, Result = T>,
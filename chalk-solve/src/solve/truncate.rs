@@ -114,6 +114,14 @@ impl<'infer> TypeFolder<ChalkIr> for Truncater<'infer> {
     ) -> Fallible<Lifetime<ChalkIr>> {
         fold::super_fold_lifetime(self, lifetime, binders)
     }
+
+    fn fold_const(
+        &mut self,
+        constant: &Const<ChalkIr>,
+        binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        fold::super_fold_const(self, constant, binders)
+    }
 }
 
 impl<'infer> DefaultFreeVarFolder for Truncater<'infer> {}
@@ -1,5 +1,7 @@
 use crate::RustIrDatabase;
 use chalk_ir::family::ChalkIr;
+use chalk_ir::fold::shift::Shift;
+use chalk_ir::fold::Subst;
 use chalk_ir::*;
 use chalk_rust_ir::*;
 use std::sync::Arc;
@@ -161,6 +163,83 @@ pub trait Split: RustIrDatabase {
 
         (impl_parameters, projection)
     }
+
+    /// Returns the values of the associated types in the impl
+    /// `impl_id`: the ones it defines, followed by the defaults from
+    /// the trait for the associated types it does not define.
+    ///
+    /// # Example
+    ///
+    /// ```ignore (example)
+    /// trait Foo<T> {
+    ///     type Item<'a> = Vec<&'a T>;
+    /// }
+    ///
+    /// impl<U> Foo<Box<U>> for Bar { }
+    /// ```
+    ///
+    /// Here the impl gets the value `type Item<'a> = Vec<&'a Box<U>>;`.
+    fn impl_associated_ty_values(&self, impl_id: ImplId) -> Vec<Arc<AssociatedTyValue>> {
+        let impl_datum = self.impl_datum(impl_id);
+        let mut values: Vec<_> = impl_datum
+            .associated_ty_value_ids
+            .iter()
+            .map(|&atv_id| self.associated_ty_value(atv_id))
+            .collect();
+
+        let trait_ref = &impl_datum.binders.value.trait_ref;
+        let trait_datum = self.trait_datum(trait_ref.trait_id);
+        for &associated_ty_id in &trait_datum.associated_ty_ids {
+            if values
+                .iter()
+                .any(|value| value.associated_ty_id == associated_ty_id)
+            {
+                continue;
+            }
+
+            let associated_ty = self.associated_ty_data(associated_ty_id);
+            let default = match &associated_ty.binders.value.default {
+                Some(default) => default,
+                None => continue,
+            };
+
+            // The default is written in terms of the parameters of the
+            // associated type (`'a`) and of the trait (`Self`, `T`); we
+            // keep the former, and replace the latter with the
+            // parameters of the trait ref of the impl (`Bar`, `Box<U>`).
+            let num_addl_parameters = associated_ty.binders.len() - trait_datum.binders.len();
+            let addl_binders = &associated_ty.binders.binders[..num_addl_parameters];
+            let parameters: Vec<_> = addl_binders
+                .iter()
+                .zip(0..)
+                .map(|p| p.to_parameter())
+                .chain(
+                    trait_ref
+                        .parameters
+                        .iter()
+                        .map(|p| p.shifted_in(num_addl_parameters)),
+                )
+                .collect();
+
+            values.push(Arc::new(AssociatedTyValue {
+                impl_id,
+                associated_ty_id,
+                defaultness: Defaultness::Final,
+                value: Binders {
+                    binders: addl_binders
+                        .iter()
+                        .chain(&impl_datum.binders.binders)
+                        .cloned()
+                        .collect(),
+                    value: AssociatedTyValueBound {
+                        ty: Subst::apply(&parameters, default),
+                    },
+                },
+            }));
+        }
+
+        values
+    }
 }
 
 impl<DB: RustIrDatabase + ?Sized> Split for DB {}
@@ -0,0 +1,287 @@
+//! Computes the variance of the parameters of types, which determines how
+//! two applications of the same type are related by subtyping (see
+//! `SubtypeGoal`).
+
+use crate::RustIrDatabase;
+use chalk_engine::fallible::Fallible;
+use chalk_ir::family::ChalkIr;
+use chalk_ir::fold::{
+    DefaultInferenceFolder, DefaultPlaceholderFolder, DefaultTypeFolder, Fold, FreeVarFolder,
+};
+use chalk_ir::*;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Computes the variance of each parameter of the structs and enums
+/// `adt_ids` (and of any other struct or enum they contain) from the
+/// types of their fields; for an enum, these are the fields of all of its
+/// variants. A parameter that only appears in covariant (contravariant)
+/// positions of the fields is covariant (contravariant); one that appears
+/// in both, or in a position whose variance we do not know, such as the
+/// parameters of an associated type projection, is invariant.
+///
+/// Parameters that are not used by any field are also invariant, so that
+/// e.g. `struct Vec<T> { }` is related exactly as it is by unification.
+///
+/// This is meant to be computed once for a whole program; the results
+/// are then looked up through `RustIrDatabase::adt_variances`.
+pub fn compute_adt_variances(
+    db: &dyn RustIrDatabase,
+    adt_ids: impl IntoIterator<Item = TypeKindId>,
+) -> BTreeMap<TypeKindId, Arc<Vec<Variance>>> {
+    let mut solver = VarianceSolver {
+        db,
+        variances: BTreeMap::new(),
+    };
+    for adt_id in adt_ids {
+        solver.variances_of(adt_id);
+    }
+    solver.solve();
+    solver
+        .variances
+        .into_iter()
+        .map(|(adt_id, variances)| {
+            let variances = variances
+                .into_iter()
+                .map(|variance| variance.unwrap_or(Variance::Invariant))
+                .collect();
+            (adt_id, Arc::new(variances))
+        })
+        .collect()
+}
+
+/// Returns the variances of the `num_parameters` parameters of the type
+/// `name`.
+pub(crate) fn type_variances(
+    db: &dyn RustIrDatabase,
+    name: TypeName,
+    num_parameters: usize,
+) -> Arc<Vec<Variance>> {
+    match name {
+        TypeName::TypeKindId(adt_id @ TypeKindId::StructId(_))
+        | TypeName::TypeKindId(adt_id @ TypeKindId::EnumId(_)) => db.adt_variances(adt_id),
+        _ => Arc::new(
+            builtin_variances(name, num_parameters)
+                .unwrap_or_else(|| vec![Variance::Invariant; num_parameters]),
+        ),
+    }
+}
+
+/// The variances of the parameters of the built-in types; `None` for
+/// types that are not built in.
+fn builtin_variances(name: TypeName, num_parameters: usize) -> Option<Vec<Variance>> {
+    use Variance::*;
+
+    match name {
+        TypeName::Ref(Mutability::Not) => Some(vec![Covariant, Covariant]),
+        TypeName::Ref(Mutability::Mut) => Some(vec![Covariant, Invariant]),
+        TypeName::Raw(Mutability::Not) | TypeName::Slice => Some(vec![Covariant]),
+        TypeName::Raw(Mutability::Mut) => Some(vec![Invariant]),
+        TypeName::Array => Some(vec![Covariant, Invariant]),
+        TypeName::Tuple(_) => Some(vec![Covariant; num_parameters]),
+        TypeName::FnPtr(arity) => {
+            let mut variances = vec![Contravariant; arity];
+            variances.push(Covariant);
+            Some(variances)
+        }
+        TypeName::Str | TypeName::Scalar(_) | TypeName::Never => Some(vec![]),
+        TypeName::TypeKindId(_)
+        | TypeName::Placeholder(_)
+        | TypeName::AssociatedType(_)
+        | TypeName::Error => None,
+    }
+}
+
+/// Computes struct and enum variances by iterating to a fixed point, since
+/// they may (directly or indirectly) contain themselves. While iterating,
+/// a parameter's variance is `None` until we find a use of it.
+struct VarianceSolver<'db> {
+    db: &'db dyn RustIrDatabase,
+    /// Keyed by the `StructId` or `EnumId`.
+    variances: BTreeMap<TypeKindId, Vec<Option<Variance>>>,
+}
+
+impl VarianceSolver<'_> {
+    fn solve(&mut self) {
+        loop {
+            let num_adts = self.variances.len();
+            let mut changed = false;
+
+            let adt_ids: Vec<_> = self.variances.keys().cloned().collect();
+            for adt_id in adt_ids {
+                let fields = self.fields(adt_id);
+                let mut variances = vec![None; fields.binders.len()];
+                for field in &fields.value {
+                    self.add_ty(field, Variance::Covariant, 0, &mut variances);
+                }
+
+                if self.variances[&adt_id] != variances {
+                    self.variances.insert(adt_id, variances);
+                    changed = true;
+                }
+            }
+
+            if !changed && self.variances.len() == num_adts {
+                break;
+            }
+        }
+    }
+
+    /// The types of the fields of the struct or enum `adt_id`, under its
+    /// parameters.
+    fn fields(&self, adt_id: TypeKindId) -> Binders<Vec<Ty<ChalkIr>>> {
+        match adt_id {
+            TypeKindId::StructId(struct_id) => {
+                let datum = self.db.struct_datum(struct_id);
+                datum.binders.map_ref(|bound| bound.fields.clone())
+            }
+            TypeKindId::EnumId(enum_id) => {
+                let datum = self.db.enum_datum(enum_id);
+                datum
+                    .binders
+                    .map_ref(|bound| bound.fields().cloned().collect())
+            }
+            _ => panic!("no variances to compute for {:?}", adt_id),
+        }
+    }
+
+    /// The variances of the struct or enum computed so far; those we have
+    /// not seen before start out with no uses of their parameters.
+    fn variances_of(&mut self, adt_id: TypeKindId) -> Vec<Option<Variance>> {
+        if !self.variances.contains_key(&adt_id) {
+            let num_parameters = self.fields(adt_id).binders.len();
+            self.variances.insert(adt_id, vec![None; num_parameters]);
+        }
+        self.variances[&adt_id].clone()
+    }
+
+    /// Records the uses of parameters in `ty`, which appears in a
+    /// position with variance `variance` under `binders` binders of its
+    /// own (e.g. from `for<'a> fn(&'a T)`).
+    fn add_ty(
+        &mut self,
+        ty: &Ty<ChalkIr>,
+        variance: Variance,
+        binders: usize,
+        variances: &mut [Option<Variance>],
+    ) {
+        match ty {
+            Ty::BoundVar(depth) => add_use(variances, *depth, binders, variance),
+            Ty::Apply(apply) => {
+                let num_parameters = apply.parameters.len();
+                let parameter_variances = match apply.name {
+                    TypeName::TypeKindId(adt_id @ TypeKindId::StructId(_))
+                    | TypeName::TypeKindId(adt_id @ TypeKindId::EnumId(_)) => {
+                        self.variances_of(adt_id)
+                    }
+                    name => builtin_variances(name, num_parameters)
+                        .unwrap_or_else(|| vec![Variance::Invariant; num_parameters])
+                        .into_iter()
+                        .map(Some)
+                        .collect(),
+                };
+
+                for (parameter, parameter_variance) in
+                    apply.parameters.iter().zip(parameter_variances)
+                {
+                    if let Some(parameter_variance) = parameter_variance {
+                        self.add_parameter(
+                            parameter,
+                            variance.xform(parameter_variance),
+                            binders,
+                            variances,
+                        );
+                    }
+                }
+            }
+            Ty::ForAll(quantified_ty) => self.add_ty(
+                &quantified_ty.ty,
+                variance,
+                binders + quantified_ty.num_binders,
+                variances,
+            ),
+            Ty::Projection(_) | Ty::Dyn(_) | Ty::Opaque(_) => {
+                add_invariant_uses(ty, binders, variances)
+            }
+            Ty::InferenceVar(_) => {}
+        }
+    }
+
+    fn add_parameter(
+        &mut self,
+        parameter: &Parameter<ChalkIr>,
+        variance: Variance,
+        binders: usize,
+        variances: &mut [Option<Variance>],
+    ) {
+        match &parameter.0 {
+            ParameterKind::Ty(ty) => self.add_ty(ty, variance, binders, variances),
+            ParameterKind::Lifetime(Lifetime::BoundVar(depth)) => {
+                add_use(variances, *depth, binders, variance)
+            }
+            ParameterKind::Lifetime(_) => {}
+            ParameterKind::Const(constant) => add_invariant_uses(constant, binders, variances),
+        }
+    }
+}
+
+/// Records a use, with variance `variance`, of the variable with depth
+/// `depth` under `binders` binders, unless that variable is bound by one
+/// of those binders.
+fn add_use(variances: &mut [Option<Variance>], depth: usize, binders: usize, variance: Variance) {
+    if depth < binders {
+        return;
+    }
+
+    let slot = &mut variances[depth - binders];
+    *slot = Some(match *slot {
+        None => variance,
+        Some(old) if old == variance => variance,
+        Some(_) => Variance::Invariant,
+    });
+}
+
+/// Records an invariant use of every parameter that appears in `value`.
+fn add_invariant_uses<T: Fold<ChalkIr>>(
+    value: &T,
+    binders: usize,
+    variances: &mut [Option<Variance>],
+) {
+    let mut collector = FreeVarCollector { depths: vec![] };
+    value.fold_with(&mut collector, 0).unwrap();
+    for depth in collector.depths {
+        add_use(variances, depth, binders, Variance::Invariant);
+    }
+}
+
+/// Collects the depths of the free variables of a value.
+struct FreeVarCollector {
+    depths: Vec<usize>,
+}
+
+impl DefaultTypeFolder for FreeVarCollector {}
+
+impl DefaultPlaceholderFolder for FreeVarCollector {}
+
+impl DefaultInferenceFolder for FreeVarCollector {}
+
+impl FreeVarFolder<ChalkIr> for FreeVarCollector {
+    fn fold_free_var_ty(&mut self, depth: usize, binders: usize) -> Fallible<Ty<ChalkIr>> {
+        self.depths.push(depth);
+        Ok(Ty::BoundVar(depth + binders))
+    }
+
+    fn fold_free_var_lifetime(
+        &mut self,
+        depth: usize,
+        binders: usize,
+    ) -> Fallible<Lifetime<ChalkIr>> {
+        self.depths.push(depth);
+        Ok(Lifetime::BoundVar(depth + binders))
+    }
+
+    fn fold_free_var_const(&mut self, depth: usize, binders: usize) -> Fallible<Const<ChalkIr>> {
+        self.depths.push(depth);
+        Ok(Const::BoundVar(depth + binders))
+    }
+}
@@ -1,11 +1,17 @@
 use std::fmt;
 
 use crate::ext::*;
+use crate::object_safety;
 use crate::solve::SolverChoice;
 use crate::split::Split;
 use crate::RustIrDatabase;
+use chalk_engine::fallible::Fallible;
 use chalk_ir::cast::*;
 use chalk_ir::family::ChalkIr;
+use chalk_ir::fold::{
+    super_fold_const, super_fold_lifetime, super_fold_ty, DefaultFreeVarFolder,
+    DefaultInferenceFolder, DefaultPlaceholderFolder, Fold, TypeFolder,
+};
 use chalk_ir::*;
 use chalk_rust_ir::*;
 use itertools::Itertools;
@@ -14,6 +20,11 @@ use itertools::Itertools;
 pub enum WfError {
     IllFormedTypeDecl(chalk_ir::Identifier),
     IllFormedTraitImpl(chalk_ir::Identifier),
+    IllFormedTraitDecl(chalk_ir::Identifier),
+    /// The hidden type of a named opaque type does not meet its bounds.
+    IllFormedOpaqueTyDecl(chalk_ir::Identifier),
+    /// A trait object type `dyn Trait` names a trait that is not object safe.
+    ObjectUnsafeTrait(chalk_ir::Identifier),
 }
 
 impl fmt::Display for WfError {
@@ -29,6 +40,19 @@ impl fmt::Display for WfError {
                 "trait impl for {:?} does not meet well-formedness requirements",
                 id
             ),
+            WfError::IllFormedTraitDecl(id) => write!(
+                f,
+                "trait declaration {:?} does not meet well-formedness requirements",
+                id
+            ),
+            WfError::IllFormedOpaqueTyDecl(id) => write!(
+                f,
+                "hidden type of opaque type {:?} does not meet its bounds",
+                id
+            ),
+            WfError::ObjectUnsafeTrait(id) => {
+                write!(f, "the trait {:?} cannot be made into an object", id)
+            }
         }
     }
 }
@@ -45,7 +69,7 @@ trait FoldInputTypes {
     fn fold(&self, accumulator: &mut Vec<Ty<ChalkIr>>);
 }
 
-impl<T: FoldInputTypes> FoldInputTypes for Vec<T> {
+impl<T: FoldInputTypes> FoldInputTypes for [T] {
     fn fold(&self, accumulator: &mut Vec<Ty<ChalkIr>>) {
         for f in self {
             f.fold(accumulator);
@@ -53,6 +77,12 @@ impl<T: FoldInputTypes> FoldInputTypes for Vec<T> {
     }
 }
 
+impl<T: FoldInputTypes> FoldInputTypes for Vec<T> {
+    fn fold(&self, accumulator: &mut Vec<Ty<ChalkIr>>) {
+        self[..].fold(accumulator);
+    }
+}
+
 impl FoldInputTypes for Parameter<ChalkIr> {
     fn fold(&self, accumulator: &mut Vec<Ty<ChalkIr>>) {
         if let ParameterKind::Ty(ty) = &self.0 {
@@ -110,6 +140,21 @@ impl FoldInputTypes for WhereClause<ChalkIr> {
         match self {
             WhereClause::Implemented(tr) => tr.fold(accumulator),
             WhereClause::ProjectionEq(p) => p.fold(accumulator),
+            WhereClause::LifetimeOutlives(..) => {}
+            WhereClause::TypeOutlives(o) => o.ty.fold(accumulator),
+        }
+    }
+}
+
+impl FoldInputTypes for InlineBound {
+    fn fold(&self, accumulator: &mut Vec<Ty<ChalkIr>>) {
+        match self {
+            InlineBound::TraitBound(b) => b.args_no_self.fold(accumulator),
+            InlineBound::ProjectionEqBound(b) => {
+                b.trait_bound.args_no_self.fold(accumulator);
+                b.parameters.fold(accumulator);
+                b.value.fold(accumulator);
+            }
         }
     }
 }
@@ -129,34 +174,75 @@ where
         Self { db, solver_choice }
     }
 
+    /// Every field of a struct but the last must be `Sized`.
     pub fn verify_struct_decl(&self, struct_id: StructId) -> Result<(), WfError> {
         let struct_datum = self.db.struct_datum(struct_id);
+        let Binders { binders, value } = &struct_datum.binders;
+        let sized_fields = &value.fields[..value.fields.len().saturating_sub(1)];
+        self.verify_adt_decl(
+            struct_id.into(),
+            binders,
+            &value.fields,
+            sized_fields,
+            &value.where_clauses,
+        )
+    }
 
-        // We retrieve all the input types of the struct fields.
+    /// Like `verify_struct_decl`, but the fields of every variant must
+    /// be well-formed, and all of them must be `Sized`.
+    pub fn verify_enum_decl(&self, enum_id: EnumId) -> Result<(), WfError> {
+        let enum_datum = self.db.enum_datum(enum_id);
+        let Binders { binders, value } = &enum_datum.binders;
+        let fields: Vec<_> = value.fields().cloned().collect();
+        self.verify_adt_decl(
+            enum_id.into(),
+            binders,
+            &fields,
+            &fields,
+            &value.where_clauses,
+        )
+    }
+
+    fn verify_adt_decl(
+        &self,
+        type_kind_id: TypeKindId,
+        binders: &[ParameterKind<()>],
+        fields: &[Ty<ChalkIr>],
+        sized_fields: &[Ty<ChalkIr>],
+        where_clauses: &[QuantifiedWhereClause<ChalkIr>],
+    ) -> Result<(), WfError> {
+        // We retrieve all the input types of the fields.
         let mut input_types = Vec::new();
-        struct_datum.binders.value.fields.fold(&mut input_types);
-        struct_datum
-            .binders
-            .value
-            .where_clauses
-            .fold(&mut input_types);
+        fields.fold(&mut input_types);
+        where_clauses.fold(&mut input_types);
+        self.verify_trait_objects(fields)?;
+        self.verify_trait_objects(where_clauses)?;
 
-        if input_types.is_empty() {
-            return Ok(());
-        }
+        // If the program has no `Sized` trait, there is nothing to check.
+        let sized_goals = self
+            .db
+            .well_known_trait_id(LangItem::Sized)
+            .into_iter()
+            .flat_map(|trait_id| {
+                sized_fields.iter().map(move |ty| TraitRef {
+                    trait_id,
+                    parameters: vec![ty.clone().cast()],
+                })
+            })
+            .casted();
 
         let goals = input_types
             .into_iter()
             .map(|ty| DomainGoal::WellFormed(WellFormed::Ty(ty)))
-            .casted();
-        let goal = goals
-            .fold1(|goal, leaf| Goal::And(Box::new(goal), Box::new(leaf)))
-            .expect("at least one goal");
-
-        let hypotheses = struct_datum
-            .binders
-            .value
-            .where_clauses
+            .casted()
+            .chain(sized_goals);
+        let goal: Goal<ChalkIr> =
+            match goals.fold1(|goal, leaf| Goal::And(Box::new(goal), Box::new(leaf))) {
+                Some(goal) => goal,
+                None => return Ok(()),
+            };
+
+        let hypotheses = where_clauses
             .iter()
             .cloned()
             .map(|wc| wc.map(|bound| bound.into_from_env_goal()))
@@ -164,9 +250,9 @@ where
             .collect();
 
         // We ask that the above input types are well-formed provided that all the where-clauses
-        // on the struct definition hold.
+        // on the type definition hold.
         let goal = Goal::Implies(hypotheses, Box::new(goal))
-            .quantify(QuantifierKind::ForAll, struct_datum.binders.binders.clone());
+            .quantify(QuantifierKind::ForAll, binders.to_vec());
 
         let is_legal = match self
             .solver_choice
@@ -178,13 +264,193 @@ where
         };
 
         if !is_legal {
-            let name = self.db.type_name(struct_id.into());
+            let name = self.db.type_name(type_kind_id);
             Err(WfError::IllFormedTypeDecl(name))
         } else {
             Ok(())
         }
     }
 
+    /// The input types of the where clauses of a trait must be
+    /// well-formed, and so must those of the bounds and where clauses of
+    /// its associated types. For example, in:
+    ///
+    /// ```ignore
+    /// struct Vec<T> where T: Clone { }
+    ///
+    /// trait Foo where Vec<Self>: Bar {
+    ///     type Item<'a>: Baz<Vec<Self>> where Vec<&'a Self>: Baz<Self>;
+    /// }
+    /// ```
+    ///
+    /// we would need to prove `WellFormed(Vec<Self>)`, which does not
+    /// hold unless `Self: Clone`. Inside the trait, we may assume that
+    /// `Self: Foo` and that the where clauses of the trait hold; inside
+    /// the associated type, that its where clauses hold as well.
+    pub fn verify_trait_decl(&self, trait_id: TraitId) -> Result<(), WfError> {
+        let trait_datum = self.db.trait_datum(trait_id);
+        let Binders { binders, value } = &trait_datum.binders;
+
+        let mut goals = self.compute_quantified_wf_goals(&value.where_clauses)?;
+        for &assoc_ty_id in &trait_datum.associated_ty_ids {
+            goals.extend(self.compute_assoc_ty_decl_goal(assoc_ty_id, binders.len())?);
+        }
+
+        let goal = match goals
+            .into_iter()
+            .fold1(|goal, leaf| Goal::And(Box::new(goal), Box::new(leaf)))
+        {
+            Some(goal) => goal,
+            None => return Ok(()),
+        };
+
+        let trait_ref = TraitRef {
+            trait_id,
+            parameters: binders.iter().zip(0..).map(|p| p.to_parameter()).collect(),
+        };
+        let hypotheses = value
+            .where_clauses
+            .iter()
+            .cloned()
+            .map(|wc| wc.map(|bound| bound.into_from_env_goal()))
+            .casted()
+            .chain(Some(DomainGoal::FromEnv(FromEnv::Trait(trait_ref))).cast())
+            .collect();
+
+        let goal = Goal::Implies(hypotheses, Box::new(goal))
+            .quantify(QuantifierKind::ForAll, binders.clone());
+
+        debug!("WF trait decl goal: {:?}", goal);
+
+        let is_legal = match self
+            .solver_choice
+            .into_solver()
+            .solve(self.db, &goal.into_closed_goal())
+        {
+            Some(sol) => sol.is_unique(),
+            None => false,
+        };
+
+        if is_legal {
+            Ok(())
+        } else {
+            let name = self.db.type_name(trait_id.into());
+            Err(WfError::IllFormedTraitDecl(name))
+        }
+    }
+
+    /// The part of the goal of `verify_trait_decl` for one associated
+    /// type, like `forall<'a> { if (FromEnv(Vec<&'a Self>: Baz<Self>)) {
+    /// WellFormed(Vec<Self>), WellFormed(Vec<&'a Self>) } }` in our
+    /// example. The binders of an associated type come before those of
+    /// its trait, so this goal is meant to be nested in the `forall` of
+    /// the latter, which has `trait_binders_len` binders.
+    fn compute_assoc_ty_decl_goal(
+        &self,
+        assoc_ty_id: TypeId,
+        trait_binders_len: usize,
+    ) -> Result<Option<Goal<ChalkIr>>, WfError> {
+        let assoc_ty_datum = self.db.associated_ty_data(assoc_ty_id);
+        let Binders { binders, value } = &assoc_ty_datum.binders;
+
+        let mut goals = self.compute_quantified_wf_goals(&value.bounds)?;
+        goals.extend(self.compute_quantified_wf_goals(&value.where_clauses)?);
+
+        let goal = match goals
+            .into_iter()
+            .fold1(|goal, leaf| Goal::And(Box::new(goal), Box::new(leaf)))
+        {
+            Some(goal) => goal,
+            None => return Ok(None),
+        };
+
+        let hypotheses = value
+            .where_clauses
+            .iter()
+            .cloned()
+            .map(|wc| wc.map(|bound| bound.into_from_env_goal()))
+            .casted()
+            .collect();
+
+        let own_binders = binders[..binders.len() - trait_binders_len].to_vec();
+        Ok(Some(
+            Goal::Implies(hypotheses, Box::new(goal)).quantify(QuantifierKind::ForAll, own_binders),
+        ))
+    }
+
+    /// Goals that the input types of each of `items` are well-formed.
+    /// Types under the binders of an item, like the `&'a T` in
+    /// `for<'a> T: Foo<&'a T>`, must be well-formed for all values of
+    /// those binders.
+    fn compute_quantified_wf_goals<T: FoldInputTypes + Fold<ChalkIr>>(
+        &self,
+        items: &[Binders<T>],
+    ) -> Result<Vec<Goal<ChalkIr>>, WfError> {
+        let mut goals = Vec::new();
+        for item in items {
+            let mut input_types = Vec::new();
+            item.value.fold(&mut input_types);
+            self.verify_trait_objects(Some(&item.value))?;
+
+            let goal = input_types
+                .into_iter()
+                .map(|ty| DomainGoal::WellFormed(WellFormed::Ty(ty)))
+                .casted()
+                .fold1(|goal, leaf| Goal::And(Box::new(goal), Box::new(leaf)));
+            goals.extend(goal.map(|goal| match item.binders.len() {
+                0 => goal,
+                _ => goal.quantify(QuantifierKind::ForAll, item.binders.clone()),
+            }));
+        }
+        Ok(goals)
+    }
+
+    /// The hidden type of a named opaque type must meet the bounds of
+    /// the opaque type, for all values of its parameters. For
+    /// `opaque type Foo<T>: Iterator<Item = T> = Bar<T>;`, we prove:
+    ///
+    /// ```notrust
+    /// forall<T> {
+    ///     Implemented(Bar<T>: Iterator),
+    ///     ProjectionEq(<Bar<T> as Iterator>::Item = T)
+    /// }
+    /// ```
+    pub fn verify_opaque_ty_decl(&self, opaque_ty_id: OpaqueTyId) -> Result<(), WfError> {
+        let opaque_ty_datum = self.db.opaque_ty_data(opaque_ty_id);
+        let Binders { binders, value } = &opaque_ty_datum.binders;
+
+        let goal = value
+            .bounds
+            .iter()
+            .flat_map(|qb| qb.into_where_clauses(value.hidden_ty.clone()))
+            .casted()
+            .fold1(|goal, leaf| Goal::And(Box::new(goal), Box::new(leaf)));
+        let goal: Goal<ChalkIr> = match goal {
+            Some(goal) => goal,
+            None => return Ok(()),
+        };
+
+        let goal = goal.quantify(QuantifierKind::ForAll, binders.clone());
+
+        debug!("WF opaque type goal: {:?}", goal);
+
+        let is_legal = match self
+            .solver_choice
+            .into_solver()
+            .solve(self.db, &goal.into_closed_goal())
+        {
+            Some(sol) => sol.is_unique(),
+            None => false,
+        };
+
+        if is_legal {
+            Ok(())
+        } else {
+            let name = self.db.type_name(opaque_ty_id.into());
+            Err(WfError::IllFormedOpaqueTyDecl(name))
+        }
+    }
+
     pub fn verify_trait_impl(&self, impl_id: ImplId) -> Result<(), WfError> {
         let impl_datum = self.db.impl_datum(impl_id);
 
@@ -223,10 +489,22 @@ where
         let trait_ref = &impl_datum.binders.value.trait_ref;
         trait_ref.fold(&mut header_input_types);
 
-        let assoc_ty_goals = impl_datum
-            .associated_ty_value_ids
+        // This includes the defaults of the associated types that the
+        // impl does not define itself, which must meet the bounds in the
+        // trait as if they had been written in the impl.
+        let assoc_ty_values = self.db.impl_associated_ty_values(impl_id);
+
+        self.verify_trait_objects(&impl_datum.binders.value.where_clauses)?;
+        self.verify_trait_objects(Some(trait_ref))?;
+        self.verify_trait_objects(
+            assoc_ty_values
+                .iter()
+                .map(|assoc_ty| &assoc_ty.value.value.ty),
+        )?;
+
+        let assoc_ty_goals = assoc_ty_values
             .iter()
-            .filter_map(|&id| self.compute_assoc_ty_goal(id));
+            .filter_map(|assoc_ty| self.compute_assoc_ty_goal(assoc_ty));
 
         // Things to prove well-formed: input types of the where-clauses, projection types
         // appearing in the header, associated type values, and of course the trait ref.
@@ -283,6 +561,29 @@ where
         }
     }
 
+    /// Every trait object type in `values`, like `dyn Foo`, must be made
+    /// of object safe traits. We check this separately from the
+    /// `WellFormed` goals, which would fail for such types as well, so as
+    /// to report which trait is at fault.
+    fn verify_trait_objects<'v, T: Fold<ChalkIr> + 'v>(
+        &self,
+        values: impl IntoIterator<Item = &'v T>,
+    ) -> Result<(), WfError> {
+        let mut collector = TraitObjectCollector { trait_ids: vec![] };
+        for value in values {
+            value.fold_with(&mut collector, 0).unwrap();
+        }
+
+        let db: &dyn RustIrDatabase = self.db;
+        for trait_id in collector.trait_ids {
+            if !object_safety::is_object_safe(db, trait_id) {
+                let name = self.db.type_name(trait_id.into());
+                return Err(WfError::ObjectUnsafeTrait(name));
+            }
+        }
+        Ok(())
+    }
+
     /// Associated type values are special because they can be parametric (independently of
     /// the impl), so we issue a special goal which is quantified using the binders of the
     /// associated type value, for example in:
@@ -310,9 +611,7 @@ where
     ///     forall<'a> { WellFormed(Box<&'a T>) },
     /// }
     /// ```
-    fn compute_assoc_ty_goal(&self, assoc_ty_id: AssociatedTyValueId) -> Option<Goal<ChalkIr>> {
-        let assoc_ty = &self.db.associated_ty_value(assoc_ty_id);
-
+    fn compute_assoc_ty_goal(&self, assoc_ty: &AssociatedTyValue) -> Option<Goal<ChalkIr>> {
         // The substitutions for the binders on this associated type
         // value. These would be placeholders like `'!a` and `!T`, in
         // our example above.
@@ -364,6 +663,7 @@ where
         let AssociatedTyDatumBound {
             bounds: defn_bounds,
             where_clauses: defn_where_clauses,
+            ..
         } = assoc_ty_datum.binders.substitute(&projection.parameters);
 
         // Check that the `value_ty` meets the bounds from the trait.
@@ -408,3 +708,42 @@ where
         Some(goal.quantify(QuantifierKind::ForAll, value_binders.to_vec()))
     }
 }
+
+/// Collects the traits of the trait object types in a value, like the
+/// `Foo` of `dyn Foo`. Unlike `FoldInputTypes`, this looks inside
+/// higher-ranked types, like `for<'a> fn(&'a dyn Foo)`.
+struct TraitObjectCollector {
+    trait_ids: Vec<TraitId>,
+}
+
+impl TypeFolder<ChalkIr> for TraitObjectCollector {
+    fn fold_ty(&mut self, ty: &Ty<ChalkIr>, binders: usize) -> Fallible<Ty<ChalkIr>> {
+        if let Ty::Dyn(exists_qwcs) = ty {
+            self.trait_ids
+                .extend(object_safety::trait_object_traits(exists_qwcs));
+        }
+        super_fold_ty(self, ty, binders)
+    }
+
+    fn fold_lifetime(
+        &mut self,
+        lifetime: &Lifetime<ChalkIr>,
+        binders: usize,
+    ) -> Fallible<Lifetime<ChalkIr>> {
+        super_fold_lifetime(self, lifetime, binders)
+    }
+
+    fn fold_const(
+        &mut self,
+        constant: &Const<ChalkIr>,
+        binders: usize,
+    ) -> Fallible<Const<ChalkIr>> {
+        super_fold_const(self, constant, binders)
+    }
+}
+
+impl DefaultFreeVarFolder for TraitObjectCollector {}
+
+impl DefaultPlaceholderFolder for TraitObjectCollector {}
+
+impl DefaultInferenceFolder for TraitObjectCollector {}
@@ -3,6 +3,7 @@ extern crate serde_derive;
 
 use std::fs::File;
 use std::io::Read;
+use std::path::Path;
 use std::process::exit;
 
 use chalk_integration::db::ChalkDatabase;
@@ -48,12 +49,12 @@ struct LoadedProgram {
 }
 
 impl LoadedProgram {
-    /// Creates a new Program struct, given a `.chalk` file as a String and
-    /// a [`SolverChoice`].
+    /// Creates a new Program struct, given a `.chalk` file as a String, the
+    /// directory its includes are relative to and a [`SolverChoice`].
     ///
     /// [`SolverChoice`]: struct.solve.SolverChoice.html
-    fn new(text: String, solver_choice: SolverChoice) -> Result<LoadedProgram> {
-        let db = ChalkDatabase::with(&text, solver_choice);
+    fn new(text: String, dir: &Path, solver_choice: SolverChoice) -> Result<LoadedProgram> {
+        let db = ChalkDatabase::with_dir(&text, dir, solver_choice);
         Ok(LoadedProgram { text, db })
     }
 
@@ -205,7 +206,11 @@ fn process(
         help()
     } else if command == "program" {
         // Load a .chalk file via stdin, until EOF is found.
-        *prog = Some(LoadedProgram::new(read_program(rl)?, args.solver_choice())?);
+        *prog = Some(LoadedProgram::new(
+            read_program(rl)?,
+            Path::new(""),
+            args.solver_choice(),
+        )?);
     } else if command.starts_with("load ") {
         // Load a .chalk file.
         let filename = &command["load ".len()..];
@@ -250,7 +255,10 @@ fn process(
 fn load_program(args: &Args, filename: &str) -> Result<LoadedProgram> {
     let mut text = String::new();
     File::open(filename)?.read_to_string(&mut text)?;
-    Ok(LoadedProgram::new(text, args.solver_choice())?)
+    let dir = Path::new(filename)
+        .parent()
+        .unwrap_or_else(|| Path::new(""));
+    Ok(LoadedProgram::new(text, dir, args.solver_choice())?)
 }
 
 /// Print out help for commands in interpreter mode.
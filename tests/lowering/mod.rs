@@ -74,8 +74,6 @@ fn negative_impl() {
                 type Item;
             }
 
-            struct i32 { }
-
             impl !Foo for i32 {
                 type Item = i32;
             }
@@ -93,8 +91,6 @@ fn negative_impl() {
                 type Item;
             }
 
-            struct i32 { }
-
             impl<T> !Foo for T where T: Iterator<Item = i32> { }
         }
     }
@@ -114,6 +110,39 @@ fn invalid_name() {
     }
 }
 
+#[test]
+fn module_paths() {
+    lowering_success! {
+        program {
+            mod a {
+                mod b {
+                    struct Foo { }
+                }
+                struct Bar<T> { }
+            }
+            trait Baz { }
+            impl Baz for a::Bar<a::b::Foo> { }
+        }
+    }
+
+    // Paths are resolved relative to the enclosing modules, so `b::Foo`
+    // is only visible from within `a`.
+    lowering_error! {
+        program {
+            mod a {
+                mod b {
+                    struct Foo { }
+                }
+            }
+            trait Baz { }
+            impl Baz for b::Foo { }
+        }
+        error_msg {
+            "invalid type name `b::Foo`"
+        }
+    }
+}
+
 #[test]
 fn type_parameter() {
     lowering_success! {
@@ -196,6 +225,7 @@ fn atc_accounting() {
             &r#"AssociatedTyValue {
     impl_id: ImplId(2),
     associated_ty_id: (Iterable::Iter),
+    defaultness: Final,
     value: for<lifetime, type> AssociatedTyValueBound {
         ty: Iter<'^0, ^1>
     },
@@ -229,7 +259,6 @@ fn check_parameter_kinds() {
     lowering_error! {
         program {
             struct Foo<'a> { }
-            struct i32 { }
             trait Bar { }
             impl Bar for Foo<i32> { }
         }
@@ -292,6 +321,28 @@ fn check_parameter_kinds() {
             "incorrect parameter kind for trait `IntoTime`: expected lifetime, found type"
         }
     }
+
+    lowering_error! {
+        program {
+            struct Foo<const N: usize> { }
+            trait Bar { }
+            impl Bar for Foo<i32> { }
+        }
+        error_msg {
+            "incorrect parameter kind for `Foo`: expected const, found type"
+        }
+    }
+
+    lowering_error! {
+        program {
+            struct Foo<T> { }
+            trait Bar { }
+            impl Bar for Foo<3> { }
+        }
+        error_msg {
+            "incorrect parameter kind for `Foo`: expected type, found const"
+        }
+    }
 }
 
 #[test]
@@ -343,7 +394,7 @@ fn gat_higher_ranked_bound() {
     lowering_success! {
         program {
             trait Fn<T> {}
-            trait Ref<'a, T> {}
+            struct Ref<'a, T> {}
             trait Sized {}
 
             trait Foo {
@@ -379,9 +430,9 @@ fn duplicate_parameters() {
 
     lowering_error! {
         program {
-            struct fn<'a> { }
+            struct Func<'a> { }
             struct Foo<'a> {
-                a: for<'a> fn<'a>
+                a: for<'a> Func<'a>
             }
         } error_msg {
             "duplicate or shadowed parameters"
@@ -412,6 +463,35 @@ fn upstream_items() {
     }
 }
 
+#[test]
+fn enum_defn() {
+    lowering_success! {
+        program {
+            enum Option<T> {
+                None,
+                Some(T)
+            }
+            enum Foo<'a, T> {
+                A { a: Option<T>, b: T },
+                B(Option<T>, T),
+                C
+            }
+        }
+    }
+
+    lowering_error! {
+        program {
+            enum Option<T> {
+                None,
+                Some(U)
+            }
+        }
+        error_msg {
+            "invalid type name `U`"
+        }
+    }
+}
+
 #[test]
 fn fundamental_multiple_type_parameters() {
     lowering_error! {
@@ -425,3 +505,163 @@ fn fundamental_multiple_type_parameters() {
         }
     }
 }
+
+#[test]
+fn lang_items() {
+    lowering_success! {
+        program {
+            #[lang(sized)] trait Sized { }
+            #[lang(clone)] trait Clone { }
+            #[lang(copy)] trait Copy where Self: Clone { }
+            #[auto] #[lang(send)] trait Send { }
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(copy)] trait Copy { }
+            #[lang(copy)] trait MyCopy { }
+        }
+
+        error_msg {
+            "duplicate lang item `Copy`"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(copyable)] trait Copy { }
+        }
+
+        error_msg {
+            "parse error: User { error: \"unknown lang item\" }"
+        }
+    }
+}
+
+#[test]
+fn lang_item_names_are_identifiers() {
+    lowering_success! {
+        program {
+            #[lang(drop)] trait Drop { }
+            struct send { }
+            struct sync { }
+            struct Foo<copy, clone> {
+                drop: send,
+                sized: sync,
+                fn_once: copy,
+                fn_mut: clone
+            }
+        }
+    }
+}
+
+#[test]
+fn relaxed_bounds() {
+    lowering_success! {
+        program {
+            #[lang(sized)] trait Sized { }
+            struct Box<T> where T: ?Sized { }
+            trait AsRef<T> where T: ?Sized { }
+            impl<T> AsRef<T> for Box<T> where T: ?Sized { }
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait Clone { }
+            struct Box<T> where T: ?Clone { }
+        }
+        error_msg {
+            "relaxed bound `?Clone` does not name the `Sized` lang item"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+            struct Vec<T> { }
+            struct Box<T> where Vec<T>: ?Sized { }
+        }
+        error_msg {
+            "`?Sized` bounds are only allowed on the type parameters of an item"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait Foo {
+                type Item<T> where T: ?Sized;
+            }
+        }
+        error_msg {
+            "`?Sized` bounds are only allowed on the type parameters of an item"
+        }
+    }
+}
+
+#[test]
+fn trait_aliases() {
+    lowering_success! {
+        program {
+            trait Foo<T> { type Item; }
+            trait Bar { }
+            trait Alias<T> = Foo<T> + Bar where T: Bar;
+            trait ItemAlias<T> = Foo<T, Item = T>;
+            trait HigherRanked = forall<'a> Foo<&'a Box<u32>>;
+            struct S<T> where T: ItemAlias<T>, T: Alias<T> { }
+            struct Box<T> { }
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Foo { }
+            trait Alias = Foo;
+            struct S { }
+            impl Alias for S { }
+        }
+        error_msg {
+            "cannot implement trait alias `Alias`; implement the traits it stands for instead"
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Foo { }
+            trait A = Foo + B;
+            trait B = A;
+        }
+        error_msg {
+            "trait alias `A` is defined in terms of itself"
+        }
+    }
+}
+
+#[test]
+fn inline_bounds() {
+    lowering_success! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait Foo<T> { type Item; }
+            trait Bar { }
+            trait Baz<T: Bar>: Foo<T, Item = T> + forall<'a> Foo<&'a T> where T: Foo<T> { }
+            trait Alias<T: Bar> = Bar;
+            struct S<'a, T: Bar + ?Sized, const N: usize> { }
+            enum E<T: Bar> { }
+            impl<'a, T: Bar, U: Foo<T, Item = T>> Bar for S<'a, U, 3> { }
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Bar { }
+            #[auto] trait Foo: Bar { }
+        }
+        error_msg {
+            "auto trait `Foo` cannot have where clauses"
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Tests for defaults of associated types (`type Item = Vec<Self>;` in a
+//! trait), which are used by the impls that do not give a value.
+
+use super::*;
+
+#[test]
+fn normalize_to_default() {
+    test! {
+        program {
+            struct Vec<T> { }
+            struct Foo { }
+            struct Bar { }
+
+            trait Iterator {
+                type Item = Vec<Self>;
+            }
+
+            impl Iterator for Foo { }
+            impl Iterator for Bar {
+                type Item = Bar;
+            }
+        }
+
+        goal {
+            <Foo as Iterator>::Item = Vec<Foo>
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            exists<U> {
+                Normalize(<Bar as Iterator>::Item -> U)
+            }
+        } yields {
+            "Unique; substitution [?0 := Bar]"
+        }
+
+        goal {
+            <Bar as Iterator>::Item = Vec<Bar>
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn default_with_parameters() {
+    test! {
+        program {
+            struct Ref<'a, T> { }
+            struct Box<T> { }
+            struct Foo { }
+
+            trait Lend<T> {
+                type Item<'a> = Ref<'a, T>;
+            }
+
+            impl<U> Lend<Box<U>> for Foo { }
+        }
+
+        goal {
+            forall<'a, U> {
+                <Foo as Lend<Box<U>>>::Item<'a> = Ref<'a, Box<U>>
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<'a> {
+                exists<U> {
+                    Normalize(<Foo as Lend<U>>::Item<'a> -> Ref<'a, Box<Foo>>)
+                }
+            }
+        } yields {
+            "Unique; substitution [?0 := Box<Foo>]"
+        }
+    }
+}
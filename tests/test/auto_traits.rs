@@ -8,8 +8,6 @@ fn auto_trait_without_impls() {
         program {
             #[auto] trait Send { }
 
-            struct i32 { }
-
             struct Useless<T> { }
 
             struct Data<T> {
@@ -50,8 +48,6 @@ fn auto_trait_with_impls() {
         program {
             #[auto] trait Send { }
 
-            struct i32 { }
-            struct f32 { }
             struct Vec<T> { }
 
             impl<T> Send for Vec<T> where T: Send { }
@@ -91,3 +87,54 @@ fn auto_trait_with_impls() {
         }
     }
 }
+
+#[test]
+fn auto_trait_enum() {
+    test! {
+        program {
+            #[auto] trait Send { }
+
+            struct Rc<T> { }
+
+            impl<T> !Send for Rc<T> { }
+
+            enum Option<T> {
+                None,
+                Some(T)
+            }
+
+            enum Either<A, B> {
+                Left { value: A },
+                Right(B)
+            }
+        }
+
+        goal {
+            Option<i32>: Send
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            Option<Rc<i32>>: Send
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            Either<i32, Rc<i32>>: Send
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<A, B> {
+                if (A: Send; B: Send) {
+                    Either<A, B>: Send
+                }
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Tests targeting closure types, declared with `closure`
+
+use super::*;
+
+#[test]
+fn closures_implement_fn_traits() {
+    test! {
+        program {
+            #[lang(fn_once)] trait FnOnce<Args> {
+                type Output;
+            }
+            #[lang(fn_mut)] trait FnMut<Args> where Self: FnOnce<Args> { }
+            #[lang(fn)] trait Fn<Args> where Self: FnMut<Args> { }
+
+            struct Foo { }
+            struct Bar { }
+
+            closure consume(self, x: Foo) -> Bar { Foo }
+            closure mutate(&mut self, x: Foo) -> Bar { Foo }
+            closure inspect(&self, x: Foo, y: Foo) -> Bar { Foo }
+        }
+
+        goal {
+            consume: FnOnce<(Foo,)>
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            consume: FnMut<(Foo,)>
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            mutate: FnMut<(Foo,)>
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            mutate: Fn<(Foo,)>
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            inspect: Fn<(Foo, Foo)>
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            inspect: FnOnce<(Foo,)>
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn closure_output() {
+    test! {
+        program {
+            #[lang(fn_once)] trait FnOnce<Args> {
+                type Output;
+            }
+
+            struct Foo { }
+
+            closure make(self) -> Foo { }
+            closure id<T>(&self, x: T) -> T { }
+        }
+
+        goal {
+            exists<U> {
+                Normalize(<make as FnOnce<()>>::Output -> U)
+            }
+        } yields {
+            "Unique; substitution [?0 := Foo]"
+        }
+
+        goal {
+            forall<T> {
+                <id<T> as FnOnce<(T,)>>::Output = T
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn closure_auto_traits() {
+    test! {
+        program {
+            #[auto] #[lang(send)] trait Send { }
+
+            struct Foo { }
+            struct Bar { }
+            impl !Send for Bar { }
+
+            closure holds_foo(&self) { Foo, u32 }
+            closure holds_bar(&self) { Foo, Bar }
+            closure generic<T>(&self) { T }
+        }
+
+        goal {
+            holds_foo: Send
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            holds_bar: Send
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<T> {
+                if (T: Send) {
+                    generic<T>: Send
+                }
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn closures_are_sized_and_copy_if_upvars_are() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+            #[lang(clone)] trait Clone { }
+            #[lang(copy)] trait Copy where Self: Clone { }
+
+            struct Foo { }
+
+            closure copyable(&self) { u32, bool }
+            closure not_copyable(&self) { u32, Foo }
+        }
+
+        goal {
+            not_copyable: Sized
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            copyable: Copy
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            not_copyable: Clone
+        } yields {
+            "No possible solution"
+        }
+    }
+}
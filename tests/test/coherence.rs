@@ -28,6 +28,42 @@ fn generic_vec_and_specific_vec() {
     }
 }
 
+#[test]
+fn distinct_const_values() {
+    lowering_success! {
+        program {
+            trait Foo { }
+            struct Array<const N: usize> { }
+            impl Foo for Array<3> { }
+            impl Foo for Array<5> { }
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Foo { }
+            struct Array<const N: usize> { }
+            impl Foo for Array<3> { }
+            impl Foo for Array<3> { }
+        }
+        error_msg {
+            "overlapping impls of trait \"Foo\""
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Foo<const N: usize> { }
+            struct Bar { }
+            impl<const N: usize> Foo<N> for Bar { }
+            impl<const M: usize> Foo<M> for Bar { }
+        }
+        error_msg {
+            "overlapping impls of trait \"Foo\""
+        }
+    }
+}
+
 #[test]
 fn concrete_impl_and_blanket_impl() {
     lowering_success! {
@@ -192,7 +228,6 @@ fn overlapping_negative_positive_impls() {
     lowering_error! {
         program {
             trait Send { }
-            struct i32 { }
 
             impl Send for i32 { }
             impl !Send for i32 { }
@@ -211,7 +246,6 @@ fn overlapping_negative_impls() {
             trait Bar { }
 
             struct Vec<T> { }
-            struct i32 { }
 
             impl Foo for i32 { }
             impl Bar for i32 { }
@@ -264,7 +298,6 @@ fn fundamental_traits() {
     lowering_error! {
         program {
             #[upstream] trait Sized { }
-            #[upstream] struct str { }
             trait Bar { }
             impl Bar for str { }
             impl<T> Bar for T where T: Sized { }
@@ -279,7 +312,6 @@ fn fundamental_traits() {
     lowering_success! {
         program {
             #[upstream] #[fundamental] trait Sized { }
-            #[upstream] struct str { }
             trait Bar { }
             impl Bar for str { }
             impl<T> Bar for T where T: Sized { }
@@ -365,8 +397,6 @@ fn orphan_check() {
         program {
             #[auto] #[upstream] trait Send { }
             #[upstream] trait TheTrait<T> { }
-            #[upstream] struct isize { }
-            #[upstream] struct usize { }
 
             struct TheType { }
 
@@ -385,7 +415,6 @@ fn orphan_check() {
         program {
             #[auto] #[upstream] trait Send { }
             #[upstream] struct Vec<T> { }
-            #[upstream] struct isize { }
 
             impl !Send for Vec<isize> { }
         } error_msg {
@@ -410,7 +439,6 @@ fn orphan_check() {
         program {
             #[upstream] trait Remote1<T> { }
             #[upstream] struct Pair<T, U> { }
-            #[upstream] struct i32 { }
 
             struct Local<T> { }
 
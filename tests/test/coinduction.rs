@@ -8,8 +8,6 @@ fn coinductive_semantics() {
         program {
             #[auto] trait Send { }
 
-            struct i32 { }
-
             struct Ptr<T> { }
             impl<T> Send for Ptr<T> where T: Send { }
 
@@ -0,0 +1,141 @@
+//! Tests targeting const generic parameters like `struct Foo<const N: usize>`
+
+use super::*;
+
+#[test]
+fn unify_consts() {
+    test! {
+        program {
+            struct Foo<const N: usize> { }
+        }
+
+        goal {
+            Foo<3> = Foo<3>
+        } yields {
+            "Unique; substitution [], lifetime constraints []"
+        }
+
+        goal {
+            Foo<3> = Foo<5>
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            exists<const N: usize> {
+                Foo<N> = Foo<3>
+            }
+        } yields {
+            "Unique; substitution [?0 := 3], lifetime constraints []"
+        }
+
+        goal {
+            exists<const N: usize, const M: usize> {
+                Foo<N> = Foo<M>
+            }
+        } yields {
+            "Unique; for<?U0> { substitution [?0 := ^0, ?1 := ^0], lifetime constraints [] }"
+        }
+
+        goal {
+            forall<const N: usize> {
+                Foo<N> = Foo<3>
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<const N: usize> {
+                exists<const M: usize> {
+                    Foo<N> = Foo<M>
+                }
+            }
+        } yields {
+            "Unique; substitution [?0 := !1_0], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn const_impls() {
+    test! {
+        program {
+            struct Foo<const N: usize> { }
+            trait Trait { }
+            trait Three { }
+
+            impl<const N: usize> Trait for Foo<N> { }
+            impl Three for Foo<3> { }
+        }
+
+        goal {
+            Foo<5>: Trait
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<const N: usize> {
+                Foo<N>: Trait
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            Foo<3>: Three
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            Foo<4>: Three
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            exists<const N: usize> {
+                Foo<N>: Three
+            }
+        } yields {
+            "Unique; substitution [?0 := 3], lifetime constraints []"
+        }
+
+        goal {
+            forall<const N: usize> {
+                Foo<N>: Three
+            }
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn const_and_type_parameters() {
+    test! {
+        program {
+            struct Array<T, const N: usize> { }
+            struct Bar { }
+            trait Len<const N: usize> { }
+
+            impl<T, const N: usize> Len<N> for Array<T, N> { }
+        }
+
+        goal {
+            exists<const N: usize> {
+                Array<Bar, 7>: Len<N>
+            }
+        } yields {
+            "Unique; substitution [?0 := 7], lifetime constraints []"
+        }
+
+        goal {
+            Array<Bar, 7>: Len<8>
+        } yields {
+            "No possible solution"
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Tests for programs made up of several crates (`crate b: a { ... }`),
+//! where locality is relative to the crate we reason from.
+
+use super::*;
+
+#[test]
+fn locality_is_relative_to_the_crate() {
+    test! {
+        program {
+            crate std {
+                struct Vec<T> { }
+            }
+
+            crate serde: std {
+                struct Value { }
+            }
+
+            crate app: serde {
+                struct Config { }
+            }
+
+            crate other: std { }
+        }
+
+        goal { in crate serde { IsLocal(serde::Value) } } yields { "Unique" }
+        goal { in crate serde { IsUpstream(std::Vec<serde::Value>) } } yields { "Unique" }
+        goal { in crate serde { IsLocal(app::Config) } } yields { "No possible solution" }
+        goal { in crate serde { IsUpstream(app::Config) } } yields { "No possible solution" }
+
+        // Dependencies are transitive: `std` is upstream of `app`.
+        goal { in crate app { IsUpstream(std::Vec<app::Config>) } } yields { "Unique" }
+        goal { in crate app { IsUpstream(serde::Value) } } yields { "Unique" }
+        goal { in crate app { IsLocal(app::Config) } } yields { "Unique" }
+
+        // `other` does not depend on `serde`.
+        goal { in crate other { IsUpstream(serde::Value) } } yields { "No possible solution" }
+
+        // Without a crate to reason from, nothing is local or upstream.
+        goal { IsLocal(serde::Value) } yields { "No possible solution" }
+        goal { IsUpstream(serde::Value) } yields { "No possible solution" }
+    }
+}
+
+#[test]
+fn downstream_types_are_relative_to_the_crate() {
+    test! {
+        program {
+            crate a { }
+
+            crate b: a {
+                struct T { }
+            }
+
+            crate c: b { }
+
+            crate other { }
+        }
+
+        goal { in crate a { DownstreamType(b::T) } } yields { "Unique" }
+        goal { in crate b { DownstreamType(b::T) } } yields { "No possible solution" }
+        goal { in crate c { DownstreamType(b::T) } } yields { "No possible solution" }
+        goal { in crate other { DownstreamType(b::T) } } yields { "No possible solution" }
+    }
+}
+
+#[test]
+fn fundamental_types_in_crates() {
+    test! {
+        program {
+            crate std {
+                #[fundamental]
+                struct Box<T> { }
+            }
+
+            crate app: std {
+                struct Config { }
+            }
+        }
+
+        goal { in crate app { IsLocal(std::Box<app::Config>) } } yields { "Unique" }
+        goal { in crate std { IsLocal(std::Box<app::Config>) } } yields { "Unique" }
+        goal { in crate app { IsUpstream(std::Box<app::Config>) } } yields { "No possible solution" }
+    }
+}
+
+#[test]
+fn orphan_rules_per_crate() {
+    lowering_success! {
+        program {
+            crate std {
+                trait Clone { }
+                struct Vec<T> { }
+                impl<T> Clone for Vec<T> where T: Clone { }
+            }
+
+            crate app: std {
+                struct Config { }
+                impl std::Clone for Config { }
+            }
+        }
+    }
+
+    lowering_error! {
+        program {
+            crate std {
+                trait Clone { }
+                struct Vec<T> { }
+            }
+
+            crate app: std {
+                struct Config { }
+                impl std::Clone for std::Vec<Config> { }
+            }
+        } error_msg {
+            "impl for trait \"std::Clone\" violates the orphan rules"
+        }
+    }
+
+    // `app` does not depend on `serde`, so an impl of one of its
+    // traits in `app` is not local to either crate.
+    lowering_error! {
+        program {
+            crate serde {
+                trait Serialize { }
+            }
+
+            crate app {
+                struct Config { }
+                impl serde::Serialize for Config { }
+            }
+        } error_msg {
+            "impl for trait \"serde::Serialize\" violates the orphan rules"
+        }
+    }
+}
+
+#[test]
+fn invalid_crates() {
+    lowering_error! {
+        program {
+            crate app: serde { }
+        } error_msg {
+            "invalid crate name `serde`"
+        }
+    }
+
+    lowering_error! {
+        program {
+            crate a: b { }
+            crate b: a { }
+        } error_msg {
+            "crate `a` depends on itself"
+        }
+    }
+}
+
+#[test]
+fn overlap_across_crates() {
+    lowering_error! {
+        program {
+            crate std {
+                trait Into<T> { }
+                struct String { }
+                impl<T> Into<String> for T { }
+            }
+
+            crate app: std {
+                struct Config { }
+                impl<T> std::Into<T> for Config { }
+            }
+        } error_msg {
+            "overlapping impls of trait \"std::Into\""
+        }
+    }
+}
+
+#[test]
+fn closures_and_opaque_types_in_sibling_crates() {
+    test! {
+        program {
+            crate std {
+                trait Show { }
+                struct Unit { }
+                impl Show for Unit { }
+            }
+
+            crate a: std {
+                closure make(self) -> std::Unit { }
+                opaque type Shown: std::Show = std::Unit;
+            }
+
+            crate b: std { }
+        }
+
+        goal { in crate a { IsLocal(a::make) } } yields { "Unique" }
+        goal { in crate a { IsLocal(a::Shown) } } yields { "Unique" }
+        goal { in crate b { IsLocal(a::make) } } yields { "No possible solution" }
+        goal { in crate b { IsLocal(a::Shown) } } yields { "No possible solution" }
+        goal { in crate b { IsUpstream(a::Shown) } } yields { "No possible solution" }
+        goal { in crate std { DownstreamType(a::make) } } yields { "Unique" }
+    }
+
+    lowering_success! {
+        program {
+            crate std {
+                trait Show { }
+                struct Unit { }
+                impl Show for Unit { }
+            }
+
+            crate a: std {
+                closure make(self) -> std::Unit { }
+                impl std::Show for make { }
+            }
+
+            crate b: std {
+                closure make(self) -> std::Unit { }
+                impl std::Show for make { }
+            }
+        }
+    }
+
+    // `b` is a sibling of `a`, not a dependent: it may not implement
+    // `std`'s traits for `a`'s types.
+    lowering_error! {
+        program {
+            crate std {
+                trait Show { }
+                struct Unit { }
+            }
+
+            crate a: std {
+                closure make(self) -> std::Unit { }
+            }
+
+            crate b: std {
+                impl std::Show for a::make { }
+            }
+        } error_msg {
+            "impl for trait \"std::Show\" violates the orphan rules"
+        }
+    }
+}
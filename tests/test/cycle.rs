@@ -18,7 +18,6 @@ fn inner_cycle() {
             #[marker]
             trait B { }
 
-            struct i32 { }
             struct Vec<T> { }
 
             impl<T> A for T where T: B { }
@@ -62,7 +61,6 @@ fn cycle_many_solutions() {
         program {
             trait Foo { }
             struct S<T> { }
-            struct i32 { }
             impl<T> Foo for S<T> where T: Foo { }
             impl Foo for i32 { }
         }
@@ -85,7 +83,6 @@ fn cycle_unique_solution() {
             trait Foo { }
             trait Bar { }
             struct S<T> { }
-            struct i32 { }
             impl<T> Foo for S<T> where T: Foo, T: Bar { }
             impl Foo for i32 { }
         }
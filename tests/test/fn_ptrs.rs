@@ -0,0 +1,188 @@
+//! Tests targeting the built-in function pointer types like `fn(A) -> R`
+
+use super::*;
+
+#[test]
+fn unify_fn_ptrs() {
+    test! {
+        program {
+            struct Foo { }
+            struct Bar { }
+        }
+
+        goal {
+            exists<A, R> {
+                fn(A) -> R = fn(Foo) -> Bar
+            }
+        } yields {
+            "Unique; substitution [?0 := Foo, ?1 := Bar], lifetime constraints []"
+        }
+
+        goal {
+            fn(Foo) = fn(Foo) -> ()
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            fn(Foo) = fn(Foo, Foo)
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            for<'a> fn(&'a Foo) -> &'a Foo = for<'b> fn(&'b Foo) -> &'b Foo
+        } yields {
+            "Unique"
+        }
+
+        // The bound lifetime is instantiated with a placeholder; the
+        // resulting region constraint is left to the caller.
+        goal {
+            forall<'x> {
+                for<'a> fn(&'a Foo) = fn(&'x Foo)
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!2_0 == '!1_0 }]"
+        }
+    }
+}
+
+#[test]
+fn fn_ptrs_are_copy_clone_sized() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+            #[lang(clone)] trait Clone { }
+            #[lang(copy)] trait Copy where Self: Clone { }
+
+            struct Foo { }
+        }
+
+        goal {
+            forall<T> {
+                fn(T) -> T: Copy
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            for<'a> fn(&'a Foo): Clone
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            fn(): Sized
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn fn_ptrs_implement_fn_traits() {
+    test! {
+        program {
+            #[lang(fn_once)] trait FnOnce<Args> {
+                type Output;
+            }
+            #[lang(fn_mut)] trait FnMut<Args> where Self: FnOnce<Args> { }
+            #[lang(fn)] trait Fn<Args> where Self: FnMut<Args> { }
+
+            struct Foo { }
+            struct Bar { }
+        }
+
+        goal {
+            fn(Foo) -> Bar: Fn<(Foo,)>
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            fn(Foo) -> Bar: FnMut<(Bar,)>
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            exists<U> {
+                Normalize(<fn(Foo, Foo) -> Bar as FnOnce<(Foo, Foo)>>::Output -> U)
+            }
+        } yields {
+            "Unique; substitution [?0 := Bar]"
+        }
+
+        goal {
+            <fn(Foo, Foo) -> Bar as FnOnce<(Foo, Foo)>>::Output = Bar
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<'x> {
+                for<'a> fn(&'a Foo) -> &'a Foo: FnOnce<(&'x Foo,)>
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<'x> {
+                exists<U> {
+                    Normalize(<for<'a> fn(&'a Foo) -> &'a Foo as FnOnce<(&'x Foo,)>>::Output -> U)
+                }
+            }
+        } yields {
+            "Unique; substitution [?0 := &'!1_0 Foo], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn fn_once_output_is_found_by_lang_item() {
+    // The output is `FnOnce`'s only associated type, whatever its name.
+    test! {
+        program {
+            #[lang(fn_once)] trait FnOnce<Args> {
+                type Result;
+            }
+
+            struct Foo { }
+            struct Bar { }
+        }
+
+        goal {
+            <fn(Foo) -> Bar as FnOnce<(Foo,)>>::Result = Bar
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn fn_ptrs_auto_traits() {
+    test! {
+        program {
+            #[auto] #[lang(send)] trait Send { }
+
+            struct Rc<T> { }
+
+            impl<T> !Send for Rc<T> { }
+        }
+
+        goal {
+            fn(Rc<()>) -> Rc<()>: Send
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            for<'a> fn(&'a Rc<()>) -> Rc<()>: Send
+        } yields {
+            "Unique"
+        }
+    }
+}
@@ -8,7 +8,6 @@ fn implied_bounds() {
         program {
             trait Clone { }
             trait Iterator where Self: Clone { type Item; }
-            struct u32 { }
         }
 
         goal {
@@ -29,7 +28,6 @@ fn gat_implied_bounds() {
         program {
             trait Clone { }
             trait Foo { type Item<T>: Clone; }
-            struct u32 { }
         }
 
         goal {
@@ -47,7 +45,6 @@ fn gat_implied_bounds() {
         program {
             trait Clone { }
             trait Foo { type Item<T>; }
-            struct u32 { }
         }
 
         goal {
@@ -160,7 +160,6 @@ fn prove_forall() {
 fn higher_ranked() {
     test! {
         program {
-            struct u8 { }
             struct SomeType<T> { }
             trait Foo<T> { }
             impl<U> Foo<u8> for SomeType<U> { }
@@ -205,8 +204,6 @@ fn normalize_rev_infer() {
     test! {
         program {
             trait Identity { type Item; }
-            struct u32 { }
-            struct i32 { }
             impl Identity for u32 { type Item = u32; }
             impl Identity for i32 { type Item = i32; }
         }
@@ -228,8 +225,6 @@ fn normalize_rev_infer_gat() {
     test! {
         program {
             trait Combine { type Item<T>; }
-            struct u32 { }
-            struct i32 { }
             struct Either<T, U> { }
             impl Combine for u32 { type Item<U> = Either<u32, U>; }
             impl Combine for i32 { type Item<U> = Either<i32, U>; }
@@ -362,8 +357,6 @@ fn suggested_subst() {
             trait SomeTrait<A> {}
             struct Foo {}
             struct Bar {}
-            struct i32 {}
-            struct bool {}
             impl SomeTrait<i32> for Foo {}
             impl SomeTrait<bool> for Bar {}
             impl SomeTrait<i32> for Bar {}
@@ -490,9 +483,6 @@ fn where_clause_trumps() {
 fn inapplicable_assumption_does_not_shadow() {
     test! {
         program {
-            struct i32 { }
-            struct u32 { }
-
             trait Foo<T> { }
 
             impl<T> Foo<i32> for T { }
@@ -520,9 +510,6 @@ fn partial_overlap_2() {
             trait Foo {}
             trait Bar {}
 
-            struct i32 {}
-            struct u32 {}
-
             impl<T> Marker<i32> for T where T: Foo {}
             impl<T> Marker<u32> for T where T: Bar {}
         }
@@ -570,7 +557,6 @@ fn partial_overlap_3() {
             impl<T> Marker for T where T: Foo {}
             impl<T> Marker for T where T: Bar {}
 
-            struct i32 {}
             impl Foo for i32 {}
             impl Bar for i32 {}
         }
@@ -597,7 +583,6 @@ fn clauses_in_if_goals() {
         program {
             trait Foo { }
             struct Vec<T> { }
-            struct i32 { }
         }
 
         goal {
@@ -0,0 +1,161 @@
+//! Tests for `include "file.chalk";` directives. Paths in the programs
+//! below are relative to the root of the repository (see `solve_goal`).
+
+use super::*;
+use chalk_integration::include;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// The path of the file `name` in `tests/test/includes`, as includes in
+/// the programs below resolve it.
+fn include_path(name: &str) -> PathBuf {
+    include::resolve(
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+        &format!("tests/test/includes/{}", name),
+    )
+}
+
+/// A database for `program_text`, whose includes are resolved like those
+/// of the programs in `test!`.
+fn database(program_text: &str) -> ChalkDatabase {
+    ChalkDatabase::with_dir(
+        program_text,
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+        SolverChoice::default(),
+    )
+}
+
+/// Solves `goal` against the program in `db`.
+fn solve(db: &ChalkDatabase, goal: &str) -> Option<Solution> {
+    let program = db.checked_program().unwrap();
+    chalk_ir::tls::set_current_program(&program, || {
+        let goal = db.parse_and_lower_goal(goal).unwrap();
+        db.solve(&goal.into_peeled_goal())
+    })
+}
+
+#[test]
+fn include_file() {
+    test! {
+        program {
+            include "tests/test/includes/numbers.chalk";
+
+            struct Foo { }
+        }
+
+        goal { Vec<Int>: Clone } yields { "Unique" }
+        goal { Vec<Foo>: Clone } yields { "No possible solution" }
+    }
+}
+
+#[test]
+fn include_shared_prelude() {
+    // Both files include the prelude; it is only included once, so
+    // `Clone` and `Vec` are not defined twice.
+    test! {
+        program {
+            include "tests/test/includes/numbers.chalk";
+            include "tests/test/includes/strings.chalk";
+
+            impl Clone for Str { }
+        }
+
+        goal { Vec<Vec<Str>>: Clone } yields { "Unique" }
+    }
+}
+
+#[test]
+fn include_in_module() {
+    test! {
+        program {
+            mod std {
+                include "tests/test/includes/prelude.chalk";
+            }
+
+            struct Foo { }
+            impl std::Clone for Foo { }
+        }
+
+        goal { std::Vec<Foo>: std::Clone } yields { "Unique" }
+    }
+}
+
+#[test]
+fn include_errors() {
+    lowering_error! {
+        program {
+            include "tests/test/includes/cycle_a.chalk";
+        }
+        error_msg {
+            format!("file `{}` includes itself", include_path("cycle_a.chalk").display())
+        }
+    }
+
+    lowering_error! {
+        program {
+            include "tests/test/includes/missing.chalk";
+        }
+        error_msg {
+            format!(
+                "cannot read included file `{}`",
+                include_path("missing.chalk").display()
+            )
+        }
+    }
+}
+
+#[test]
+fn edit_included_file() {
+    let mut db = database(r#"include "tests/test/includes/numbers.chalk";"#);
+    assert_result(&solve(&db, "Vec<Int>: Clone"), "Unique");
+
+    // Dropping the `where` clause from the prelude is seen by the files
+    // that include it.
+    db.edit_file(
+        &include_path("prelude.chalk"),
+        "trait Clone { } struct Vec<T> { } impl<T> Clone for Vec<T> where T: Copy { } trait Copy { }",
+    );
+    assert_result(&solve(&db, "Vec<Int>: Clone"), "No possible solution");
+}
+
+#[test]
+fn edit_included_file_reuses_the_rest() {
+    let mut db = database(r#"include "tests/test/includes/numbers.chalk";"#);
+    let prelude = db.parsed_file(include_path("prelude.chalk")).unwrap();
+    let program = db.program_ir().unwrap();
+
+    // Only the edited file is parsed again. Its items are unchanged (a
+    // comment at the end does not move them), so the program is not
+    // lowered again either.
+    let numbers = std::fs::read_to_string(include_path("numbers.chalk")).unwrap();
+    db.edit_file(
+        &include_path("numbers.chalk"),
+        &format!("{}\n// edited\n", numbers),
+    );
+    assert!(Arc::ptr_eq(
+        &prelude,
+        &db.parsed_file(include_path("prelude.chalk")).unwrap()
+    ));
+    assert!(Arc::ptr_eq(&program, &db.program_ir().unwrap()));
+
+    // Changing its items lowers the program again.
+    db.edit_file(
+        &include_path("numbers.chalk"),
+        &format!("{}\nstruct Float {{ }}\n", numbers),
+    );
+    assert!(!Arc::ptr_eq(&program, &db.program_ir().unwrap()));
+    assert_result(&solve(&db, "Vec<Float>: Clone"), "No possible solution");
+}
+
+#[test]
+fn edit_program_reads_new_includes() {
+    let mut db = database("struct Foo { }");
+    db.edit_program(
+        r#"
+        include "tests/test/includes/numbers.chalk";
+        struct Foo { }
+        impl Clone for Foo { }
+        "#,
+    );
+    assert_result(&solve(&db, "Vec<Foo>: Clone"), "Unique");
+}
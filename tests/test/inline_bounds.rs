@@ -0,0 +1,146 @@
+//! Tests for supertraits (`trait Copy: Clone { }`) and bounds written
+//! inline in parameter lists (`impl<T: Clone>`), which mean the same as
+//! the corresponding where clauses.
+
+use super::*;
+
+#[test]
+fn supertraits() {
+    test! {
+        program {
+            trait Clone { }
+            trait Foo<T> { type Item; }
+            trait Copy: Clone + Foo<Self, Item = Self> { }
+        }
+
+        goal {
+            forall<T> {
+                if (T: Copy) {
+                    T: Clone
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                if (T: Copy) {
+                    <T as Foo<T>>::Item = T
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                if (T: Copy) {
+                    WellFormed(T: Copy)
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                if (FromEnv(T: Copy)) {
+                    WellFormed(T: Copy)
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                if (T: Clone) {
+                    WellFormed(T: Copy)
+                }
+            }
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn inline_bounds_on_impls() {
+    test! {
+        program {
+            trait Clone { }
+            trait Send { }
+            trait Foo { }
+            struct Vec<T> { }
+            struct A { }
+            struct B { }
+
+            impl<T: Clone + Send> Foo for Vec<T> { }
+            impl Clone for A { }
+            impl Send for A { }
+            impl Clone for B { }
+        }
+
+        goal {
+            Vec<A>: Foo
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            Vec<B>: Foo
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn inline_bounds_on_types() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait Bar { }
+            struct S<T: Bar> { }
+            struct Unsized<T: ?Sized + Bar> { }
+            struct A { }
+            impl Bar for A { }
+            impl Bar for [A] { }
+        }
+
+        goal {
+            WellFormed(S<A>)
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            WellFormed(S<u32>)
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<T> {
+                if (FromEnv(S<T>)) {
+                    T: Bar
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            WellFormed(S<[A]>)
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            WellFormed(Unsized<[A]>)
+        } yields {
+            "Unique"
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Tests targeting traits marked with `#[lang(..)]`
+
+use super::*;
+
+#[test]
+fn built_in_impls_follow_lang_items() {
+    test! {
+        program {
+            #[lang(copy)] trait MyCopy { }
+            trait Copy { }
+        }
+
+        goal {
+            (u32, bool): MyCopy
+        } yields {
+            "Unique"
+        }
+
+        // Only the lang item gets built-in impls, whatever it's called.
+        goal {
+            (u32, bool): Copy
+        } yields {
+            "No possible solution"
+        }
+    }
+}
@@ -3,6 +3,7 @@ use chalk_integration::query::LoweringDatabase;
 use chalk_ir;
 use chalk_solve::ext::*;
 use chalk_solve::{Solution, SolverChoice};
+use std::path::Path;
 
 #[cfg(feature = "bench")]
 mod bench;
@@ -132,8 +133,10 @@ fn solve_goal(program_text: &str, goals: Vec<(&str, SolverChoice, TestGoal)>) {
     assert!(program_text.starts_with("{"));
     assert!(program_text.ends_with("}"));
 
-    let mut db = ChalkDatabase::with(
+    // Includes are resolved relative to the root of the repository.
+    let mut db = ChalkDatabase::with_dir(
         &program_text[1..program_text.len() - 1],
+        Path::new(env!("CARGO_MANIFEST_DIR")),
         SolverChoice::default(),
     );
 
@@ -196,13 +199,35 @@ fn solve_goal(program_text: &str, goals: Vec<(&str, SolverChoice, TestGoal)>) {
     }
 }
 
+mod assoc_ty_defaults;
 mod auto_traits;
+mod closures;
 mod coherence_goals;
 mod coinduction;
+mod consts;
+mod crates;
 mod cycle;
+mod fn_ptrs;
 mod implied_bounds;
 mod impls;
+mod includes;
+mod inline_bounds;
+mod lang_items;
+mod modules;
 mod negation;
+mod never;
+mod object_safety;
+mod opaque_types;
+mod outlives;
 mod projection;
+mod raw_ptrs;
+mod refs;
+mod scalars;
+mod sized;
+mod slices;
+mod specialization;
+mod subtype;
+mod trait_aliases;
+mod tuples;
 mod unify;
 mod wf_goals;
@@ -0,0 +1,113 @@
+//! Tests for `mod` blocks and the paths (`foo::Bar`) that refer to the
+//! items inside them.
+
+use super::*;
+
+#[test]
+fn same_name_in_different_modules() {
+    test! {
+        program {
+            trait Debug { }
+
+            mod io {
+                struct Error { }
+                impl Debug for Error { }
+            }
+
+            mod fmt {
+                struct Error { }
+            }
+        }
+
+        goal {
+            io::Error: Debug
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            fmt::Error: Debug
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            io::Error = fmt::Error
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn names_resolve_in_enclosing_modules() {
+    test! {
+        program {
+            struct Error { }
+            trait Iterator { type Item; }
+
+            mod a {
+                struct Error { }
+
+                mod b {
+                    struct Iter { }
+
+                    // `Error` is `a::Error`, the closest enclosing
+                    // definition; `Iterator` is found at the top level.
+                    impl Iterator for Iter {
+                        type Item = Error;
+                    }
+                }
+            }
+        }
+
+        goal {
+            exists<T> {
+                Normalize(<a::b::Iter as Iterator>::Item -> T)
+            }
+        } yields {
+            "Unique; substitution [?0 := a::Error]"
+        }
+
+        goal {
+            Normalize(<a::b::Iter as Iterator>::Item -> Error)
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn paths_into_nested_modules() {
+    test! {
+        program {
+            mod collections {
+                trait Iter { }
+
+                mod vec {
+                    struct Vec<T> { }
+                    impl<T> Iter for Vec<T> { }
+                }
+
+                struct Wrapper<T> { }
+                impl<T> Iter for Wrapper<T> where T: Iter { }
+            }
+
+            struct Foo { }
+        }
+
+        goal {
+            collections::Wrapper<collections::vec::Vec<Foo>>: collections::Iter
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            exists<T> {
+                T: collections::Iter
+            }
+        } yields {
+            "Ambiguous"
+        }
+    }
+}
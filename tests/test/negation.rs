@@ -6,7 +6,6 @@ use super::*;
 fn simple_negation() {
     test! {
         program {
-            struct i32 {}
             trait Foo {}
         }
 
@@ -101,8 +100,6 @@ fn deep_negation() {
 fn negation_quantifiers() {
     test! {
         program {
-            struct i32 {}
-            struct u32 {}
         }
 
         goal {
@@ -142,8 +139,6 @@ fn negation_free_vars() {
     test! {
         program {
             struct Vec<T> {}
-            struct i32 {}
-            struct u32 {}
             trait Foo {}
             impl Foo for Vec<u32> {}
         }
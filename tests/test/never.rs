@@ -0,0 +1,116 @@
+//! Tests targeting the built-in never type `!`
+
+use super::*;
+
+#[test]
+fn unify_never() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            exists<T> {
+                T = !
+            }
+        } yields {
+            "Unique; substitution [?0 := !], lifetime constraints []"
+        }
+
+        goal {
+            ! = Foo
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            exists<T> {
+                fn(T) -> ! = fn(Foo) -> !
+            }
+        } yields {
+            "Unique; substitution [?0 := Foo], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn never_impls() {
+    test! {
+        program {
+            #[lang(clone)] trait Clone { }
+            #[lang(copy)] trait Copy where Self: Clone { }
+            #[lang(sized)] trait Sized { }
+            #[auto] #[lang(send)] trait Send { }
+            #[auto] #[lang(sync)] trait Sync { }
+            trait Debug { }
+            impl Debug for ! { }
+        }
+
+        goal {
+            !: Sized
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            !: Copy
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            !: Send
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            !: Sync
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            !: Debug
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            WellFormed(!)
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn never_coherence() {
+    lowering_error! {
+        program {
+            trait Foo { }
+            impl Foo for ! { }
+            impl Foo for ! { }
+        } error_msg {
+            "overlapping impls of trait \"Foo\""
+        }
+    }
+
+    lowering_success! {
+        program {
+            trait Foo { }
+            struct Bar { }
+            impl Foo for Bar { }
+            impl Foo for ! { }
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[upstream] trait Remote { }
+            impl Remote for ! { }
+        } error_msg {
+            "impl for trait \"Remote\" violates the orphan rules"
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Tests for object safety (`ObjectSafe(Trait)`) and the well-formedness
+//! of trait objects, `dyn Trait`.
+
+use super::*;
+
+#[test]
+fn object_safe_traits() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            trait Clone { }
+            trait Iterator { type Item; }
+            trait Reader: Clone { }
+
+            trait Unsized1 where Self: Sized { }
+            trait Unsized2: Sized { }
+            trait Unsized3: Unsized2 { }
+
+            trait Lending { type Item<'a>; }
+            trait Lending2: Lending { }
+        }
+
+        goal { ObjectSafe(Clone) } yields { "Unique" }
+        goal { ObjectSafe(Iterator) } yields { "Unique" }
+        goal { ObjectSafe(Reader) } yields { "Unique" }
+        goal { ObjectSafe(Unsized1) } yields { "No possible solution" }
+        goal { ObjectSafe(Unsized2) } yields { "No possible solution" }
+        goal { ObjectSafe(Unsized3) } yields { "No possible solution" }
+        goal { ObjectSafe(Lending) } yields { "No possible solution" }
+        goal { ObjectSafe(Lending2) } yields { "No possible solution" }
+    }
+}
+
+#[test]
+fn object_safety_of_cyclic_traits() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            trait A where Self: B { }
+            trait B where Self: A { }
+
+            trait C where Self: D { }
+            trait D where Self: C, Self: Sized { }
+        }
+
+        goal { ObjectSafe(A) } yields { "Unique" }
+        goal { ObjectSafe(C) } yields { "No possible solution" }
+        goal { ObjectSafe(D) } yields { "No possible solution" }
+    }
+}
+
+#[test]
+fn well_formed_trait_objects() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            trait Clone { }
+            trait Send { }
+            trait Iterator { type Item; }
+            trait Unsized: Sized { }
+            trait Lending { type Item<'a>; }
+
+            struct Foo { }
+        }
+
+        goal { WellFormed(dyn Clone) } yields { "Unique" }
+        goal { WellFormed(dyn Clone + Send) } yields { "Unique" }
+        goal { WellFormed(dyn Iterator<Item = Foo>) } yields { "Unique" }
+        goal { WellFormed(dyn Unsized) } yields { "No possible solution" }
+        goal { WellFormed(dyn Clone + Unsized) } yields { "No possible solution" }
+        goal { forall<'a> { WellFormed(dyn Lending<Item<'a> = Foo>) } } yields { "No possible solution" }
+    }
+}
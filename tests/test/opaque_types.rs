@@ -0,0 +1,163 @@
+//! Tests for named opaque types (`opaque type Foo<T>: Bounds = Hidden;`),
+//! whose hidden type is only visible in `reveal { .. }` goals.
+
+use super::*;
+
+#[test]
+fn opaque_types_have_identity() {
+    test! {
+        program {
+            struct Bar<T> { }
+            struct Baz { }
+            trait Clone { }
+            impl<T> Clone for Bar<T> { }
+
+            opaque type Foo<T>: Clone = Bar<T>;
+        }
+
+        goal {
+            Foo<Baz> = Foo<Baz>
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            exists<T> {
+                Foo<Baz> = Foo<T>
+            }
+        } yields {
+            "Unique; substitution [?0 := Baz]"
+        }
+
+        goal {
+            forall<T> {
+                Foo<T> = Bar<T>
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<T> {
+                WellFormed(Foo<T>)
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn opaque_types_meet_their_bounds() {
+    test! {
+        program {
+            struct Bar<T> { }
+            struct Baz { }
+            trait Clone { }
+            trait Debug { }
+            trait Iterator { type Item; }
+
+            impl<T> Clone for Bar<T> { }
+            impl<T> Debug for Bar<T> { }
+            impl<T> Iterator for Bar<T> { type Item = T; }
+
+            opaque type Foo<T>: Clone + Iterator<Item = T> = Bar<T>;
+        }
+
+        goal {
+            forall<T> {
+                Foo<T>: Clone
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                <Foo<T> as Iterator>::Item = T
+            }
+        } yields {
+            "Unique"
+        }
+
+        // The hidden type implements `Debug`, but that is not visible.
+        goal {
+            forall<T> {
+                Foo<T>: Debug
+            }
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn reveal_hidden_type() {
+    test! {
+        program {
+            struct Bar<T> { }
+            struct Baz { }
+            trait Clone { }
+            trait Debug { }
+            #[auto] trait Send { }
+
+            impl<T> Clone for Bar<T> { }
+            impl Debug for Bar<Baz> { }
+
+            opaque type Foo<T>: Clone = Bar<T>;
+        }
+
+        goal {
+            reveal {
+                forall<T> {
+                    Foo<T> = Bar<T>
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            reveal {
+                exists<T> {
+                    Foo<Baz> = Bar<T>
+                }
+            }
+        } yields {
+            "Unique; substitution [?0 := Baz]"
+        }
+
+        goal {
+            reveal {
+                forall<T> {
+                    Foo<T> = Baz
+                }
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            reveal {
+                Foo<Baz>: Debug
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            Foo<Baz>: Debug
+        } yields {
+            "No possible solution"
+        }
+
+        // Auto traits of the hidden type leak out when revealed.
+        goal {
+            reveal {
+                Foo<Baz>: Send
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
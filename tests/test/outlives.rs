@@ -0,0 +1,159 @@
+//! Tests targeting outlives requirements (`'a: 'b` and `T: 'a`), which are
+//! reported back to the caller as region constraints.
+
+use super::*;
+
+#[test]
+fn outlives_goals_yield_constraints() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            forall<'a, 'b> {
+                'a: 'b
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_0: '!1_1 }]"
+        }
+
+        goal {
+            forall<'a, T> {
+                T: 'a
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: !1_1: '!1_0 }]"
+        }
+
+        goal {
+            forall<'a, 'b> {
+                if ('a: 'b) {
+                    'a: 'b
+                }
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn outlives_where_clauses() {
+    test! {
+        program {
+            trait Foo { }
+            struct Ref<'a, T> where T: 'a { }
+            impl<'a, T> Foo for Ref<'a, T> where T: 'a { }
+        }
+
+        goal {
+            forall<'a, T> {
+                Ref<'a, T>: Foo
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: !1_1: '!1_0 }]"
+        }
+
+        goal {
+            forall<'a, T> {
+                WellFormed(Ref<'a, T>)
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: !1_1: '!1_0 }]"
+        }
+
+        goal {
+            forall<'a, T> {
+                if (FromEnv(Ref<'a, T>)) {
+                    T: 'a
+                }
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn references_require_outlives() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            forall<'a, T> {
+                WellFormed(&'a T)
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: !1_1: '!1_0 }]"
+        }
+
+        goal {
+            forall<'a, T> {
+                if (FromEnv(&'a T)) {
+                    T: 'a
+                }
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn region_constraints_are_simplified() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        // Duplicate constraints are only reported once.
+        goal {
+            forall<'a, 'b> {
+                (&'a Foo, &'a Foo) = (&'b Foo, &'b Foo)
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_1 == '!1_0 }]"
+        }
+
+        goal {
+            forall<'a, 'b> {
+                'a: 'b, 'a: 'b
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_0: '!1_1 }]"
+        }
+
+        // Outlives constraints between equal lifetimes are trivial.
+        goal {
+            forall<'a, 'b> {
+                &'a Foo = &'b Foo, 'a: 'b
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_1 == '!1_0 }]"
+        }
+
+    }
+}
+
+#[test]
+fn local_region_constraints_are_dropped() {
+    test! {
+        program {
+            trait Bar { }
+            struct Foo { }
+
+            // `'x` and `'y` only appear in the where clause, so the
+            // constraint `'x: 'y` is about lifetimes the caller cannot see.
+            impl<'x, 'y> Bar for Foo where 'x: 'y { }
+        }
+
+        goal {
+            Foo: Bar
+        } yields {
+            "Unique; substitution [], lifetime constraints []"
+        }
+    }
+}
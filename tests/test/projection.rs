@@ -8,7 +8,6 @@ fn normalize_basic() {
         program {
             trait Iterator { type Item; }
             struct Vec<T> { }
-            struct u32 { }
             impl<T> Iterator for Vec<T> {
                 type Item = T;
             }
@@ -98,7 +97,6 @@ fn normalize_into_iterator() {
             trait IntoIterator { type Item; }
             trait Iterator { type Item; }
             struct Vec<T> { }
-            struct u32 { }
             impl<T> IntoIterator for Vec<T> {
                 type Item = T;
             }
@@ -129,7 +127,6 @@ fn projection_equality() {
             trait Trait2<T> { }
             impl<T, U> Trait2<T> for U where U: Trait1<Type = T> {}
 
-            struct u32 {}
             struct S {}
             impl Trait1 for S {
                 type Type = u32;
@@ -168,10 +165,9 @@ fn normalize_gat1() {
             }
 
             struct Iter<'a, T> { }
-            struct Ref<'a, T> { }
 
             impl<'a, T> Iterator for Iter<'a, T> {
-                type Item = Ref<'a, T>;
+                type Item = &'a T;
             }
         }
 
@@ -196,7 +192,6 @@ fn normalize_gat2() {
             trait StreamingIterator<T> { type Item<'a>; }
             struct Span<'a, T> { }
             struct StreamIterMut<T> { }
-            struct u32 { }
             impl<T> StreamingIterator<T> for StreamIterMut<T> {
                 type Item<'a> = Span<'a, T>;
             }
@@ -281,7 +276,6 @@ fn normalize_gat_with_where_clause2() {
                 type Item<U> where U: Bar<T>;
             }
 
-            struct i32 { }
             impl<T> Foo<T> for i32 {
                 type Item<U> = U;
             }
@@ -316,7 +310,6 @@ fn normalize_gat_with_higher_ranked_trait_bound() {
     test! {
         program {
             trait Foo<'a, T> { }
-            struct i32 { }
 
             trait Bar<'a, T> {
                 type Item<V>: Foo<'a, T> where forall<'b> V: Foo<'b, T>;
@@ -594,7 +587,6 @@ fn projection_from_env_slow() {
             struct Slice<T> where T: Sized { }
             impl<T> Sized for Slice<T> { }
 
-            struct u32 { }
             impl Clone for u32 { }
             impl Sized for u32 { }
 
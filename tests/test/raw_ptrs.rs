@@ -0,0 +1,176 @@
+//! Tests targeting the built-in raw pointer types `*const T` and `*mut T`
+
+use super::*;
+
+#[test]
+fn unify_raw_ptrs() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            exists<T> {
+                *const T = *const Foo
+            }
+        } yields {
+            "Unique; substitution [?0 := Foo], lifetime constraints []"
+        }
+
+        goal {
+            *const Foo = *mut Foo
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<'a> {
+                *const Foo = &'a Foo
+            }
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn raw_ptrs_are_copy_and_sized() {
+    test! {
+        program {
+            #[lang(clone)] trait Clone { }
+            #[lang(copy)] trait Copy where Self: Clone { }
+            #[lang(sized)] trait Sized { }
+            struct Foo { }
+        }
+
+        goal {
+            *mut Foo: Copy
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                *const T: Clone
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            *const str: Sized
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn raw_ptrs_are_not_send_or_sync() {
+    test! {
+        program {
+            #[auto] #[lang(send)] trait Send { }
+            #[auto] #[lang(sync)] trait Sync { }
+            #[auto] trait Unpin { }
+            struct Foo { }
+            struct Bar { }
+            struct Wrapper { ptr: *mut Foo }
+            struct Unwrapped { ptr: *mut Foo }
+
+            unsafe impl Send for Wrapper { }
+            unsafe impl Send for *const Bar { }
+        }
+
+        goal {
+            *const Foo: Send
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            *mut Foo: Sync
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            Unwrapped: Send
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            Wrapper: Send
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            Wrapper: Sync
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            *const Bar: Send
+        } yields {
+            "Unique"
+        }
+
+        // Other auto traits look through the pointer as usual.
+        goal {
+            *mut Foo: Unpin
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn raw_ptrs_are_not_fundamental() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            IsLocal(*const Foo)
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            IsUpstream(*mut Foo)
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                WellFormed(*const T)
+            }
+        } yields {
+            "Unique"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[upstream] trait Remote { }
+            struct Local { }
+
+            impl Remote for *const Local { }
+        } error_msg {
+            "impl for trait \"Remote\" violates the orphan rules"
+        }
+    }
+
+    lowering_success! {
+        program {
+            #[upstream] trait Remote<T> { }
+            struct Local { }
+
+            impl Remote<*const Local> for Local { }
+        }
+    }
+}
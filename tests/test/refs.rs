@@ -0,0 +1,215 @@
+//! Tests targeting the built-in reference types `&'a T` and `&'a mut T`
+
+use super::*;
+
+#[test]
+fn unify_refs() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            forall<'a> {
+                exists<T> {
+                    &'a T = &'a Foo
+                }
+            }
+        } yields {
+            "Unique; substitution [?0 := Foo], lifetime constraints []"
+        }
+
+        goal {
+            forall<'a> {
+                exists<T> {
+                    &'a mut T = &'a Foo
+                }
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<'a, 'b> {
+                &'a Foo = &'b Foo
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_1 == '!1_0 }]"
+        }
+    }
+}
+
+#[test]
+fn shared_refs_are_copy() {
+    test! {
+        program {
+            #[lang(clone)] trait Clone { }
+            #[lang(copy)] trait Copy where Self: Clone { }
+
+            struct Foo { }
+        }
+
+        goal {
+            forall<'a, T> {
+                &'a T: Copy
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<'a, T> {
+                &'a T: Clone
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<'a> {
+                &'a mut Foo: Copy
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<'a> {
+                &'a mut Foo: Clone
+            }
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn refs_copy_needs_lang_items() {
+    // Traits are recognized by their `#[lang(..)]` attribute, not by name.
+    test! {
+        program {
+            trait Clone { }
+            trait Copy where Self: Clone { }
+        }
+
+        goal {
+            forall<'a, T> {
+                &'a T: Copy
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<'a, T> {
+                &'a T: Clone
+            }
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn refs_auto_traits() {
+    test! {
+        program {
+            #[auto] #[lang(send)] trait Send { }
+
+            struct Foo { }
+            struct Rc<T> { }
+
+            impl<T> !Send for Rc<T> { }
+        }
+
+        goal {
+            forall<'a> {
+                &'a Foo: Send
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<'a> {
+                &'a mut Rc<Foo>: Send
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<'a, T> {
+                if (T: Send) {
+                    &'a T: Send
+                }
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn refs_are_well_formed() {
+    test! {
+        program {
+            trait Eq { }
+            struct Set<T> where T: Eq { }
+            struct Foo { }
+        }
+
+        // The referent must outlive the reference.
+        goal {
+            forall<'a, T> {
+                WellFormed(&'a mut T)
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: !1_1: '!1_0 }]"
+        }
+
+        // Only the reference itself is checked here, not its referent,
+        // which need only outlive it.
+        goal {
+            forall<'a> {
+                WellFormed(&'a Set<Foo>)
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: Set<Foo>: '!1_0 }]"
+        }
+    }
+}
+
+#[test]
+fn refs_are_fundamental() {
+    test! {
+        program {
+            #[upstream] struct Upstream { }
+            struct Local { }
+        }
+
+        goal {
+            forall<'a> {
+                IsLocal(&'a Local)
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<'a> {
+                IsLocal(&'a mut Upstream)
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<'a> {
+                IsUpstream(&'a Upstream)
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
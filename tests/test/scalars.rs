@@ -0,0 +1,125 @@
+//! Tests targeting the built-in scalar types like `i32` and `bool`
+
+use super::*;
+
+#[test]
+fn unify_scalars() {
+    test! {
+        program { }
+
+        goal {
+            i32 = i32
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            i32 = u32
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            exists<T> {
+                (bool, T) = (bool, f64)
+            }
+        } yields {
+            "Unique; substitution [?0 := f64], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn scalars_are_copy_clone_sized() {
+    test! {
+        program {
+            #[lang(clone)] trait Clone { }
+            #[lang(copy)] trait Copy where Self: Clone { }
+            #[lang(sized)] trait Sized { }
+        }
+
+        goal {
+            i32: Copy
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            char: Clone
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            u128: Sized
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            (bool, f32, usize): Copy
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn scalars_auto_traits() {
+    test! {
+        program {
+            #[auto] #[lang(send)] trait Send { }
+            #[auto] #[lang(sync)] trait Sync { }
+        }
+
+        goal {
+            u8: Send
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            isize: Sync
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn scalars_are_upstream() {
+    test! {
+        program { }
+
+        goal {
+            IsUpstream(i64)
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            IsLocal(bool)
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            WellFormed(char)
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn scalars_orphan_check() {
+    lowering_error! {
+        program {
+            #[upstream] trait Remote { }
+
+            impl Remote for u16 { }
+        } error_msg {
+            "impl for trait \"Remote\" violates the orphan rules"
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Tests targeting the built-in `Sized` impls and implicit `Sized` bounds
+
+use super::*;
+
+#[test]
+fn structs_are_sized_if_last_field_is() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait Foo { }
+
+            struct Empty { }
+            struct Tail<T> where T: ?Sized {
+                len: usize,
+                data: T
+            }
+            enum Option<T> {
+                None,
+                Some(T)
+            }
+        }
+
+        goal {
+            Empty: Sized
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            Tail<u32>: Sized
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            Tail<[u32]>: Sized
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<T> {
+                Option<T>: Sized
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            dyn Foo: Sized
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn type_parameters_are_implicitly_sized() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            struct Vec<T> { }
+            struct Box<T> where T: ?Sized { }
+        }
+
+        goal {
+            forall<T> {
+                if (FromEnv(Vec<T>)) {
+                    T: Sized
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                if (FromEnv(Box<T>)) {
+                    T: Sized
+                }
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            WellFormed(Vec<str>)
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            WellFormed(Box<str>)
+        } yields {
+            "Unique"
+        }
+    }
+}
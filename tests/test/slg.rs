@@ -89,7 +89,6 @@ fn basic() {
         program {
             trait Sized { }
 
-            struct i32 { }
             impl Sized for i32 { }
         }
 
@@ -118,7 +117,6 @@ fn breadth_first() {
         program {
             trait Sized { }
 
-            struct i32 { }
             impl Sized for i32 { }
 
             struct Vec<T> { }
@@ -259,7 +257,6 @@ fn only_draw_so_many() {
             struct Vec<T> { }
             impl<T> Sized for Vec<T> where T: Sized { }
 
-            struct i32 { }
             impl Sized for i32 { }
 
             struct Slice<T> { }
@@ -285,7 +282,6 @@ fn only_draw_so_many_blow_up() {
             impl<T> Sized for Vec<T> where T: Sized { }
             impl<T> Foo for Vec<T> where T: Sized { }
 
-            struct i32 { }
             impl Sized for i32 { }
 
             struct Slice<T> { }
@@ -308,7 +304,6 @@ fn negative_loop() {
         program {
             trait P { }
             trait Q { }
-            struct u32 { }
 
             forall<> { u32: P if not { u32: Q } }
             forall<> { u32: Q if not { u32: P } }
@@ -330,7 +325,6 @@ fn subgoal_cycle_uninhabited() {
             trait Foo { }
             struct Box<T> { }
             struct Vec<T> { }
-            struct u32 { }
             impl<T> Foo for Box<T> where Box<Vec<T>>: Foo { }
         }
 
@@ -431,7 +425,6 @@ fn subgoal_cycle_inhabited() {
             trait Foo { }
             struct Box<T> { }
             struct Vec<T> { }
-            struct u32 { }
             impl<T> Foo for Box<T> where Box<Vec<T>>: Foo { }
             impl Foo for u32 { }
         }
@@ -461,7 +454,6 @@ fn basic_region_constraint_from_positive_impl() {
         program {
             trait Foo { }
             struct Ref<'a, 'b, T> { }
-            struct u32 { }
             impl<'x, T> Foo for Ref<'x, 'x, T> { }
         }
 
@@ -649,7 +641,6 @@ fn contradiction() {
     test! {
         program {
             trait P { }
-            struct u32 { }
 
             forall<> { u32: P if not { u32: P } }
         }
@@ -910,7 +901,6 @@ fn negative_answer_ambiguous() {
         program {
             trait P { }
             trait Q { }
-            struct u32 { }
 
             forall<> { u32: P if not { u32: Q } }
             forall<> { u32: Q if not { u32: Q } }
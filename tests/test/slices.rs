@@ -0,0 +1,256 @@
+//! Tests targeting the built-in slice, array and `str` types
+
+use super::*;
+
+#[test]
+fn unify_slices_and_arrays() {
+    test! {
+        program {
+            struct Foo { }
+            struct Bar { }
+        }
+
+        goal {
+            exists<T> {
+                [T] = [Foo]
+            }
+        } yields {
+            "Unique; substitution [?0 := Foo], lifetime constraints []"
+        }
+
+        goal {
+            [Foo] = [Foo; 3]
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            exists<T, const N: usize> {
+                [T; N] = [Bar; 3]
+            }
+        } yields {
+            "Unique; substitution [?0 := Bar, ?1 := 3], lifetime constraints []"
+        }
+
+        goal {
+            [Foo; 3] = [Foo; 4]
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            exists<T> {
+                T = str
+            }
+        } yields {
+            "Unique; substitution [?0 := str], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn slices_and_str_are_not_sized() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+            struct Foo { }
+        }
+
+        goal {
+            [Foo]: Sized
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            str: Sized
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            [Foo; 3]: Sized
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<const N: usize> {
+                [Foo; N]: Sized
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn elements_must_be_sized() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+            struct Foo { }
+        }
+
+        goal {
+            WellFormed([Foo])
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            WellFormed([[Foo]])
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            WellFormed([[Foo]; 3])
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<T> {
+                if (FromEnv([T])) {
+                    T: Sized
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T, const N: usize> {
+                if (T: Sized) {
+                    WellFormed([T; N])
+                }
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn arrays_are_copy_if_element_is() {
+    test! {
+        program {
+            #[lang(clone)] trait Clone { }
+            #[lang(copy)] trait Copy where Self: Clone { }
+            struct Foo { }
+            struct Bar { }
+            impl Clone for Foo { }
+            impl Copy for Foo { }
+        }
+
+        goal {
+            [Foo; 3]: Copy
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<const N: usize> {
+                [Foo; N]: Clone
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            [Bar; 3]: Copy
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            [Foo]: Copy
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            str: Clone
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn slices_and_arrays_auto_traits() {
+    test! {
+        program {
+            #[auto] #[lang(send)] trait Send { }
+            struct Foo { }
+            struct Bar { }
+            impl !Send for Bar { }
+        }
+
+        goal {
+            [Foo]: Send
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            [Bar; 2]: Send
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<T> {
+                if (T: Send) {
+                    [T]: Send
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            str: Send
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn slices_and_arrays_are_upstream() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            IsUpstream([Foo])
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            IsUpstream(str)
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<const N: usize> {
+                IsLocal([Foo; N])
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<T, const N: usize> {
+                WellFormed([T; N])
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
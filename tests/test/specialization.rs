@@ -0,0 +1,134 @@
+//! Tests for normalizing associated types whose values are given by
+//! impls that specialize one another.
+
+use super::*;
+
+#[test]
+fn normalize_to_most_specialized_value() {
+    test! {
+        program {
+            struct Foo { }
+            struct Bar { }
+            struct Baz { }
+
+            trait Iterator {
+                type Item;
+            }
+
+            impl<T> Iterator for T {
+                default type Item = Foo;
+            }
+
+            impl Iterator for Bar {
+                type Item = Bar;
+            }
+        }
+
+        goal {
+            exists<U> {
+                Normalize(<Bar as Iterator>::Item -> U)
+            }
+        } yields {
+            "Unique; substitution [?0 := Bar]"
+        }
+
+        goal {
+            <Bar as Iterator>::Item = Bar
+        } yields {
+            "Unique"
+        }
+
+        // The `default type` may be overridden by impls that
+        // specialize the impl it is in, so it is opaque.
+        goal {
+            exists<U> {
+                Normalize(<Baz as Iterator>::Item -> U)
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            <Baz as Iterator>::Item = Foo
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<T> {
+                exists<U> {
+                    Normalize(<T as Iterator>::Item -> U)
+                }
+            }
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn default_value_in_final_impl() {
+    test! {
+        program {
+            struct Foo { }
+            struct Bar { }
+
+            trait Iterator {
+                type Item;
+            }
+
+            impl<T> Iterator for T {
+                default type Item = Foo;
+            }
+        }
+
+        goal {
+            exists<U> {
+                Normalize(<Bar as Iterator>::Item -> U)
+            }
+        } yields {
+            "Unique; substitution [?0 := Foo]"
+        }
+
+        goal {
+            forall<T> {
+                <T as Iterator>::Item = Foo
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn inherited_default_value() {
+    test! {
+        program {
+            struct Foo { }
+            struct Bar { }
+
+            trait Clone { }
+            impl Clone for Bar { }
+
+            trait Iterator {
+                type Item;
+            }
+
+            impl<T> Iterator for T {
+                default type Item = Foo;
+            }
+
+            // This impl does not give `Item` a value of its own; the
+            // value it inherits is still `default`, and so opaque.
+            impl<T> Iterator for T where T: Clone { }
+        }
+
+        goal {
+            exists<U> {
+                Normalize(<Bar as Iterator>::Item -> U)
+            }
+        } yields {
+            "No possible solution"
+        }
+    }
+}
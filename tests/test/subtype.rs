@@ -0,0 +1,201 @@
+//! Tests for subtyping goals (`A <: B`), which relate the parameters of
+//! types according to their variance.
+
+use super::*;
+
+#[test]
+fn subtype_refs() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            forall<'a, 'b> {
+                &'a Foo <: &'b Foo
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_0: '!1_1 }]"
+        }
+
+        goal {
+            forall<'a, 'b, 'c> {
+                &'a &'b Foo <: &'a &'c Foo
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_1: '!1_2 }]"
+        }
+
+        goal {
+            forall<'a, 'b, 'c> {
+                &'a mut &'b Foo <: &'a mut &'c Foo
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_2 == '!1_1 }]"
+        }
+
+        goal {
+            forall<'a> {
+                &'a Foo <: &'a Foo
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn subtype_fn_ptrs() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            forall<'a, 'b> {
+                fn(&'a Foo) <: fn(&'b Foo)
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_1: '!1_0 }]"
+        }
+    }
+}
+
+#[test]
+fn subtype_struct_variance() {
+    test! {
+        program {
+            struct Foo { }
+            struct Covariant<'a, T> { field: &'a T }
+            struct Contravariant<'a> { field: fn(&'a Foo) }
+            struct Unused<'a> { }
+            struct List<'a> { head: &'a Foo, tail: Option<List<'a>> }
+            struct Option<T> { value: T }
+        }
+
+        goal {
+            forall<'a, 'b> {
+                Covariant<'a, Foo> <: Covariant<'b, Foo>
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_0: '!1_1 }]"
+        }
+
+        goal {
+            forall<'a, 'b> {
+                Contravariant<'a> <: Contravariant<'b>
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_1: '!1_0 }]"
+        }
+
+        goal {
+            forall<'a, 'b> {
+                Unused<'a> <: Unused<'b>
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_1 == '!1_0 }]"
+        }
+
+        goal {
+            forall<'a, 'b> {
+                List<'a> <: List<'b>
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_0: '!1_1 }]"
+        }
+
+        goal {
+            forall<'a> {
+                Covariant<'a, Foo> <: Contravariant<'a>
+            }
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn subtype_enum_variance() {
+    test! {
+        program {
+            struct U8 { }
+            enum Opt<T> { None, Some(T) }
+            enum Either<'a, 'b> { Left(&'a U8), Right(fn(&'b U8)) }
+            struct Node<'a> { next: Link<'a> }
+            enum Link<'a> { Nil, Cons(&'a U8, Opt<Node<'a>>) }
+        }
+
+        goal {
+            forall<'a, 'b> {
+                Opt<&'a U8> <: Opt<&'b U8>
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_0: '!1_1 }]"
+        }
+
+        goal {
+            forall<'a, 'b, 'c, 'd> {
+                Either<'a, 'c> <: Either<'b, 'd>
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_0: '!1_1 }, InEnvironment { environment: Env([]), goal: '!1_3: '!1_2 }]"
+        }
+
+        goal {
+            forall<'a, 'b> {
+                Node<'a> <: Node<'b>
+            }
+        } yields {
+            "Unique; substitution [], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_0: '!1_1 }]"
+        }
+    }
+}
+
+#[test]
+fn subtype_infers_types() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            exists<T> {
+                forall<'a> {
+                    &'a T <: &'a Foo
+                }
+            }
+        } yields {
+            "Unique; substitution [?0 := Foo], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn subtype_bound_inference_variables() {
+    test! {
+        program {
+            struct Foo { }
+        }
+
+        goal {
+            forall<'a, 'b> {
+                exists<T> {
+                    T = &'a Foo, T <: &'b Foo
+                }
+            }
+        } yields {
+            "Unique; substitution [?0 := &'!1_0 Foo], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_0: '!1_1 }]"
+        }
+
+        goal {
+            forall<'a, 'b> {
+                exists<T> {
+                    T <: &'b Foo, T = &'a Foo
+                }
+            }
+        } yields {
+            "Unique; substitution [?0 := &'!1_0 Foo], lifetime constraints [InEnvironment { environment: Env([]), goal: '!1_0: '!1_1 }]"
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Tests for trait aliases (`trait Alias = Foo + Bar;`), which are
+//! implemented exactly when the bounds they stand for hold.
+
+use super::*;
+
+#[test]
+fn trait_alias_is_implemented_by_components() {
+    test! {
+        program {
+            trait Foo<T> { }
+            trait Bar { }
+            trait Baz { }
+            trait Alias<T> = Foo<T> + Bar where T: Baz;
+
+            struct A { }
+            struct B { }
+            struct C { }
+
+            impl Foo<B> for A { }
+            impl Bar for A { }
+            impl Baz for B { }
+
+            impl Foo<C> for A { }
+        }
+
+        goal {
+            A: Alias<B>
+        } yields {
+            "Unique"
+        }
+
+        // `C: Baz` does not hold
+        goal {
+            A: Alias<C>
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            exists<T> { A: Alias<T> }
+        } yields {
+            "Unique; substitution [?0 := B]"
+        }
+
+        goal {
+            B: Alias<B>
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn trait_alias_implies_components() {
+    test! {
+        program {
+            trait Foo { }
+            trait Bar { }
+            trait Alias = Foo + Bar;
+        }
+
+        goal {
+            forall<T> {
+                if (T: Alias) {
+                    T: Foo
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                if (T: Foo; T: Bar) {
+                    T: Alias
+                }
+            }
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                if (T: Foo) {
+                    T: Alias
+                }
+            }
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn nested_trait_aliases() {
+    test! {
+        program {
+            trait Foo { }
+            trait Bar { }
+            trait Inner = Foo;
+            trait Outer = Inner + Bar;
+
+            struct A { }
+            impl Foo for A { }
+            impl Bar for A { }
+        }
+
+        goal {
+            A: Outer
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<T> {
+                if (T: Outer) {
+                    T: Foo
+                }
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn dyn_trait_alias() {
+    test! {
+        program {
+            trait Foo<T> { }
+            trait Bar { }
+            trait Baz { }
+            trait Inner = Bar;
+            trait Alias<T> = Foo<T> + Inner;
+
+            struct A { }
+        }
+
+        goal {
+            dyn Alias<A>: Foo<A>
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            dyn Alias<A>: Bar
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            dyn Alias<A>: Alias<A>
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            dyn Alias<A>: Baz
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            impl Alias<A>: Bar
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            forall<'a> {
+                dyn Alias<&'a A>: Foo<&'a A>
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
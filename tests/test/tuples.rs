@@ -0,0 +1,199 @@
+//! Tests targeting the built-in tuple types like `(A, B)` and `()`
+
+use super::*;
+
+#[test]
+fn unify_tuples() {
+    test! {
+        program {
+            struct Foo { }
+            struct Bar { }
+        }
+
+        goal {
+            exists<T> {
+                (Foo, T) = (Foo, Bar)
+            }
+        } yields {
+            "Unique; substitution [?0 := Bar], lifetime constraints []"
+        }
+
+        goal {
+            exists<T> {
+                (Foo, Bar) = (T,)
+            }
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            exists<T> {
+                T = ()
+            }
+        } yields {
+            "Unique; substitution [?0 := ()], lifetime constraints []"
+        }
+
+        goal {
+            exists<T> {
+                T = (Foo, (Bar,))
+            }
+        } yields {
+            "Unique; substitution [?0 := (Foo, (Bar,))], lifetime constraints []"
+        }
+    }
+}
+
+#[test]
+fn tuples_auto_traits() {
+    test! {
+        program {
+            #[auto] #[lang(send)] trait Send { }
+
+            struct Foo { }
+            struct Rc<T> { }
+
+            impl<T> !Send for Rc<T> { }
+        }
+
+        goal {
+            (): Send
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            (Foo, (Foo,)): Send
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            (Foo, Rc<Foo>): Send
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            forall<T> {
+                if (T: Send) {
+                    (T, Foo): Send
+                }
+            }
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn tuples_are_copy_and_clone() {
+    test! {
+        program {
+            #[lang(clone)] trait Clone { }
+            #[lang(copy)] trait Copy where Self: Clone { }
+
+            struct Foo { }
+            struct Bar { }
+
+            impl Clone for Foo { }
+            impl Copy for Foo { }
+            impl Clone for Bar { }
+        }
+
+        goal {
+            (): Copy
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            (Foo, Foo): Copy
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            (Foo, Bar): Copy
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            (Foo, Bar): Clone
+        } yields {
+            "Unique"
+        }
+    }
+}
+
+#[test]
+fn tuples_are_sized() {
+    test! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            struct Foo { }
+        }
+
+        goal {
+            (): Sized
+        } yields {
+            "Unique"
+        }
+
+        // Only the last field of a tuple may be unsized, and a tuple
+        // with an unsized last field is unsized itself. As for structs,
+        // the other fields are checked by well-formedness, not `Sized`.
+        goal {
+            (str, Foo): Sized
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            (Foo, str): Sized
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            WellFormed((Foo, str))
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            WellFormed((str, Foo))
+        } yields {
+            "No possible solution"
+        }
+    }
+}
+
+#[test]
+fn tuples_are_upstream() {
+    test! {
+        program {
+            struct Local { }
+        }
+
+        goal {
+            IsLocal((Local,))
+        } yields {
+            "No possible solution"
+        }
+
+        goal {
+            IsUpstream((Local, Local))
+        } yields {
+            "Unique"
+        }
+
+        goal {
+            WellFormed((Local, Local))
+        } yields {
+            "Unique"
+        }
+    }
+}
@@ -138,7 +138,7 @@ fn equality_binder() {
         } yields {
             "Unique; for<?U1> { \
                  substitution [?0 := '^0], \
-                 lifetime constraints [InEnvironment { environment: Env([]), goal: '!2_0 == '^0 }] \
+                 lifetime constraints [InEnvironment { environment: Env([]), goal: '^0 == '!2_0 }] \
              }"
         }
     }
@@ -256,25 +256,25 @@ fn quantified_types() {
     test! {
         program {
             trait Foo { }
-            struct fn<'a> { }
-            struct fn2<'a, 'b> { }
-            impl Foo for for<'a> fn<'a> { }
+            struct Func<'a> { }
+            struct Func2<'a, 'b> { }
+            impl Foo for for<'a> Func<'a> { }
         }
 
         goal {
-            for<'a> fn<'a>: Foo
+            for<'a> Func<'a>: Foo
         } yields {
             "Unique"
         }
 
         goal {
-            for<'a, 'b> fn2<'a, 'b> = for<'b, 'a> fn2<'a, 'b>
+            for<'a, 'b> Func2<'a, 'b> = for<'b, 'a> Func2<'a, 'b>
         } yields {
             "Unique"
         }
 
         goal {
-            forall<'a> { fn<'a>: Foo }
+            forall<'a> { Func<'a>: Foo }
         } yields {
             // Lifetime constraints are unsatisfiable
             "Unique; substitution [], \
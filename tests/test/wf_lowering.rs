@@ -7,8 +7,6 @@ fn well_formed_trait_decl() {
             trait Clone { }
             trait Copy where Self: Clone { }
 
-            struct i32 { }
-
             impl Clone for i32 { }
             impl Copy for i32 { }
         }
@@ -22,9 +20,9 @@ fn ill_formed_trait_decl() {
             trait Clone { }
             trait Copy where Self: Clone { }
 
-            struct i32 { }
+            struct Foo { }
 
-            impl Copy for i32 { }
+            impl Copy for Foo { }
         } error_msg {
             "trait impl for \"Copy\" does not meet well-formedness requirements"
         }
@@ -94,8 +92,6 @@ fn ill_formed_assoc_ty() {
             trait Foo { }
             struct OnlyFoo<T> where T: Foo { }
 
-            struct i32 { }
-
             trait Bar {
                 type Value;
             }
@@ -149,6 +145,37 @@ fn ill_formed_ty_decl() {
     }
 }
 
+#[test]
+fn ill_formed_enum_decl() {
+    lowering_error! {
+        program {
+            trait Hash { }
+            struct Set<K> where K: Hash { }
+
+            enum MyEnum<K> {
+                Empty,
+                Tuple(K),
+                Named { value: Set<K> }
+            }
+        } error_msg {
+            "type declaration \"MyEnum\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_success! {
+        program {
+            trait Hash { }
+            struct Set<K> where K: Hash { }
+
+            enum MyEnum<K> where K: Hash {
+                Empty,
+                Tuple(K),
+                Named { value: Set<K> }
+            }
+        }
+    }
+}
+
 #[test]
 fn implied_bounds_on_ty_decl() {
     lowering_success! {
@@ -442,7 +469,6 @@ fn higher_ranked_trait_bounds() {
         program {
             trait Foo<'a> { }
             trait Bar where forall<'a> Self: Foo<'a> { }
-            struct i32 { }
 
             impl Bar for i32 { }
         } error_msg {
@@ -454,7 +480,6 @@ fn higher_ranked_trait_bounds() {
         program {
             trait Foo<'a> { }
             trait Bar where forall<'a> Self: Foo<'a> { }
-            struct i32 { }
 
             impl<'a> Foo<'a> for i32 { }
             impl Bar for i32 { }
@@ -467,7 +492,6 @@ fn higher_ranked_trait_bound_on_gat() {
     lowering_success! {
         program {
             trait Foo<'a> { }
-            struct i32 { }
 
             trait Bar<'a> {
                 type Item<V>: Foo<'a> where forall<'b> V: Foo<'b>;
@@ -524,18 +548,17 @@ fn higher_ranked_inline_bound_on_gat() {
         program {
             trait Fn<T> { }
             struct Ref<'a, T> { }
-            struct i32 {}
 
-            struct fn<T> { }
+            struct Func<T> { }
 
-            impl<'a, T> Fn<Ref<'a, T>> for for<'b> fn<Ref<'b, T>> { }
+            impl<'a, T> Fn<Ref<'a, T>> for for<'b> Func<Ref<'b, T>> { }
 
             trait Bar {
                 type Item<T>: forall<'a> Fn<Ref<'a, T>>;
             }
 
             impl Bar for i32 {
-                type Item<T> = for<'a> fn<Ref<'a, T>>;
+                type Item<T> = for<'a> Func<Ref<'a, T>>;
             }
         }
     }
@@ -543,18 +566,17 @@ fn higher_ranked_inline_bound_on_gat() {
     lowering_error! {
         program {
             trait Fn<T, U> { }
-            struct i32 {}
 
-            struct fn<T, U> { }
+            struct Func<T, U> { }
 
-            impl<T, U> Fn<T, U> for fn<T, U> { }
+            impl<T, U> Fn<T, U> for Func<T, U> { }
 
             trait Bar {
                 type Item<T>: forall<U> Fn<T, U>;
             }
 
             impl Bar for i32 {
-                type Item<T> = fn<T, i32>;
+                type Item<T> = Func<T, i32>;
             }
         } error_msg {
             "trait impl for \"Bar\" does not meet well-formedness requirements"
@@ -575,9 +597,6 @@ fn assoc_type_recursive_bound() {
                 type Item: Sized where <Self as Foo>::Item: Sized;
             }
 
-            struct i32 { }
-            struct str { } // not sized
-
             impl Foo for i32 {
                 // Well-formedness checks require that the following
                 // goal is true:
@@ -636,3 +655,355 @@ fn assoc_type_recursive_bound() {
         }
     }
 }
+
+#[test]
+fn unsized_fields() {
+    lowering_success! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            struct Tail<T> where T: ?Sized {
+                len: usize,
+                data: T
+            }
+            struct Str {
+                data: Tail<str>
+            }
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            struct Foo {
+                data: str,
+                len: usize
+            }
+        } error_msg {
+            "type declaration \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            struct Tail<T> where T: ?Sized {
+                data: T
+            }
+            struct Foo {
+                data: Tail<str>,
+                len: usize
+            }
+        } error_msg {
+            "type declaration \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            enum Foo<T> where T: ?Sized {
+                A(T)
+            }
+        } error_msg {
+            "type declaration \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            struct Foo {
+                data: [[u8]; 2]
+            }
+        } error_msg {
+            "type declaration \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+
+            struct Foo {
+                data: Box<(str, u8)>
+            }
+            struct Box<T> where T: ?Sized { }
+        } error_msg {
+            "type declaration \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+}
+
+#[test]
+fn implicit_sized_bounds() {
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait Foo {
+                type Item;
+            }
+
+            struct Vec<T> { }
+            struct Box<T> where T: ?Sized { }
+            impl<T> Foo for Box<T> where T: ?Sized {
+                type Item = Vec<T>;
+            }
+        } error_msg {
+            "trait impl for \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait AsRef<T> { }
+
+            impl AsRef<str> for str { }
+        } error_msg {
+            "trait impl for \"AsRef\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_success! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait AsRef<T> where T: ?Sized { }
+
+            impl AsRef<str> for str { }
+        }
+    }
+}
+
+#[test]
+fn assoc_type_defaults() {
+    lowering_success! {
+        program {
+            trait Clone { }
+            struct Vec<T> { }
+            impl<T> Clone for Vec<T> where T: Clone { }
+
+            trait Foo where Self: Clone {
+                type Item: Clone = Vec<Self>;
+            }
+
+            struct Bar { }
+            impl Clone for Bar { }
+            impl Foo for Bar { }
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Clone { }
+            struct Vec<T> { }
+
+            trait Foo {
+                type Item: Clone = Vec<Self>;
+            }
+
+            struct Bar { }
+            impl Foo for Bar { }
+        } error_msg {
+            "trait impl for \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_success! {
+        program {
+            trait Clone { }
+            struct Vec<T> { }
+
+            trait Foo {
+                type Item: Clone = Vec<Self>;
+            }
+
+            // The default does not meet the bounds, but it is not used.
+            struct Bar { }
+            impl Clone for Bar { }
+            impl Foo for Bar {
+                type Item = Bar;
+            }
+        }
+    }
+}
+
+#[test]
+fn object_unsafe_trait_objects() {
+    lowering_success! {
+        program {
+            trait Clone { }
+            struct Box<T> { }
+            struct Foo { field: Box<dyn Clone> }
+            impl Clone for Box<dyn Clone> { }
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait Unsized: Sized { }
+            struct Box<T> { }
+            struct Foo { field: Box<dyn Unsized> }
+        } error_msg {
+            "the trait \"Unsized\" cannot be made into an object"
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Clone { }
+            trait Lending { type Item<'a>; }
+            struct Box<T> { }
+            impl<'a> Clone for Box<dyn Lending<Item<'a> = ()>> { }
+        } error_msg {
+            "the trait \"Lending\" cannot be made into an object"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait Unsized: Sized { }
+            struct Foo { field: for<'a> fn(&'a dyn Unsized) }
+        } error_msg {
+            "the trait \"Unsized\" cannot be made into an object"
+        }
+    }
+
+    lowering_error! {
+        program {
+            #[lang(sized)] trait Sized { }
+            trait Unsized: Sized { }
+            trait Iterator { type Item; }
+            struct Box<T> { }
+            struct Foo { }
+            impl Iterator for Foo {
+                type Item = Box<dyn Unsized>;
+            }
+        } error_msg {
+            "the trait \"Unsized\" cannot be made into an object"
+        }
+    }
+}
+
+#[test]
+fn ill_formed_trait_decls() {
+    lowering_success! {
+        program {
+            trait Clone { }
+            trait Bar { }
+            trait Into<T> { }
+            struct Vec<T> where T: Clone { }
+
+            trait Foo where Self: Clone, Vec<Self>: Bar { }
+
+            trait Baz<T> where T: Clone {
+                type Item: Into<Vec<T>>;
+                type Assoc<U>: Bar where Vec<U>: Bar, U: Clone;
+            }
+
+            trait HigherRanked where forall<T> Vec<T>: Bar, forall<T> T: Clone { }
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Clone { }
+            trait Bar { }
+            struct Vec<T> where T: Clone { }
+
+            trait Foo where Vec<Self>: Bar { }
+        } error_msg {
+            "trait declaration \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Clone { }
+            trait Bar<T> { }
+            struct Vec<T> where T: Clone { }
+
+            trait Foo<T> {
+                type Item: Bar<Vec<T>>;
+            }
+        } error_msg {
+            "trait declaration \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Clone { }
+            trait Bar { }
+            struct Vec<T> where T: Clone { }
+
+            trait Foo {
+                type Item<T> where Vec<T>: Bar;
+            }
+        } error_msg {
+            "trait declaration \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Clone { }
+            trait Bar { }
+            struct Vec<T> where T: Clone { }
+
+            trait Foo where forall<T> Vec<T>: Bar { }
+        } error_msg {
+            "trait declaration \"Foo\" does not meet well-formedness requirements"
+        }
+    }
+}
+
+#[test]
+fn opaque_type_hidden_type_meets_bounds() {
+    lowering_success! {
+        program {
+            trait Clone { }
+            trait Iter { type Item; }
+            struct Bar<T> { }
+            impl<T> Clone for Bar<T> where T: Clone { }
+            impl<T> Iter for Bar<T> { type Item = T; }
+
+            opaque type Foo<T>: Iter<Item = T> = Bar<T>;
+            opaque type Refs<'a, T>: Iter<Item = &'a T> = Bar<&'a T>;
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Clone { }
+            trait Iter { type Item; }
+            struct A { }
+            struct B { }
+            struct Bar<T> { }
+            impl<T> Iter for Bar<T> { type Item = B; }
+
+            opaque type Foo<T>: Iter<Item = T> = Bar<T>;
+        } error_msg {
+            "hidden type of opaque type \"Foo\" does not meet its bounds"
+        }
+    }
+
+    lowering_error! {
+        program {
+            trait Clone { }
+            struct Bar<T> { }
+            impl<T> Clone for Bar<T> where T: Clone { }
+
+            // `Bar<T>` is only `Clone` if `T` is.
+            opaque type Foo<T>: Clone = Bar<T>;
+        } error_msg {
+            "hidden type of opaque type \"Foo\" does not meet its bounds"
+        }
+    }
+}
@@ -9,8 +9,9 @@ macro_rules! lowering_success {
         let program_text = stringify!($program);
         assert!(program_text.starts_with("{"));
         assert!(program_text.ends_with("}"));
-        let result = chalk_integration::db::ChalkDatabase::with(
+        let result = chalk_integration::db::ChalkDatabase::with_dir(
             &program_text[1..program_text.len() - 1],
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")),
             chalk_solve::SolverChoice::default(),
         )
         .checked_program();
@@ -26,8 +27,9 @@ macro_rules! lowering_error {
         let program_text = stringify!($program);
         assert!(program_text.starts_with("{"));
         assert!(program_text.ends_with("}"));
-        let error = chalk_integration::db::ChalkDatabase::with(
+        let error = chalk_integration::db::ChalkDatabase::with_dir(
             &program_text[1..program_text.len() - 1],
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")),
             chalk_solve::SolverChoice::default(),
         )
         .checked_program()